@@ -0,0 +1,248 @@
+//! Detection of regressions of a benchmark run against a baseline run.
+//!
+//! A benchmark regresses when its mean time grows by more than a threshold and Welch's t-test
+//! finds the growth significant.
+
+use crate::results::{self, BenchmarkResult, Components, Statistics};
+use std::fmt::Write;
+
+/// A benchmark of both runs at the same component values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+	pub pallet: String,
+	pub benchmark: String,
+	pub components: Components,
+	pub baseline: Statistics,
+	pub current: Statistics,
+	/// The relative change of the mean time, `0.1` for 10% slower.
+	pub change: f64,
+	/// The probability of a growth of the mean time at least as large without a regression,
+	/// or `None` with fewer than two samples in either run.
+	pub p_value: Option<f64>,
+	pub regressed: bool,
+}
+
+/// The comparison of the benchmarks of two runs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Diff {
+	pub comparisons: Vec<Comparison>,
+	/// The `pallet::benchmark [components]`s of the baseline missing in the current run.
+	pub missing: Vec<String>,
+	/// The `pallet::benchmark [components]`s new in the current run.
+	pub added: Vec<String>,
+}
+
+impl Diff {
+	pub fn regressions(&self) -> usize {
+		self.comparisons.iter().filter(|c| c.regressed).count()
+	}
+}
+
+/// `pallet::benchmark`, followed by the component values in brackets if there are any.
+fn name(pallet: &str, benchmark: &str, components: &Components) -> String {
+	if components.is_empty() {
+		format!("{pallet}::{benchmark}")
+	} else {
+		format!("{pallet}::{benchmark} [{}]", results::label(components))
+	}
+}
+
+/// A benchmark, the values of its components and its execution times at them.
+type Samples<'a> = (&'a BenchmarkResult, Components, Vec<f64>);
+
+/// The execution times of each component value combination of each benchmark.
+fn samples<'a>(results: impl Iterator<Item = &'a BenchmarkResult>) -> Vec<Samples<'a>> {
+	results
+		.flat_map(|result| {
+			result
+				.times()
+				.into_iter()
+				.map(move |(components, times)| (result, components, times))
+		})
+		.collect()
+}
+
+/// Whether both samples are of the same benchmark at the same component values.
+fn same((a, a_components, _): &Samples, (b, b_components, _): &Samples) -> bool {
+	a.pallet == b.pallet && a.benchmark == b.benchmark && a_components == b_components
+}
+
+/// Compares the benchmarks whose names contain `filter`, matched by pallet, name and component
+/// values. A benchmark regresses at some component values if its mean time grows by more than
+/// `threshold` with a p-value below `alpha`. Without enough samples for the test the threshold
+/// alone decides.
+pub fn compare(
+	baseline: &[BenchmarkResult],
+	current: &[BenchmarkResult],
+	filter: &str,
+	threshold: f64,
+	alpha: f64,
+) -> Diff {
+	let selected = |result: &&BenchmarkResult| result.benchmark.contains(filter);
+	let base_samples = samples(baseline.iter().filter(selected));
+	let new_samples = samples(current.iter().filter(selected));
+	let mut diff = Diff::default();
+	for base in &base_samples {
+		let (result, components, base_times) = base;
+		let new_times = match new_samples.iter().find(|new| same(base, new)) {
+			Some((_, _, new_times)) => new_times,
+			None => {
+				diff.missing.push(name(&result.pallet, &result.benchmark, components));
+				continue
+			},
+		};
+		let (baseline, current) = match (Statistics::of(base_times), Statistics::of(new_times)) {
+			(Some(baseline), Some(current)) => (baseline, current),
+			_ => {
+				diff.missing.push(name(&result.pallet, &result.benchmark, components));
+				continue
+			},
+		};
+		let change = current.mean / baseline.mean - 1.0;
+		let p_value = welch_p_value(base_times, new_times);
+		let significant = match p_value {
+			Some(p) => p < alpha,
+			None => true,
+		};
+		let regressed = change > threshold && significant;
+		diff.comparisons.push(Comparison {
+			pallet: result.pallet.clone(),
+			benchmark: result.benchmark.clone(),
+			components: components.clone(),
+			baseline,
+			current,
+			change,
+			p_value,
+			regressed,
+		});
+	}
+	diff.added = new_samples
+		.iter()
+		.filter(|new| !base_samples.iter().any(|base| same(base, new)))
+		.map(|(result, components, _)| name(&result.pallet, &result.benchmark, components))
+		.collect();
+	diff
+}
+
+/// A table of the comparisons, followed by the missing and new benchmarks.
+pub fn render(diff: &Diff) -> String {
+	let mut text = String::new();
+	let width = diff
+		.comparisons
+		.iter()
+		.map(|c| name(&c.pallet, &c.benchmark, &c.components).chars().count())
+		.max()
+		.unwrap_or(0);
+	for c in &diff.comparisons {
+		let p_value = c.p_value.map_or("p = n/a".into(), |p| format!("p = {p:.4}"));
+		writeln!(
+			text,
+			"{:width$}  {:>10.2} µs -> {:>10.2} µs  {:>+8.2}%  {p_value:<10}  {}",
+			name(&c.pallet, &c.benchmark, &c.components),
+			c.baseline.mean,
+			c.current.mean,
+			c.change * 100.0,
+			if c.regressed { "REGRESSION" } else { "ok" },
+		)
+		.expect("writing to a string; qed");
+	}
+	for missing in &diff.missing {
+		writeln!(text, "missing in the current run: {missing}").expect("writing to a string; qed");
+	}
+	for added in &diff.added {
+		writeln!(text, "not in the baseline: {added}").expect("writing to a string; qed");
+	}
+	text
+}
+
+/// The one-sided p-value of Welch's t-test for the mean of `current` exceeding that of
+/// `baseline`, or `None` with fewer than two samples in either.
+pub fn welch_p_value(baseline: &[f64], current: &[f64]) -> Option<f64> {
+	let (base, new) = (Statistics::of(baseline)?, Statistics::of(current)?);
+	if base.samples < 2 || new.samples < 2 {
+		return None
+	}
+	let (base_var, new_var) =
+		(base.stddev.powi(2) / base.samples as f64, new.stddev.powi(2) / new.samples as f64);
+	let standard_error = (base_var + new_var).sqrt();
+	if standard_error == 0.0 {
+		return Some(if new.mean > base.mean { 0.0 } else { 1.0 })
+	}
+	let t = (new.mean - base.mean) / standard_error;
+	let df = (base_var + new_var).powi(2) /
+		(base_var.powi(2) / (base.samples - 1) as f64 +
+			new_var.powi(2) / (new.samples - 1) as f64);
+	Some(student_t_upper_tail(t, df))
+}
+
+/// `P(T > t)` for Student's t-distribution with `df` degrees of freedom.
+pub fn student_t_upper_tail(t: f64, df: f64) -> f64 {
+	let tail = 0.5 * incomplete_beta(df / 2.0, 0.5, df / (df + t * t));
+	if t >= 0.0 {
+		tail
+	} else {
+		1.0 - tail
+	}
+}
+
+/// The regularized incomplete beta function `I_x(a, b)`.
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+	if x <= 0.0 {
+		return 0.0
+	}
+	if x >= 1.0 {
+		return 1.0
+	}
+	let front =
+		(ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+	// The continued fraction converges quickly on this side of the mean.
+	if x < (a + 1.0) / (a + b + 2.0) {
+		front * beta_continued_fraction(a, b, x) / a
+	} else {
+		1.0 - front * beta_continued_fraction(b, a, 1.0 - x) / b
+	}
+}
+
+/// The continued fraction of the incomplete beta function by the modified Lentz method.
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+	const TINY: f64 = 1e-300;
+	let clamp = |v: f64| if v.abs() < TINY { TINY } else { v };
+	let mut c = 1.0;
+	let mut d = 1.0 / clamp(1.0 - (a + b) * x / (a + 1.0));
+	let mut fraction = d;
+	for m in 1..=300 {
+		let m = m as f64;
+		let even = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+		d = 1.0 / clamp(1.0 + even * d);
+		c = clamp(1.0 + even / c);
+		fraction *= d * c;
+		let odd = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+		d = 1.0 / clamp(1.0 + odd * d);
+		c = clamp(1.0 + odd / c);
+		let step = d * c;
+		fraction *= step;
+		if (step - 1.0).abs() < 1e-15 {
+			break
+		}
+	}
+	fraction
+}
+
+/// The logarithm of the gamma function by the Lanczos approximation.
+fn ln_gamma(x: f64) -> f64 {
+	const COEFFICIENTS: [f64; 6] = [
+		76.18009172947146,
+		-86.50532032941677,
+		24.01409824083091,
+		-1.231739572450155,
+		0.1208650973866179e-2,
+		-0.5395239384953e-5,
+	];
+	let tmp = x + 5.5;
+	let tmp = tmp - (x + 0.5) * tmp.ln();
+	let series = COEFFICIENTS
+		.iter()
+		.enumerate()
+		.fold(1.000000000190015, |sum, (i, c)| sum + c / (x + 1.0 + i as f64));
+	-tmp + (2.5066282746310005 * series / x).ln()
+}
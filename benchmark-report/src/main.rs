@@ -0,0 +1,115 @@
+//! Reports of the `--json` output of `node-template benchmark pallet`, pairing each extrinsic
+//! with its `optimized` variant, and detection of regressions against a baseline run.
+
+use clap::Parser;
+use std::{error::Error, fs, path::PathBuf, process::ExitCode};
+
+mod compare;
+mod report;
+mod results;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Parser)]
+struct Cli {
+	#[command(subcommand)]
+	command: Command,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum Command {
+	/// Write the statistics and speedups of the extrinsics as markdown, CSV and JSON.
+	Report(ReportCmd),
+
+	/// Compare a run with a baseline run, failing if a benchmark regressed.
+	Compare(CompareCmd),
+}
+
+/// The formats of `report`.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+enum Format {
+	/// `benchmark.md`
+	Markdown,
+	/// `results.csv`
+	Csv,
+	/// `report.json`
+	Json,
+}
+
+#[derive(Debug, clap::Args)]
+struct ReportCmd {
+	/// The output of `benchmark pallet --json`.
+	#[arg(long, default_value = "results.json")]
+	input: PathBuf,
+
+	/// The directory to write the reports to.
+	#[arg(long, default_value = ".")]
+	out_dir: PathBuf,
+
+	/// The reports to write.
+	#[arg(long, value_enum, value_delimiter = ',', default_value = "markdown,csv,json")]
+	formats: Vec<Format>,
+}
+
+impl ReportCmd {
+	fn run(&self) -> Result<(), Box<dyn Error>> {
+		let results = results::parse(&fs::read_to_string(&self.input)?)?;
+		let rows = report::rows(&results);
+		fs::create_dir_all(&self.out_dir)?;
+		for format in &self.formats {
+			let (file, contents) = match format {
+				Format::Markdown => ("benchmark.md", report::markdown(&rows)),
+				Format::Csv => ("results.csv", report::csv(&rows)),
+				Format::Json => ("report.json", report::json(&rows)?),
+			};
+			fs::write(self.out_dir.join(file), contents)?;
+		}
+		Ok(())
+	}
+}
+
+#[derive(Debug, clap::Args)]
+struct CompareCmd {
+	/// The output of `benchmark pallet --json` of the baseline run.
+	#[arg(long, default_value = "results.json")]
+	baseline: PathBuf,
+
+	/// The output of `benchmark pallet --json` of the run to check.
+	#[arg(long)]
+	current: PathBuf,
+
+	/// Only compare the benchmarks whose names contain this.
+	#[arg(long, default_value = "groth16")]
+	filter: String,
+
+	/// The growth of the mean time a regression exceeds, `0.05` for 5%.
+	#[arg(long, default_value_t = 0.05)]
+	threshold: f64,
+
+	/// The significance level of the t-test.
+	#[arg(long, default_value_t = 0.05)]
+	alpha: f64,
+}
+
+impl CompareCmd {
+	/// Prints the comparison and returns whether no benchmark regressed.
+	fn run(&self) -> Result<bool, Box<dyn Error>> {
+		let baseline = results::parse(&fs::read_to_string(&self.baseline)?)?;
+		let current = results::parse(&fs::read_to_string(&self.current)?)?;
+		let diff = compare::compare(&baseline, &current, &self.filter, self.threshold, self.alpha);
+		print!("{}", compare::render(&diff));
+		let regressions = diff.regressions();
+		if regressions > 0 {
+			eprintln!("{regressions} benchmark(s) regressed.");
+		}
+		Ok(regressions == 0)
+	}
+}
+
+fn main() -> Result<ExitCode, Box<dyn Error>> {
+	match Cli::parse().command {
+		Command::Report(cmd) => cmd.run().map(|()| ExitCode::SUCCESS),
+		Command::Compare(cmd) => Ok(if cmd.run()? { ExitCode::SUCCESS } else { ExitCode::FAILURE }),
+	}
+}
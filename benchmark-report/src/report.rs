@@ -0,0 +1,135 @@
+//! Pairing of plain and optimized benchmarks and the markdown, CSV and JSON reports of them.
+
+use crate::results::{self, BenchmarkResult, Components, Statistics};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// A plain extrinsic and its optimized variant at the same component values, either of which
+/// may be missing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Row {
+	pub pallet: String,
+	/// The name of the plain extrinsic.
+	pub extrinsic: String,
+	/// The values of the components both variants were measured at.
+	pub components: Components,
+	pub plain: Option<Statistics>,
+	pub optimized: Option<Statistics>,
+	/// The mean time of the plain extrinsic over that of the optimized one.
+	pub speedup: Option<f64>,
+}
+
+/// The name of the plain variant of `benchmark` and whether `benchmark` is optimized, that is
+/// has `optimized` as one of its `_` separated words, like `groth16_verification_optimized`
+/// or `groth16_optimized_prepare_inputs`.
+pub fn plain_name(benchmark: &str) -> (String, bool) {
+	let words: Vec<&str> = benchmark.split('_').collect();
+	let plain: Vec<&str> = words.iter().copied().filter(|word| *word != "optimized").collect();
+	(plain.join("_"), plain.len() != words.len())
+}
+
+/// Pairs the benchmarks of each pallet by their plain names and component values, in the order
+/// of the results.
+pub fn rows(results: &[BenchmarkResult]) -> Vec<Row> {
+	let mut rows: Vec<Row> = Vec::new();
+	for result in results {
+		let (extrinsic, optimized) = plain_name(&result.benchmark);
+		for (components, times) in result.times() {
+			let index = match rows.iter().position(|row| {
+				row.pallet == result.pallet &&
+					row.extrinsic == extrinsic &&
+					row.components == components
+			}) {
+				Some(index) => index,
+				None => {
+					rows.push(Row {
+						pallet: result.pallet.clone(),
+						extrinsic: extrinsic.clone(),
+						components,
+						plain: None,
+						optimized: None,
+						speedup: None,
+					});
+					rows.len() - 1
+				},
+			};
+			let row = &mut rows[index];
+			let statistics = Statistics::of(&times);
+			if optimized {
+				row.optimized = statistics;
+			} else {
+				row.plain = statistics;
+			}
+			row.speedup = match (row.plain, row.optimized) {
+				(Some(plain), Some(optimized)) if optimized.mean > 0.0 =>
+					Some(plain.mean / optimized.mean),
+				_ => None,
+			};
+		}
+	}
+	rows
+}
+
+fn cells(statistics: Option<Statistics>) -> [String; 3] {
+	match statistics {
+		Some(s) =>
+			[format!("{:.2}", s.mean), format!("{:.2}", s.median), format!("{:.2}", s.stddev)],
+		None => Default::default(),
+	}
+}
+
+/// A markdown table of the rows, with times in microseconds.
+pub fn markdown(rows: &[Row]) -> String {
+	let mut table = String::from(
+		"| pallet | extrinsic | components | mean (µs) | median (µs) | stddev (µs) \
+		 | optimized mean (µs) | optimized median (µs) | optimized stddev (µs) | speedup |\n\
+		 | --- | --- | --- | ---: | ---: | ---: | ---: | ---: | ---: | ---: |\n",
+	);
+	for row in rows {
+		let dash = |cell: String| if cell.is_empty() { "-".into() } else { cell };
+		let [mean, median, stddev] = cells(row.plain).map(dash);
+		let [optimized_mean, optimized_median, optimized_stddev] = cells(row.optimized).map(dash);
+		let speedup = row.speedup.map_or("-".into(), |speedup| format!("{speedup:.2}×"));
+		let components = dash(results::label(&row.components));
+		writeln!(
+			table,
+			"| {} | {} | {components} | {mean} | {median} | {stddev} | {optimized_mean} \
+			 | {optimized_median} | {optimized_stddev} | {speedup} |",
+			row.pallet, row.extrinsic,
+		)
+		.expect("writing to a string; qed");
+	}
+	table
+}
+
+/// A CSV table of the rows, with times in microseconds and empty fields for missing variants.
+/// The components are quoted, as they are separated by commas.
+pub fn csv(rows: &[Row]) -> String {
+	let mut table = String::from(
+		"pallet,extrinsic,components,mean_us,median_us,stddev_us,optimized_mean_us,\
+		 optimized_median_us,optimized_stddev_us,speedup\n",
+	);
+	for row in rows {
+		let [mean, median, stddev] = cells(row.plain);
+		let [optimized_mean, optimized_median, optimized_stddev] = cells(row.optimized);
+		let speedup = row.speedup.map_or(String::new(), |speedup| format!("{speedup:.2}"));
+		let components = if row.components.is_empty() {
+			String::new()
+		} else {
+			format!("\"{}\"", results::label(&row.components))
+		};
+		writeln!(
+			table,
+			"{},{},{components},{mean},{median},{stddev},{optimized_mean},{optimized_median},\
+			 {optimized_stddev},{speedup}",
+			row.pallet, row.extrinsic,
+		)
+		.expect("writing to a string; qed");
+	}
+	table
+}
+
+/// The rows as a JSON array.
+pub fn json(rows: &[Row]) -> serde_json::Result<String> {
+	serde_json::to_string_pretty(rows)
+}
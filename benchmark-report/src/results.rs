@@ -0,0 +1,84 @@
+//! The `--json` output of `node-template benchmark pallet` and statistics of its timings.
+
+use serde::{Deserialize, Serialize};
+
+/// The results of one benchmark. Fields of the output not used by the reports are skipped.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BenchmarkResult {
+	pub pallet: String,
+	pub benchmark: String,
+	pub time_results: Vec<TimeResult>,
+}
+
+/// One repetition of a benchmark.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TimeResult {
+	/// The values of the components of the benchmark in this repetition.
+	#[serde(default)]
+	pub components: Components,
+	/// The execution time in nanoseconds.
+	pub extrinsic_time: u128,
+}
+
+/// The names and values of the components of a benchmark, empty for one without any.
+pub type Components = Vec<(String, u32)>;
+
+/// The components as `n = 10, m = 2`.
+pub fn label(components: &Components) -> String {
+	let values: Vec<String> =
+		components.iter().map(|(name, value)| format!("{name} = {value}")).collect();
+	values.join(", ")
+}
+
+impl BenchmarkResult {
+	/// The execution times of the repetitions of each combination of component values, in
+	/// microseconds and in the order the combinations are first measured. Times of different
+	/// combinations measure different work and are never pooled.
+	pub fn times(&self) -> Vec<(Components, Vec<f64>)> {
+		let mut groups: Vec<(Components, Vec<f64>)> = Vec::new();
+		for result in &self.time_results {
+			let time = result.extrinsic_time as f64 / 1000.0;
+			match groups.iter_mut().find(|(components, _)| *components == result.components) {
+				Some((_, times)) => times.push(time),
+				None => groups.push((result.components.clone(), vec![time])),
+			}
+		}
+		groups
+	}
+}
+
+/// Parses the output of `benchmark pallet --json`.
+pub fn parse(json: &str) -> serde_json::Result<Vec<BenchmarkResult>> {
+	serde_json::from_str(json)
+}
+
+/// Summary statistics of the execution times of a benchmark, in microseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Statistics {
+	pub samples: usize,
+	pub mean: f64,
+	pub median: f64,
+	/// The sample standard deviation, zero for a single sample.
+	pub stddev: f64,
+}
+
+impl Statistics {
+	/// The statistics of `samples`, or `None` without any.
+	pub fn of(samples: &[f64]) -> Option<Self> {
+		if samples.is_empty() {
+			return None
+		}
+		let n = samples.len();
+		let mean = samples.iter().sum::<f64>() / n as f64;
+		let mut sorted = samples.to_vec();
+		sorted.sort_by(f64::total_cmp);
+		// The middle sample, or the mean of the two middle samples of an even number.
+		let median = (sorted[(n - 1) / 2] + sorted[n / 2]) / 2.0;
+		let stddev = if n > 1 {
+			(samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64).sqrt()
+		} else {
+			0.0
+		};
+		Some(Self { samples: n, mean, median, stddev })
+	}
+}
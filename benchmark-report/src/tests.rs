@@ -0,0 +1,227 @@
+use crate::{
+	compare,
+	report::{self, plain_name, Row},
+	results::{self, BenchmarkResult, Statistics},
+};
+
+const RESULTS: &str = include_str!("../../results.json");
+
+fn benchmark(pallet: &str, benchmark: &str, times: &[u128]) -> String {
+	let times: Vec<(u32, u128)> = times.iter().map(|time| (0, *time)).collect();
+	benchmark_of(pallet, benchmark, None, &times)
+}
+
+/// A benchmark with the times measured at the values of the component `component`, if any.
+fn benchmark_of(
+	pallet: &str,
+	benchmark: &str,
+	component: Option<&str>,
+	times: &[(u32, u128)],
+) -> String {
+	let time_results: Vec<String> = times
+		.iter()
+		.map(|(value, time)| {
+			let components = component.map_or(String::new(), |c| format!(r#"["{c}", {value}]"#));
+			format!(r#"{{ "components": [{components}], "extrinsic_time": {time} }}"#)
+		})
+		.collect();
+	format!(
+		r#"{{ "pallet": "{pallet}", "benchmark": "{benchmark}", "time_results": [{}] }}"#,
+		time_results.join(",")
+	)
+}
+
+#[test]
+fn statistics_of_samples() {
+	assert_eq!(Statistics::of(&[]), None);
+	assert_eq!(
+		Statistics::of(&[3.0]),
+		Some(Statistics { samples: 1, mean: 3.0, median: 3.0, stddev: 0.0 })
+	);
+	let statistics = Statistics::of(&[4.0, 1.0, 3.0, 2.0]).unwrap();
+	assert_eq!(statistics.mean, 2.5);
+	assert_eq!(statistics.median, 2.5);
+	assert!((statistics.stddev - (5.0f64 / 3.0).sqrt()).abs() < 1e-12);
+	assert_eq!(Statistics::of(&[5.0, 1.0, 2.0]).unwrap().median, 2.0);
+}
+
+#[test]
+fn optimized_variants_share_the_plain_name() {
+	assert_eq!(plain_name("groth16_verification"), ("groth16_verification".into(), false));
+	assert_eq!(plain_name("groth16_verification_optimized"), ("groth16_verification".into(), true));
+	assert_eq!(
+		plain_name("groth16_optimized_prepare_inputs"),
+		("groth16_prepare_inputs".into(), true)
+	);
+	assert_eq!(plain_name("unoptimized_call"), ("unoptimized_call".into(), false));
+}
+
+#[test]
+fn pairs_plain_and_optimized_extrinsics() {
+	let json = format!(
+		"[{}]",
+		[
+			benchmark("pallet_a", "call", &[4000, 6000]),
+			benchmark("pallet_b", "call_optimized", &[1000]),
+			benchmark("pallet_a", "call_optimized", &[1000, 1000]),
+			benchmark("pallet_a", "lonely", &[2000]),
+		]
+		.join(",")
+	);
+	let rows = report::rows(&results::parse(&json).unwrap());
+	assert_eq!(rows.len(), 3);
+	assert_eq!((rows[0].pallet.as_str(), rows[0].extrinsic.as_str()), ("pallet_a", "call"));
+	assert_eq!(rows[0].plain.unwrap().mean, 5.0);
+	assert_eq!(rows[0].optimized.unwrap().mean, 1.0);
+	assert_eq!(rows[0].speedup, Some(5.0));
+	// Variants of other pallets are not paired.
+	assert_eq!((rows[1].pallet.as_str(), rows[1].plain, rows[1].speedup), ("pallet_b", None, None));
+	assert_eq!((rows[2].extrinsic.as_str(), rows[2].optimized), ("lonely", None));
+}
+
+#[test]
+fn pairs_extrinsics_at_the_same_component_values() {
+	let json = format!(
+		"[{}]",
+		[
+			benchmark_of("pallet_a", "call", Some("n"), &[(1, 1000), (10, 10000), (1, 3000)]),
+			benchmark_of("pallet_a", "call_optimized", Some("n"), &[(10, 2000), (1, 1000)]),
+		]
+		.join(",")
+	);
+	let results = results::parse(&json).unwrap();
+	let times = results[0].times();
+	assert_eq!(times.len(), 2);
+	assert_eq!(times[0], (vec![("n".into(), 1)], vec![1.0, 3.0]));
+	assert_eq!(times[1], (vec![("n".into(), 10)], vec![10.0]));
+
+	let rows = report::rows(&results);
+	assert_eq!(rows.len(), 2);
+	assert_eq!((results::label(&rows[0].components), rows[0].speedup), ("n = 1".into(), Some(2.0)));
+	assert_eq!(
+		(results::label(&rows[1].components), rows[1].speedup),
+		("n = 10".into(), Some(5.0))
+	);
+	assert!(report::markdown(&rows).contains("| call | n = 10 |"));
+	assert!(report::csv(&rows).contains("pallet_a,call,\"n = 10\","));
+}
+
+#[test]
+fn reports_the_recorded_results() {
+	let rows = report::rows(&results::parse(RESULTS).unwrap());
+	let extrinsics: Vec<&str> = rows.iter().map(|row| row.extrinsic.as_str()).collect();
+	assert_eq!(
+		extrinsics,
+		[
+			"groth16_verification",
+			"groth16_prepare_inputs",
+			"groth16_prepare_verifying_key",
+			"groth16_verify_with_prepared_inputs"
+		]
+	);
+	assert!(rows.iter().all(|row| row.speedup.unwrap() > 1.0));
+
+	let markdown = report::markdown(&rows);
+	assert_eq!(markdown.lines().count(), 2 + rows.len());
+	assert!(markdown.lines().all(|line| line.starts_with('|') && line.ends_with('|')));
+
+	let csv = report::csv(&rows);
+	assert_eq!(csv.lines().count(), 1 + rows.len());
+	assert!(csv.lines().all(|line| line.split(',').count() == 10));
+
+	let json: Vec<Row> = serde_json::from_str(&report::json(&rows).unwrap()).unwrap();
+	for (parsed, row) in json.iter().zip(&rows) {
+		assert_eq!(parsed.extrinsic, row.extrinsic);
+		assert!((parsed.speedup.unwrap() - row.speedup.unwrap()).abs() < 1e-9);
+	}
+}
+
+#[test]
+fn student_t_tails() {
+	// The Cauchy distribution.
+	assert!((compare::student_t_upper_tail(1.0, 1.0) - 0.25).abs() < 1e-9);
+	assert!((compare::student_t_upper_tail(0.0, 7.0) - 0.5).abs() < 1e-12);
+	// The 97.5% quantiles of the t-tables.
+	assert!((compare::student_t_upper_tail(2.228, 10.0) - 0.025).abs() < 1e-4);
+	assert!((compare::student_t_upper_tail(-2.086, 20.0) - 0.975).abs() < 1e-4);
+}
+
+/// The recorded results with the times of the benchmarks named `benchmark` scaled by `factor`.
+fn scaled(benchmark: &str, factor: f64) -> Vec<BenchmarkResult> {
+	let mut results = results::parse(RESULTS).unwrap();
+	for result in results.iter_mut().filter(|result| result.benchmark == benchmark) {
+		for time in &mut result.time_results {
+			time.extrinsic_time = (time.extrinsic_time as f64 * factor) as u128;
+		}
+	}
+	results
+}
+
+#[test]
+fn detects_regressions() {
+	let baseline = results::parse(RESULTS).unwrap();
+
+	let diff = compare::compare(&baseline, &baseline, "groth16", 0.05, 0.05);
+	assert_eq!(diff.comparisons.len(), 8);
+	assert_eq!(diff.regressions(), 0);
+
+	// Faster runs and noise within the threshold do not regress.
+	for factor in [0.5, 1.02] {
+		let current = scaled("groth16_verification_optimized", factor);
+		assert_eq!(compare::compare(&baseline, &current, "groth16", 0.05, 0.05).regressions(), 0);
+	}
+
+	let current = scaled("groth16_verification_optimized", 1.1);
+	let diff = compare::compare(&baseline, &current, "groth16", 0.05, 0.05);
+	let regressed: Vec<&str> = diff
+		.comparisons
+		.iter()
+		.filter(|c| c.regressed)
+		.map(|c| c.benchmark.as_str())
+		.collect();
+	assert_eq!(regressed, ["groth16_verification_optimized"]);
+	assert!(compare::render(&diff).contains("REGRESSION"));
+	// Unless the filter skips the benchmark.
+	let diff = compare::compare(&baseline, &current, "prepare", 0.05, 0.05);
+	assert_eq!((diff.comparisons.len(), diff.regressions()), (6, 0));
+}
+
+#[test]
+fn reports_missing_and_new_benchmarks() {
+	let baseline = results::parse(RESULTS).unwrap();
+	let mut current = baseline.clone();
+	current[0].benchmark = "groth16_renamed".into();
+	let diff = compare::compare(&baseline, &current, "groth16", 0.05, 0.05);
+	assert_eq!(diff.missing, ["pallet_template::groth16_verification"]);
+	assert_eq!(diff.added, ["pallet_template::groth16_renamed"]);
+	assert_eq!(diff.regressions(), 0);
+}
+
+#[test]
+fn compares_each_component_value_on_its_own() {
+	let run = |slow: u128| {
+		let times: Vec<(u32, u128)> =
+			(0..10).flat_map(|i| [(1, 1000 + i), (100, slow + i)]).collect();
+		results::parse(&format!("[{}]", benchmark_of("pallet_a", "call", Some("n"), &times)))
+			.unwrap()
+	};
+	let baseline = run(100_000);
+
+	// Pooled, the times of both values hide a regression of one behind their spread.
+	let diff = compare::compare(&baseline, &run(120_000), "call", 0.05, 0.05);
+	assert_eq!(diff.comparisons.len(), 2);
+	let regressed: Vec<String> = diff
+		.comparisons
+		.iter()
+		.filter(|c| c.regressed)
+		.map(|c| results::label(&c.components))
+		.collect();
+	assert_eq!(regressed, ["n = 100"]);
+	assert!(compare::render(&diff).contains("pallet_a::call [n = 100]"));
+
+	let mut current = baseline.clone();
+	current[0].time_results.retain(|result| result.components[0].1 == 1);
+	let diff = compare::compare(&baseline, &current, "call", 0.05, 0.05);
+	assert_eq!(diff.missing, ["pallet_a::call [n = 100]"]);
+	assert_eq!((diff.comparisons.len(), diff.added.len()), (1, 0));
+}
@@ -5,7 +5,10 @@
 use crate::service::FullClient;
 
 use node_template_runtime as runtime;
-use runtime::{AccountId, Balance, BalancesCall, SystemCall};
+use runtime::{
+	pallet_template::{bls12_381, utils::serialize_argument},
+	AccountId, Balance, BalancesCall, SystemCall, TemplateCall,
+};
 use sc_cli::Result;
 use sc_client_api::BlockBackend;
 use sp_core::{Encode, Pair};
@@ -95,6 +98,133 @@ impl frame_benchmarking_cli::ExtrinsicBuilder for TransferKeepAliveBuilder {
 	}
 }
 
+/// Generates `TemplateModule::groth16_verification` extrinsics of the fixture proof of
+/// `pallet-template` for the benchmarks.
+///
+/// Note: Should only be used for benchmarking.
+pub struct Groth16VerifyBuilder {
+	client: Arc<FullClient>,
+}
+
+impl Groth16VerifyBuilder {
+	/// Creates a new [`Self`] from the given client.
+	pub fn new(client: Arc<FullClient>) -> Self {
+		Self { client }
+	}
+}
+
+impl frame_benchmarking_cli::ExtrinsicBuilder for Groth16VerifyBuilder {
+	fn pallet(&self) -> &str {
+		"template"
+	}
+
+	fn extrinsic(&self) -> &str {
+		"groth16_verification"
+	}
+
+	fn build(&self, nonce: u32) -> std::result::Result<OpaqueExtrinsic, &'static str> {
+		let acc = Sr25519Keyring::Bob.pair();
+		let (vk, c, proof) = groth16_fixture();
+		let extrinsic: OpaqueExtrinsic = create_benchmark_extrinsic(
+			self.client.as_ref(),
+			acc,
+			TemplateCall::groth16_verification { vk, c, proof }.into(),
+			nonce,
+		)
+		.into();
+
+		Ok(extrinsic)
+	}
+}
+
+/// Generates `TemplateModule::groth16_verification_optimized` extrinsics of the fixture proof
+/// of `pallet-template` for the benchmarks.
+///
+/// Note: Should only be used for benchmarking.
+pub struct Groth16VerifyOptimizedBuilder {
+	client: Arc<FullClient>,
+}
+
+impl Groth16VerifyOptimizedBuilder {
+	/// Creates a new [`Self`] from the given client.
+	pub fn new(client: Arc<FullClient>) -> Self {
+		Self { client }
+	}
+}
+
+impl frame_benchmarking_cli::ExtrinsicBuilder for Groth16VerifyOptimizedBuilder {
+	fn pallet(&self) -> &str {
+		"template"
+	}
+
+	fn extrinsic(&self) -> &str {
+		"groth16_verification_optimized"
+	}
+
+	fn build(&self, nonce: u32) -> std::result::Result<OpaqueExtrinsic, &'static str> {
+		let acc = Sr25519Keyring::Bob.pair();
+		let (vk, c, proof) = groth16_fixture();
+		let extrinsic: OpaqueExtrinsic = create_benchmark_extrinsic(
+			self.client.as_ref(),
+			acc,
+			TemplateCall::groth16_verification_optimized { vk, c, proof }.into(),
+			nonce,
+		)
+		.into();
+
+		Ok(extrinsic)
+	}
+}
+
+/// Generates `TemplateModule::groth16_verification_host` extrinsics of the fixture proof of
+/// `pallet-template` for the benchmarks.
+///
+/// Note: Should only be used for benchmarking.
+#[cfg(feature = "experimental")]
+pub struct Groth16VerifyHostBuilder {
+	client: Arc<FullClient>,
+}
+
+#[cfg(feature = "experimental")]
+impl Groth16VerifyHostBuilder {
+	/// Creates a new [`Self`] from the given client.
+	pub fn new(client: Arc<FullClient>) -> Self {
+		Self { client }
+	}
+}
+
+#[cfg(feature = "experimental")]
+impl frame_benchmarking_cli::ExtrinsicBuilder for Groth16VerifyHostBuilder {
+	fn pallet(&self) -> &str {
+		"template"
+	}
+
+	fn extrinsic(&self) -> &str {
+		"groth16_verification_host"
+	}
+
+	fn build(&self, nonce: u32) -> std::result::Result<OpaqueExtrinsic, &'static str> {
+		let acc = Sr25519Keyring::Bob.pair();
+		let (vk, inputs, proof) = groth16_fixture();
+		let extrinsic: OpaqueExtrinsic = create_benchmark_extrinsic(
+			self.client.as_ref(),
+			acc,
+			TemplateCall::groth16_verification_host { vk, inputs, proof }.into(),
+			nonce,
+		)
+		.into();
+
+		Ok(extrinsic)
+	}
+}
+
+/// The uncompressed verifying key, public input and proof of the fixture proof of
+/// `pallet-template`, as its Groth16 calls take them.
+fn groth16_fixture() -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+	let (vk, c, proof) = bls12_381::groth16_fixture();
+	(vk, serialize_argument(c), proof)
+}
+
 /// Create a transaction using the given `call`.
 ///
 /// Note: Should only be used for benchmarking.
@@ -112,15 +242,32 @@ pub fn create_benchmark_extrinsic(
 		.checked_next_power_of_two()
 		.map(|c| c / 2)
 		.unwrap_or(2) as u64;
+	create_extrinsic(
+		sender,
+		call,
+		nonce,
+		sp_runtime::generic::Era::mortal(period, best_block.saturated_into()),
+		genesis_hash,
+		best_hash,
+	)
+}
+
+/// Create a transaction using the given `call`, valid in `era` starting at the block
+/// `era_hash` of the chain with `genesis_hash`.
+pub fn create_extrinsic(
+	sender: sp_core::sr25519::Pair,
+	call: runtime::RuntimeCall,
+	nonce: u32,
+	era: sp_runtime::generic::Era,
+	genesis_hash: runtime::Hash,
+	era_hash: runtime::Hash,
+) -> runtime::UncheckedExtrinsic {
 	let extra: runtime::SignedExtra = (
 		frame_system::CheckNonZeroSender::<runtime::Runtime>::new(),
 		frame_system::CheckSpecVersion::<runtime::Runtime>::new(),
 		frame_system::CheckTxVersion::<runtime::Runtime>::new(),
 		frame_system::CheckGenesis::<runtime::Runtime>::new(),
-		frame_system::CheckEra::<runtime::Runtime>::from(sp_runtime::generic::Era::mortal(
-			period,
-			best_block.saturated_into(),
-		)),
+		frame_system::CheckEra::<runtime::Runtime>::from(era),
 		frame_system::CheckNonce::<runtime::Runtime>::from(nonce),
 		frame_system::CheckWeight::<runtime::Runtime>::new(),
 		pallet_transaction_payment::ChargeTransactionPayment::<runtime::Runtime>::from(0),
@@ -134,7 +281,7 @@ pub fn create_benchmark_extrinsic(
 			runtime::VERSION.spec_version,
 			runtime::VERSION.transaction_version,
 			genesis_hash,
-			best_hash,
+			era_hash,
 			(),
 			(),
 			(),
@@ -1,6 +1,7 @@
 use node_template_runtime::{
+	pallet_template::{bls12_381, Groth16Curve},
 	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	SystemConfig, TemplateModuleConfig, WASM_BINARY,
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -36,6 +37,12 @@ pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
 	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
 }
 
+/// The circuits of the development chains, the fixture circuit of `pallet-template`.
+pub fn dev_circuits() -> Vec<(Vec<u8>, Groth16Curve, Vec<u8>)> {
+	let (vk, _, _) = bls12_381::groth16_fixture();
+	vec![(b"fixture".to_vec(), Groth16Curve::Bls12_381, vk)]
+}
+
 pub fn development_config() -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
 
@@ -59,6 +66,11 @@ pub fn development_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 				],
+				// Preloaded circuits
+				dev_circuits(),
+				// Accounts trusted with offchain verification results, the node inserts the
+				// offchain key of Alice for development chains
+				vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
 				true,
 			)
 		},
@@ -107,6 +119,10 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
+				// Preloaded circuits
+				dev_circuits(),
+				// Accounts trusted with offchain verification results
+				vec![],
 				true,
 			)
 		},
@@ -130,6 +146,8 @@ fn testnet_genesis(
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	circuits: Vec<(Vec<u8>, Groth16Curve, Vec<u8>)>,
+	result_submitters: Vec<AccountId>,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -152,5 +170,10 @@ fn testnet_genesis(
 			key: Some(root_key),
 		},
 		transaction_payment: Default::default(),
+		template_module: TemplateModuleConfig {
+			// Register the circuits' Groth16 keys as approved.
+			circuits,
+			result_submitters,
+		},
 	}
 }
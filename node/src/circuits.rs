@@ -0,0 +1,50 @@
+//! The R1CS circuits behind the built-in circuits of the `groth16` sub-commands.
+
+use ark_bls12_381::Fr;
+use ark_relations::{
+	lc,
+	r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError, Variable},
+};
+
+/// `x^3 + x + 5 = y` with the public input `y`, its constraints repeated `repetitions` times
+/// over the same `x`. Without `x` the circuit only has a shape, enough for the setup.
+#[derive(Clone, Copy)]
+pub struct CubeCircuit {
+	/// The private witness.
+	pub x: Option<Fr>,
+	/// How often the constraints are repeated, at least once.
+	pub repetitions: usize,
+}
+
+impl CubeCircuit {
+	/// The public input `y` of the witness, if there is one.
+	pub fn public_inputs(&self) -> Vec<Fr> {
+		self.x.map(|x| x * x * x + x + Fr::from(5u64)).into_iter().collect()
+	}
+}
+
+impl ConstraintSynthesizer<Fr> for CubeCircuit {
+	fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+		let y = cs.new_input_variable(|| {
+			self.public_inputs().pop().ok_or(SynthesisError::AssignmentMissing)
+		})?;
+		let x_value = self.x;
+		let x = cs.new_witness_variable(|| x_value.ok_or(SynthesisError::AssignmentMissing))?;
+		for _ in 0..self.repetitions.max(1) {
+			let x_2 = cs.new_witness_variable(|| {
+				x_value.map(|x| x * x).ok_or(SynthesisError::AssignmentMissing)
+			})?;
+			let x_3 = cs.new_witness_variable(|| {
+				x_value.map(|x| x * x * x).ok_or(SynthesisError::AssignmentMissing)
+			})?;
+			cs.enforce_constraint(lc!() + x, lc!() + x, lc!() + x_2)?;
+			cs.enforce_constraint(lc!() + x_2, lc!() + x, lc!() + x_3)?;
+			cs.enforce_constraint(
+				lc!() + x_3 + x + (Fr::from(5u64), Variable::One),
+				lc!() + Variable::One,
+				lc!() + y,
+			)?;
+		}
+		Ok(())
+	}
+}
@@ -50,4 +50,8 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Groth16 proofs of the built-in circuits for `pallet-template`.
+	#[command(subcommand)]
+	Groth16(crate::groth16::Groth16Cmd),
 }
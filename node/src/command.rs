@@ -1,10 +1,15 @@
 use crate::{
-	benchmarking::{inherent_benchmark_data, RemarkBuilder, TransferKeepAliveBuilder},
+	benchmarking::{
+		inherent_benchmark_data, Groth16VerifyBuilder, Groth16VerifyOptimizedBuilder,
+		RemarkBuilder, TransferKeepAliveBuilder,
+	},
 	chain_spec,
 	cli::{Cli, Subcommand},
 	service,
 };
-use frame_benchmarking_cli::{BenchmarkCmd, ExtrinsicFactory, SUBSTRATE_REFERENCE_HARDWARE};
+use frame_benchmarking_cli::{
+	BenchmarkCmd, ExtrinsicBuilder, ExtrinsicFactory, SUBSTRATE_REFERENCE_HARDWARE,
+};
 use node_template_runtime::{Block, EXISTENTIAL_DEPOSIT};
 use sc_cli::{ChainSpec, RuntimeVersion, SubstrateCli};
 use sc_service::PartialComponents;
@@ -158,15 +163,23 @@ pub fn run() -> sc_cli::Result<()> {
 					},
 					BenchmarkCmd::Extrinsic(cmd) => {
 						let PartialComponents { client, .. } = service::new_partial(&config)?;
-						// Register the *Remark* and *TKA* builders.
-						let ext_factory = ExtrinsicFactory(vec![
+						// Register the *Remark*, *TKA* and *Groth16* builders.
+						#[allow(unused_mut)]
+						let mut builders: Vec<Box<dyn ExtrinsicBuilder>> = vec![
 							Box::new(RemarkBuilder::new(client.clone())),
 							Box::new(TransferKeepAliveBuilder::new(
 								client.clone(),
 								Sr25519Keyring::Alice.to_account_id(),
 								EXISTENTIAL_DEPOSIT,
 							)),
-						]);
+							Box::new(Groth16VerifyBuilder::new(client.clone())),
+							Box::new(Groth16VerifyOptimizedBuilder::new(client.clone())),
+						];
+						#[cfg(feature = "experimental")]
+						builders.push(Box::new(
+							crate::benchmarking::Groth16VerifyHostBuilder::new(client.clone()),
+						));
+						let ext_factory = ExtrinsicFactory(builders);
 
 						cmd.run(client, inherent_benchmark_data()?, Vec::new(), &ext_factory)
 					},
@@ -206,6 +219,7 @@ pub fn run() -> sc_cli::Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run::<Block>(&config))
 		},
+		Some(Subcommand::Groth16(cmd)) => cmd.run(),
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
 			runner.run_node_until_exit(|config| async move {
@@ -0,0 +1,395 @@
+//! The `groth16` sub-commands, which set up, prove and verify the built-in [`Circuit`]s over
+//! BLS12-381 and encode the results for `pallet-template`.
+//!
+//! Keys, public inputs and proofs use the uncompressed arkworks encoding the pallet expects.
+//! Arguments taking such a blob accept `0x`-prefixed hex or the path of a file with the raw
+//! bytes. The keys of `setup` come from a local trusted setup and are for development only.
+
+use crate::{benchmarking::create_extrinsic, circuits::CubeCircuit};
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_groth16::{Groth16, Proof, ProvingKey, VerifyingKey};
+use ark_serialize::CanonicalDeserialize;
+use ark_snark::SNARK;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use node_template_runtime::{
+	self as runtime,
+	pallet_template::{self, utils::serialize_argument},
+};
+use sc_cli::{Error, Result};
+use sp_core::{Encode, Pair};
+use sp_runtime::generic::Era;
+use std::{fs, path::PathBuf};
+
+/// Groth16 proofs of the built-in circuits.
+#[derive(Debug, clap::Subcommand)]
+pub enum Groth16Cmd {
+	/// Generate the proving and verifying key of a circuit.
+	Setup(SetupCmd),
+
+	/// Prove a statement of a circuit.
+	Prove(ProveCmd),
+
+	/// Verify a proof, failing if it is invalid.
+	Verify(VerifyCmd),
+
+	/// Print a verifying key in the encoding of `pallet-template`.
+	ExportVk(ExportVkCmd),
+
+	/// Encode a Groth16 call of `pallet-template`, or a signed extrinsic of it.
+	EncodeCall(EncodeCallCmd),
+}
+
+impl Groth16Cmd {
+	/// Run the sub-command.
+	pub fn run(&self) -> Result<()> {
+		match self {
+			Groth16Cmd::Setup(cmd) => cmd.run(),
+			Groth16Cmd::Prove(cmd) => cmd.run(),
+			Groth16Cmd::Verify(cmd) => cmd.run(),
+			Groth16Cmd::ExportVk(cmd) => cmd.run(),
+			Groth16Cmd::EncodeCall(cmd) => cmd.run(),
+		}
+	}
+}
+
+/// The built-in circuits.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum Circuit {
+	/// `x^3 + x + 5 = y` with the private witness `x` and the public input `y`.
+	Cube,
+	/// `cube` with its constraints repeated 64 times, for a larger proving key.
+	Cube64,
+}
+
+impl Circuit {
+	/// The circuit proving the statement of the witness `x`, or only its shape without one.
+	fn instance(self, x: Option<u64>) -> CubeCircuit {
+		let repetitions = match self {
+			Circuit::Cube => 1,
+			Circuit::Cube64 => 64,
+		};
+		CubeCircuit { x: x.map(Fr::from), repetitions }
+	}
+}
+
+/// How blobs are printed.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum Format {
+	/// `0x`-prefixed hex of the bytes.
+	Hex,
+	/// `0x`-prefixed hex of the bytes SCALE encoded as a `Vec<u8>` call argument.
+	Scale,
+	/// A JSON object of `0x`-prefixed hex fields.
+	Json,
+}
+
+impl Format {
+	fn print(self, blobs: &[(&str, Vec<u8>)]) -> Result<()> {
+		let encode = |bytes: &Vec<u8>| match self {
+			Format::Scale => to_hex(&bytes.encode()),
+			Format::Hex | Format::Json => to_hex(bytes),
+		};
+		match self {
+			Format::Json => {
+				let object: serde_json::Map<_, _> = blobs
+					.iter()
+					.map(|(name, bytes)| (name.to_string(), encode(bytes).into()))
+					.collect();
+				let json = serde_json::to_string_pretty(&object)
+					.map_err(|e| Error::Application(Box::new(e)))?;
+				println!("{json}");
+			},
+			Format::Hex | Format::Scale => match blobs {
+				[(_, bytes)] => println!("{}", encode(bytes)),
+				blobs =>
+					for (name, bytes) in blobs {
+						println!("{name}: {}", encode(bytes));
+					},
+			},
+		}
+		Ok(())
+	}
+}
+
+/// The `groth16 setup` command.
+#[derive(Debug, clap::Parser)]
+pub struct SetupCmd {
+	/// The circuit to set up.
+	#[arg(long, value_enum)]
+	pub circuit: Circuit,
+
+	/// Seed of the setup, for reproducible keys. Without it the keys are random.
+	#[arg(long)]
+	pub seed: Option<u64>,
+
+	/// Directory to write `proving_key.bin` and `verifying_key.bin` to.
+	#[arg(long, default_value = ".")]
+	pub out: PathBuf,
+}
+
+impl SetupCmd {
+	fn run(&self) -> Result<()> {
+		let mut rng = rng(self.seed);
+		let (pk, vk) =
+			Groth16::<Bls12_381>::circuit_specific_setup(self.circuit.instance(None), &mut rng)
+				.map_err(|e| Error::Input(format!("setting up the circuit: {e:?}")))?;
+		fs::create_dir_all(&self.out)?;
+		fs::write(self.out.join("proving_key.bin"), serialize_argument(pk))?;
+		fs::write(self.out.join("verifying_key.bin"), serialize_argument(vk))?;
+		Ok(())
+	}
+}
+
+/// The `groth16 prove` command.
+#[derive(Debug, clap::Parser)]
+pub struct ProveCmd {
+	/// The circuit to prove a statement of.
+	#[arg(long, value_enum)]
+	pub circuit: Circuit,
+
+	/// The proving key of the circuit from `groth16 setup`.
+	#[arg(long)]
+	pub proving_key: String,
+
+	/// The private witness `x`.
+	#[arg(long)]
+	pub x: u64,
+
+	/// Seed of the proof randomness, for reproducible proofs.
+	#[arg(long)]
+	pub seed: Option<u64>,
+
+	/// How to print the public inputs and the proof.
+	#[arg(long, value_enum, default_value_t = Format::Hex)]
+	pub format: Format,
+}
+
+impl ProveCmd {
+	fn run(&self) -> Result<()> {
+		let pk: ProvingKey<Bls12_381> = decode("proving key", &read_blob(&self.proving_key)?)?;
+		let circuit = self.circuit.instance(Some(self.x));
+		let proof = Groth16::<Bls12_381>::prove(&pk, circuit, &mut rng(self.seed))
+			.map_err(|e| Error::Input(format!("proving the statement: {e:?}")))?;
+		self.format.print(&[
+			("inputs", serialize_argument(circuit.public_inputs())),
+			("proof", serialize_argument(proof)),
+		])
+	}
+}
+
+/// The `groth16 verify` command.
+#[derive(Debug, clap::Parser)]
+pub struct VerifyCmd {
+	/// The verifying key.
+	#[arg(long)]
+	pub vk: String,
+
+	/// The public inputs, a vector of scalars.
+	#[arg(long)]
+	pub inputs: String,
+
+	/// The proof.
+	#[arg(long)]
+	pub proof: String,
+}
+
+impl VerifyCmd {
+	fn run(&self) -> Result<()> {
+		let vk: VerifyingKey<Bls12_381> = decode("verifying key", &read_blob(&self.vk)?)?;
+		let inputs: Vec<Fr> = decode("public inputs", &read_blob(&self.inputs)?)?;
+		let proof: Proof<Bls12_381> = decode("proof", &read_blob(&self.proof)?)?;
+		let valid = Groth16::<Bls12_381>::verify(&vk, &inputs, &proof)
+			.map_err(|e| Error::Input(format!("verifying the proof: {e:?}")))?;
+		if !valid {
+			return Err("The proof is invalid.".into())
+		}
+		println!("The proof is valid.");
+		Ok(())
+	}
+}
+
+/// The `groth16 export-vk` command.
+#[derive(Debug, clap::Parser)]
+pub struct ExportVkCmd {
+	/// The verifying key, compressed or uncompressed. Defaults to the key of the fixture
+	/// proofs of `pallet-template`.
+	#[arg(long)]
+	pub vk: Option<String>,
+
+	/// How to print the uncompressed key.
+	#[arg(long, value_enum, default_value_t = Format::Hex)]
+	pub format: Format,
+}
+
+impl ExportVkCmd {
+	fn run(&self) -> Result<()> {
+		let bytes = match &self.vk {
+			Some(vk) => read_blob(vk)?,
+			None => pallet_template::bls12_381::groth16_fixture().0,
+		};
+		let vk = VerifyingKey::<Bls12_381>::deserialize_uncompressed(&bytes[..])
+			.or_else(|_| VerifyingKey::<Bls12_381>::deserialize_compressed(&bytes[..]))
+			.map_err(|e| Error::Input(format!("decoding the verifying key: {e}")))?;
+		self.format.print(&[("vk", serialize_argument(vk))])
+	}
+}
+
+/// The calls of `groth16 encode-call`.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum CallKind {
+	/// `groth16_verification` of a key and a single public input.
+	Verify,
+	/// `groth16_verification_optimized` of a key and a single public input.
+	VerifyOptimized,
+	/// `groth16_verification_host` of a key and its public inputs, with the `experimental`
+	/// feature.
+	#[cfg(feature = "experimental")]
+	VerifyHost,
+	/// `groth16_register_vk` of a key.
+	RegisterVk,
+	/// `groth16_verify_prepared` against a registered key.
+	VerifyPrepared,
+	/// `groth16_request_verification` by the offchain worker against a registered key.
+	RequestVerification,
+}
+
+/// The `groth16 encode-call` command.
+#[derive(Debug, clap::Parser)]
+pub struct EncodeCallCmd {
+	/// The call to encode.
+	#[arg(long, value_enum)]
+	pub call: CallKind,
+
+	/// The verifying key, for the calls taking one.
+	#[arg(long)]
+	pub vk: Option<String>,
+
+	/// The id of a registered verifying key, for the calls taking one.
+	#[arg(long)]
+	pub vk_id: Option<u32>,
+
+	/// The public inputs from `groth16 prove`.
+	#[arg(long)]
+	pub inputs: Option<String>,
+
+	/// The proof.
+	#[arg(long)]
+	pub proof: Option<String>,
+
+	/// Secret URI of the signer, e.g. `//Alice`. With it an immortal signed extrinsic is
+	/// printed, ready for `author_submitExtrinsic`, instead of the call.
+	#[arg(long, requires = "genesis_hash")]
+	pub suri: Option<String>,
+
+	/// The genesis hash of the chain of the signed extrinsic.
+	#[arg(long)]
+	pub genesis_hash: Option<String>,
+
+	/// The nonce of the signer.
+	#[arg(long, default_value_t = 0)]
+	pub nonce: u32,
+}
+
+impl EncodeCallCmd {
+	fn run(&self) -> Result<()> {
+		let call = match self.call {
+			CallKind::Verify => pallet_template::Call::groth16_verification {
+				vk: required(&self.vk, "vk")?,
+				c: self.single_input()?,
+				proof: required(&self.proof, "proof")?,
+			},
+			CallKind::VerifyOptimized => pallet_template::Call::groth16_verification_optimized {
+				vk: required(&self.vk, "vk")?,
+				c: self.single_input()?,
+				proof: required(&self.proof, "proof")?,
+			},
+			#[cfg(feature = "experimental")]
+			CallKind::VerifyHost => pallet_template::Call::groth16_verification_host {
+				vk: required(&self.vk, "vk")?,
+				inputs: self.concatenated_inputs()?,
+				proof: required(&self.proof, "proof")?,
+			},
+			CallKind::RegisterVk =>
+				pallet_template::Call::groth16_register_vk { vk: required(&self.vk, "vk")? },
+			CallKind::VerifyPrepared => pallet_template::Call::groth16_verify_prepared {
+				vk_id: self.vk_id.ok_or("--vk-id is required for this call.")?,
+				public_inputs: required(&self.inputs, "inputs")?,
+				proof: required(&self.proof, "proof")?,
+			},
+			CallKind::RequestVerification => pallet_template::Call::groth16_request_verification {
+				vk_id: self.vk_id.ok_or("--vk-id is required for this call.")?,
+				public_inputs: required(&self.inputs, "inputs")?,
+				proof: required(&self.proof, "proof")?,
+			},
+		};
+		let call = runtime::RuntimeCall::TemplateModule(call);
+
+		let encoded = match (&self.suri, &self.genesis_hash) {
+			(Some(suri), Some(genesis_hash)) => {
+				let sender = sp_core::sr25519::Pair::from_string(suri, None)
+					.map_err(|e| Error::Input(format!("invalid --suri: {e:?}")))?;
+				let genesis_hash = read_blob(genesis_hash)?;
+				if genesis_hash.len() != 32 {
+					return Err("--genesis-hash must be 32 bytes of hex.".into())
+				}
+				let genesis_hash = runtime::Hash::from_slice(&genesis_hash);
+				create_extrinsic(
+					sender,
+					call,
+					self.nonce,
+					Era::Immortal,
+					genesis_hash,
+					genesis_hash,
+				)
+				.encode()
+			},
+			_ => call.encode(),
+		};
+		println!("{}", to_hex(&encoded));
+		Ok(())
+	}
+
+	/// The public inputs as the single scalar the unregistered verification calls take.
+	fn single_input(&self) -> Result<Vec<u8>> {
+		let inputs: Vec<Fr> = decode("public inputs", &required(&self.inputs, "inputs")?)?;
+		match &inputs[..] {
+			[c] => Ok(serialize_argument(*c)),
+			_ => Err("This call takes exactly one public input.".into()),
+		}
+	}
+
+	/// The public inputs as the scalars one after the other `groth16_verification_host` takes.
+	#[cfg(feature = "experimental")]
+	fn concatenated_inputs(&self) -> Result<Vec<u8>> {
+		let inputs: Vec<Fr> = decode("public inputs", &required(&self.inputs, "inputs")?)?;
+		Ok(inputs.into_iter().flat_map(serialize_argument).collect())
+	}
+}
+
+fn rng(seed: Option<u64>) -> StdRng {
+	seed.map_or_else(StdRng::from_entropy, StdRng::seed_from_u64)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+	format!("0x{}", hex::encode(bytes))
+}
+
+/// Reads `0x`-prefixed hex, or else the file at `blob`.
+fn read_blob(blob: &str) -> Result<Vec<u8>> {
+	match blob.strip_prefix("0x") {
+		Some(hex) => hex::decode(hex).map_err(|e| Error::Input(format!("invalid hex: {e}"))),
+		None => Ok(fs::read(blob)?),
+	}
+}
+
+fn required(blob: &Option<String>, name: &str) -> Result<Vec<u8>> {
+	match blob {
+		Some(blob) => read_blob(blob),
+		None => Err(Error::Input(format!("--{name} is required for this call."))),
+	}
+}
+
+fn decode<T: CanonicalDeserialize>(name: &str, bytes: &[u8]) -> Result<T> {
+	T::deserialize_uncompressed(bytes)
+		.map_err(|e| Error::Input(format!("decoding the {name}: {e}")))
+}
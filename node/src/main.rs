@@ -5,8 +5,10 @@ mod chain_spec;
 #[macro_use]
 mod service;
 mod benchmarking;
+mod circuits;
 mod cli;
 mod command;
+mod groth16;
 mod rpc;
 
 fn main() -> sc_cli::Result<()> {
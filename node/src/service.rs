@@ -1,6 +1,6 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
-use node_template_runtime::{self, opaque::Block, RuntimeApi};
+use node_template_runtime::{self, opaque::Block, pallet_template, RuntimeApi};
 use sc_client_api::BlockBackend;
 use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
 use sc_consensus_grandpa::SharedVoterState;
@@ -8,18 +8,27 @@ pub use sc_executor::NativeElseWasmExecutor;
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager, WarpSyncParams};
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
+use sp_keystore::Keystore;
 use std::{sync::Arc, time::Duration};
 
+/// The Groth16 host function of the template pallet, only with the `experimental` feature.
+#[cfg(feature = "experimental")]
+type TemplateHostFunctions = pallet_template::host::groth16::HostFunctions;
+#[cfg(not(feature = "experimental"))]
+type TemplateHostFunctions = ();
+
 // Our native executor instance.
 pub struct ExecutorDispatch;
 
 impl sc_executor::NativeExecutionDispatch for ExecutorDispatch {
 	/// Only enable the benchmarking host functions when we actually want to benchmark.
 	#[cfg(feature = "runtime-benchmarks")]
-	type ExtendHostFunctions = frame_benchmarking::benchmarking::HostFunctions;
-	/// Otherwise we only use the default Substrate host functions.
+	type ExtendHostFunctions =
+		(frame_benchmarking::benchmarking::HostFunctions, TemplateHostFunctions);
+	/// Otherwise we only add those of the template pallet to the default Substrate host
+	/// functions.
 	#[cfg(not(feature = "runtime-benchmarks"))]
-	type ExtendHostFunctions = ();
+	type ExtendHostFunctions = TemplateHostFunctions;
 
 	fn dispatch(method: &str, data: &[u8]) -> Option<Vec<u8>> {
 		node_template_runtime::api::dispatch(method, data)
@@ -182,6 +191,16 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		})?;
 
 	if config.offchain_worker.enabled {
+		// Development chains verify queued proofs with Alice's key.
+		if config.chain_spec.id() == "dev" {
+			keystore_container
+				.keystore()
+				.sr25519_generate_new(
+					node_template_runtime::pallet_template::offchain::KEY_TYPE,
+					Some("//Alice"),
+				)
+				.map_err(|e| ServiceError::Other(format!("{:?}", e)))?;
+		}
 		sc_service::build_offchain_workers(
 			&config,
 			task_manager.spawn_handle(),
@@ -0,0 +1,89 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// An example consumer of the Groth16 verification of `pallet-template`.
+///
+/// Statements, the public inputs of a registered circuit, are claimed with a proof checked by
+/// `pallet_template::Pallet::verify` without dispatching, and each statement is claimed only
+/// once. Only keys the admin of `pallet-template` approved are accepted. As the
+/// `OnProofVerified` handler of `pallet-template`, the pallet also counts the proofs each
+/// account verified through the extrinsics of `pallet-template`.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use pallet_template::{OnProofVerified, VerifyingKeyStatus};
+	use sp_runtime::traits::Hash;
+	use sp_std::vec::Vec;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_template::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+	}
+
+	/// The claimer of each statement, by key id and hash of the public inputs.
+	#[pallet::storage]
+	pub type Claims<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, u32, Identity, T::Hash, T::AccountId>;
+
+	/// The number of proofs each account verified through `pallet-template`.
+	#[pallet::storage]
+	pub type VerifiedProofs<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A statement of a registered key was claimed.
+		Claimed { vk_id: u32, statement: T::Hash, who: T::AccountId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The statement was claimed before
+		AlreadyClaimed,
+		/// The verifying key is not approved
+		KeyNotApproved,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Claims the statement `public_inputs` of the approved Groth16 key `vk_id` with a proof
+		/// of it.
+		#[pallet::call_index(0)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1).ref_time())]
+		pub fn claim(
+			origin: OriginFor<T>,
+			vk_id: u32,
+			public_inputs: Vec<u8>,
+			proof: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let statement = T::Hashing::hash(&public_inputs);
+			ensure!(!Claims::<T>::contains_key(vk_id, statement), Error::<T>::AlreadyClaimed);
+			let status = pallet_template::Pallet::<T>::verify(vk_id, &public_inputs, &proof)?;
+			ensure!(status == VerifyingKeyStatus::Approved, Error::<T>::KeyNotApproved);
+			Claims::<T>::insert(vk_id, statement, &who);
+			Self::deposit_event(Event::Claimed { vk_id, statement, who });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> OnProofVerified<T::AccountId> for Pallet<T> {
+		fn on_proof_verified(who: &T::AccountId, _vk_id: u32, _public_inputs: &[u8]) {
+			VerifiedProofs::<T>::mutate(who, |count| *count = count.saturating_add(1));
+		}
+	}
+}
@@ -0,0 +1,99 @@
+use crate::{mock::*, Claims, Error, Event, VerifiedProofs};
+use ark_bls12_381::Fr as BlsFr;
+use frame_support::{assert_noop, assert_ok};
+use pallet_template::{bls12_381, utils};
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+/// Registers the Groth16 fixture key of `pallet-template` under id 0 and returns a valid
+/// statement and its proof.
+fn register_fixture() -> (Vec<u8>, Vec<u8>) {
+	let (vk, c, proof) = bls12_381::groth16_fixture();
+	assert_ok!(TemplateModule::groth16_register_vk(RuntimeOrigin::signed(1), vk));
+	(utils::serialize_argument(vec![c]), proof)
+}
+
+/// Registers the Groth16 fixture key like `register_fixture` and approves it.
+fn approved_fixture() -> (Vec<u8>, Vec<u8>) {
+	let fixture = register_fixture();
+	assert_ok!(TemplateModule::groth16_approve_vk(RuntimeOrigin::root(), 0));
+	fixture
+}
+
+#[test]
+fn claim_verifies_without_dispatching() {
+	new_test_ext().execute_with(|| {
+		let (inputs, proof) = approved_fixture();
+		assert_ok!(ProofClaims::claim(RuntimeOrigin::signed(2), 0, inputs.clone(), proof.clone()));
+		let statement = BlakeTwo256::hash(&inputs);
+		assert_eq!(Claims::<Test>::get(0, statement), Some(2));
+		System::assert_last_event(Event::Claimed { vk_id: 0, statement, who: 2 }.into());
+		// The handler only sees proofs verified by the extrinsics of `pallet-template`.
+		assert_eq!(VerifiedProofs::<Test>::get(2), 0);
+
+		assert_noop!(
+			ProofClaims::claim(RuntimeOrigin::signed(3), 0, inputs, proof),
+			Error::<Test>::AlreadyClaimed
+		);
+	});
+}
+
+#[test]
+fn claim_rejects_invalid_proofs() {
+	new_test_ext().execute_with(|| {
+		let (inputs, proof) = approved_fixture();
+		assert_noop!(
+			ProofClaims::claim(
+				RuntimeOrigin::signed(2),
+				0,
+				utils::serialize_argument(vec![BlsFr::from(1u64)]),
+				proof.clone()
+			),
+			pallet_template::Error::<Test>::VerificationFailed
+		);
+		assert_noop!(
+			ProofClaims::claim(RuntimeOrigin::signed(2), 1, inputs, proof),
+			pallet_template::Error::<Test>::UnknownVerifyingKey
+		);
+	});
+}
+
+#[test]
+fn claim_requires_an_approved_key() {
+	new_test_ext().execute_with(|| {
+		let (inputs, proof) = register_fixture();
+		assert_noop!(
+			ProofClaims::claim(RuntimeOrigin::signed(2), 0, inputs.clone(), proof.clone()),
+			Error::<Test>::KeyNotApproved
+		);
+		assert_ok!(TemplateModule::groth16_deprecate_vk(RuntimeOrigin::root(), 0));
+		assert_noop!(
+			ProofClaims::claim(RuntimeOrigin::signed(2), 0, inputs.clone(), proof.clone()),
+			Error::<Test>::KeyNotApproved
+		);
+		assert_ok!(TemplateModule::groth16_freeze_vk(RuntimeOrigin::root(), 0));
+		assert_noop!(
+			ProofClaims::claim(RuntimeOrigin::signed(2), 0, inputs.clone(), proof.clone()),
+			pallet_template::Error::<Test>::VerifyingKeyFrozen
+		);
+
+		assert_ok!(TemplateModule::groth16_approve_vk(RuntimeOrigin::root(), 0));
+		assert_ok!(ProofClaims::claim(RuntimeOrigin::signed(2), 0, inputs, proof));
+	});
+}
+
+#[test]
+fn counts_proofs_verified_by_the_template_pallet() {
+	new_test_ext().execute_with(|| {
+		let (inputs, proof) = register_fixture();
+		for _ in 0..2 {
+			assert_ok!(TemplateModule::groth16_verify_prepared(
+				RuntimeOrigin::signed(2),
+				0,
+				inputs.clone(),
+				proof.clone()
+			));
+		}
+		assert_eq!(VerifiedProofs::<Test>::get(2), 2);
+		assert_eq!(VerifiedProofs::<Test>::get(1), 0);
+	});
+}
@@ -0,0 +1,302 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// An Ethereum beacon chain light client following the sync committee protocol.
+///
+/// The pallet is bootstrapped from a trusted block root and afterwards only accepts
+/// `LightClientUpdate`s that carry a supermajority aggregate BLS signature of the current (or
+/// next) sync committee. The pairing check runs on the host accelerated BLS12-381 curve of
+/// `pallet-template`.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod types;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use crate::types::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use pallet_template::bls_signatures::{self, MinPk, Variant};
+	use sp_core::H256;
+	use sp_std::vec::Vec;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Origin allowed to bootstrap the light client from a trusted block root.
+		type InitializeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+	}
+
+	#[pallet::storage]
+	pub type GenesisValidatorsRoot<T> = StorageValue<_, H256, ValueQuery>;
+
+	/// The forks of the beacon chain, from which the fork version of the signing domain and the
+	/// layout of the proofs follow.
+	#[pallet::storage]
+	pub type Forks<T> = StorageValue<_, ForkSchedule, ValueQuery>;
+
+	#[pallet::storage]
+	pub type FinalizedHeader<T> = StorageValue<_, BeaconBlockHeader, OptionQuery>;
+
+	#[pallet::storage]
+	pub type CurrentSyncCommittee<T> = StorageValue<_, SyncCommittee, OptionQuery>;
+
+	#[pallet::storage]
+	pub type NextSyncCommittee<T> = StorageValue<_, SyncCommittee, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The light client was bootstrapped at the given header.
+		Initialized { slot: u64, block_root: H256 },
+		/// A new finalized header was accepted.
+		FinalizedHeaderUpdated { slot: u64, block_root: H256 },
+		/// The next sync committee became the current one.
+		SyncCommitteeRotated { period: u64 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The light client has not been bootstrapped yet
+		NotInitialized,
+		/// A sync committee does not have exactly `SYNC_COMMITTEE_SIZE` members
+		InvalidSyncCommitteeSize,
+		/// The current sync committee is not part of the bootstrap header's state
+		InvalidBootstrapBranch,
+		/// The finalized header is not part of the attested header's state
+		InvalidFinalityBranch,
+		/// The next sync committee is not part of the attested header's state
+		InvalidNextSyncCommitteeBranch,
+		/// Less than two thirds of the sync committee signed the update
+		InsufficientParticipation,
+		/// The slots of the update are not strictly ordered
+		InvalidSlot,
+		/// The update is signed in a period we have no sync committee for
+		UnexpectedSyncCommitteePeriod,
+		/// The update does not advance the light client
+		StaleUpdate,
+		/// A sync committee public key is malformed
+		InvalidPublicKey,
+		/// The aggregate signature is malformed
+		InvalidSignature,
+		/// The aggregate signature does not verify
+		SignatureVerificationFailed,
+		/// The fork schedule is empty or its epochs are not strictly increasing
+		InvalidForkSchedule,
+		/// The fork schedule has no fork at the epoch of a header
+		UnknownFork,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Bootstraps the light client from a trusted header and its current sync committee.
+		#[pallet::call_index(0)]
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(5).ref_time())]
+		pub fn initialize(
+			origin: OriginFor<T>,
+			bootstrap: LightClientBootstrap,
+			genesis_validators_root: H256,
+			forks: ForkSchedule,
+		) -> DispatchResult {
+			T::InitializeOrigin::ensure_origin(origin)?;
+			ensure!(is_valid_fork_schedule(&forks), Error::<T>::InvalidForkSchedule);
+			let (index, depth) =
+				fork_at_epoch(&forks, compute_epoch_at_slot(bootstrap.header.slot))
+					.ok_or(Error::<T>::UnknownFork)?
+					.layout
+					.current_sync_committee();
+
+			let committee = &bootstrap.current_sync_committee;
+			ensure!(
+				committee.pubkeys.len() == SYNC_COMMITTEE_SIZE as usize,
+				Error::<T>::InvalidSyncCommitteeSize
+			);
+			ensure!(
+				is_valid_merkle_branch(
+					committee.hash_tree_root(),
+					&bootstrap.current_sync_committee_branch,
+					depth,
+					index,
+					bootstrap.header.state_root,
+				),
+				Error::<T>::InvalidBootstrapBranch
+			);
+
+			let slot = bootstrap.header.slot;
+			let block_root = bootstrap.header.hash_tree_root();
+			GenesisValidatorsRoot::<T>::put(genesis_validators_root);
+			Forks::<T>::put(forks);
+			FinalizedHeader::<T>::put(bootstrap.header);
+			CurrentSyncCommittee::<T>::put(bootstrap.current_sync_committee);
+			NextSyncCommittee::<T>::kill();
+
+			Self::deposit_event(Event::Initialized { slot, block_root });
+			Ok(())
+		}
+
+		/// Replaces the fork schedule, e.g. to add a scheduled hard fork.
+		#[pallet::call_index(1)]
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		pub fn set_fork_schedule(origin: OriginFor<T>, forks: ForkSchedule) -> DispatchResult {
+			T::InitializeOrigin::ensure_origin(origin)?;
+			ensure!(is_valid_fork_schedule(&forks), Error::<T>::InvalidForkSchedule);
+			Forks::<T>::put(forks);
+			Ok(())
+		}
+
+		/// Verifies and applies a finality update signed by the sync committee.
+		#[pallet::call_index(2)]
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(3).ref_time())]
+		pub fn submit_update(origin: OriginFor<T>, update: LightClientUpdate) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::process_update(update)
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn process_update(update: LightClientUpdate) -> DispatchResult {
+			let finalized = FinalizedHeader::<T>::get().ok_or(Error::<T>::NotInitialized)?;
+			let current = CurrentSyncCommittee::<T>::get().ok_or(Error::<T>::NotInitialized)?;
+			let next = NextSyncCommittee::<T>::get();
+			let forks = Forks::<T>::get();
+
+			let aggregate = &update.sync_aggregate;
+			ensure!(
+				aggregate.participation() * 3 >= SYNC_COMMITTEE_SIZE * 2,
+				Error::<T>::InsufficientParticipation
+			);
+			ensure!(
+				update.signature_slot > update.attested_header.slot &&
+					update.attested_header.slot >= update.finalized_header.slot,
+				Error::<T>::InvalidSlot
+			);
+
+			let store_period = compute_sync_committee_period(finalized.slot);
+			let signature_period = compute_sync_committee_period(update.signature_slot);
+			let attested_period = compute_sync_committee_period(update.attested_header.slot);
+			let finalized_period = compute_sync_committee_period(update.finalized_header.slot);
+
+			let learns_next_committee = next.is_none() &&
+				update.next_sync_committee.is_some() &&
+				attested_period == store_period;
+			ensure!(
+				update.finalized_header.slot > finalized.slot || learns_next_committee,
+				Error::<T>::StaleUpdate
+			);
+
+			let signing_committee = if signature_period == store_period {
+				&current
+			} else if signature_period == store_period + 1 {
+				next.as_ref().ok_or(Error::<T>::UnexpectedSyncCommitteePeriod)?
+			} else {
+				return Err(Error::<T>::UnexpectedSyncCommitteePeriod.into())
+			};
+
+			// The proofs point into the state of the attested header, the signature is made with
+			// the fork version of the slot before the signature slot.
+			let layout = fork_at_epoch(&forks, compute_epoch_at_slot(update.attested_header.slot))
+				.ok_or(Error::<T>::UnknownFork)?
+				.layout;
+			let fork_version = fork_at_epoch(
+				&forks,
+				compute_epoch_at_slot(update.signature_slot.saturating_sub(1)),
+			)
+			.ok_or(Error::<T>::UnknownFork)?
+			.version;
+
+			let (index, depth) = layout.finalized_root();
+			ensure!(
+				is_valid_merkle_branch(
+					update.finalized_header.hash_tree_root(),
+					&update.finality_branch,
+					depth,
+					index,
+					update.attested_header.state_root,
+				),
+				Error::<T>::InvalidFinalityBranch
+			);
+			if let Some(next_committee) = &update.next_sync_committee {
+				let (index, depth) = layout.next_sync_committee();
+				ensure!(
+					next_committee.pubkeys.len() == SYNC_COMMITTEE_SIZE as usize,
+					Error::<T>::InvalidSyncCommitteeSize
+				);
+				ensure!(
+					is_valid_merkle_branch(
+						next_committee.hash_tree_root(),
+						&update.next_sync_committee_branch,
+						depth,
+						index,
+						update.attested_header.state_root,
+					),
+					Error::<T>::InvalidNextSyncCommitteeBranch
+				);
+			}
+
+			Self::verify_sync_aggregate(signing_committee, &update, fork_version)?;
+
+			if next.is_some() && finalized_period == store_period + 1 {
+				CurrentSyncCommittee::<T>::put(next.expect("checked above; qed"));
+				NextSyncCommittee::<T>::set(update.next_sync_committee);
+				Self::deposit_event(Event::SyncCommitteeRotated { period: finalized_period });
+			} else if learns_next_committee {
+				NextSyncCommittee::<T>::set(update.next_sync_committee);
+			}
+
+			if update.finalized_header.slot > finalized.slot {
+				let slot = update.finalized_header.slot;
+				let block_root = update.finalized_header.hash_tree_root();
+				FinalizedHeader::<T>::put(update.finalized_header);
+				Self::deposit_event(Event::FinalizedHeaderUpdated { slot, block_root });
+			}
+			Ok(())
+		}
+
+		fn verify_sync_aggregate(
+			committee: &SyncCommittee,
+			update: &LightClientUpdate,
+			fork_version: ForkVersion,
+		) -> DispatchResult {
+			let aggregate = &update.sync_aggregate;
+			let participants: Vec<Vec<u8>> = committee
+				.pubkeys
+				.iter()
+				.enumerate()
+				.filter(|(index, _)| aggregate.is_participant(*index))
+				.map(|(_, public_key)| public_key.to_vec())
+				.collect();
+
+			let domain = compute_domain(
+				DOMAIN_SYNC_COMMITTEE,
+				fork_version,
+				GenesisValidatorsRoot::<T>::get(),
+			);
+			let signing_root =
+				compute_signing_root(update.attested_header.hash_tree_root(), domain);
+
+			let valid = bls_signatures::fast_aggregate_verify::<MinPk>(
+				&participants,
+				signing_root.as_bytes(),
+				&aggregate.sync_committee_signature,
+				MinPk::DST,
+			)
+			.map_err(|error| match error {
+				bls_signatures::Error::InvalidSignature => Error::<T>::InvalidSignature,
+				_ => Error::<T>::InvalidPublicKey,
+			})?;
+			ensure!(valid, Error::<T>::SignatureVerificationFailed);
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,527 @@
+use crate::{
+	mock::*, types::*, CurrentSyncCommittee, Error, Event, FinalizedHeader, Forks,
+	NextSyncCommittee,
+};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use pallet_template::{
+	bls12_381::BlsFrOptimized,
+	bls_signatures::{self, MinPk, Variant},
+};
+use sp_core::H256;
+use sp_runtime::DispatchError;
+
+const GENESIS_VALIDATORS_ROOT: H256 = H256::repeat_byte(0x4b);
+const FORK_VERSION: ForkVersion = [3, 0, 0, 0];
+const SLOTS_PER_PERIOD: u64 = SLOTS_PER_EPOCH * EPOCHS_PER_SYNC_COMMITTEE_PERIOD;
+
+fn forks() -> ForkSchedule {
+	BoundedVec::truncate_from(vec![Fork {
+		epoch: 0,
+		version: FORK_VERSION,
+		layout: StateLayout::Altair,
+	}])
+}
+
+/// A sync committee together with the secret keys of its members.
+struct Committee {
+	secret_keys: Vec<BlsFrOptimized>,
+	committee: SyncCommittee,
+}
+
+impl Committee {
+	fn new(seed: u64) -> Self {
+		let secret_keys: Vec<_> = (0..SYNC_COMMITTEE_SIZE as u64)
+			.map(|i| BlsFrOptimized::from(seed * 1000 + i + 1))
+			.collect();
+		let pubkeys: Vec<PublicKey> = secret_keys
+			.iter()
+			.map(|sk| bls_signatures::public_key::<MinPk>(*sk).try_into().unwrap())
+			.collect();
+		let aggregate_pubkey = bls_signatures::public_key::<MinPk>(secret_keys.iter().sum())
+			.try_into()
+			.unwrap();
+		let committee =
+			SyncCommittee { pubkeys: BoundedVec::truncate_from(pubkeys), aggregate_pubkey };
+		Self { secret_keys, committee }
+	}
+
+	/// Lets the first `participants` members sign `header` under `fork_version`.
+	fn sign(
+		&self,
+		header: &BeaconBlockHeader,
+		participants: usize,
+		fork_version: ForkVersion,
+	) -> SyncAggregate {
+		let mut sync_committee_bits = [0u8; SYNC_COMMITTEE_SIZE as usize / 8];
+		for i in 0..participants {
+			sync_committee_bits[i / 8] |= 1 << (i % 8);
+		}
+		let domain = compute_domain(DOMAIN_SYNC_COMMITTEE, fork_version, GENESIS_VALIDATORS_ROOT);
+		let signing_root = compute_signing_root(header.hash_tree_root(), domain);
+		let secret_key: BlsFrOptimized = self.secret_keys[..participants].iter().sum();
+		let signature =
+			bls_signatures::sign::<MinPk>(secret_key, signing_root.as_bytes(), MinPk::DST).unwrap();
+		SyncAggregate {
+			sync_committee_bits,
+			sync_committee_signature: signature.try_into().unwrap(),
+		}
+	}
+}
+
+/// The 32 top level fields of a `BeaconState`, reduced to what the light client proves.
+struct State {
+	leaves: Vec<[u8; 32]>,
+}
+
+impl State {
+	const FINALIZED_CHECKPOINT: usize = 20;
+	const CURRENT_SYNC_COMMITTEE: usize = 22;
+	const NEXT_SYNC_COMMITTEE: usize = 23;
+
+	fn new(finalized: &BeaconBlockHeader, current: &SyncCommittee, next: &SyncCommittee) -> Self {
+		let mut leaves = vec![[0u8; 32]; 32];
+		leaves[Self::FINALIZED_CHECKPOINT] = hash_pair(
+			&uint64_chunk(finalized.slot / SLOTS_PER_EPOCH),
+			&finalized.hash_tree_root().0,
+		);
+		leaves[Self::CURRENT_SYNC_COMMITTEE] = current.hash_tree_root().0;
+		leaves[Self::NEXT_SYNC_COMMITTEE] = next.hash_tree_root().0;
+		Self { leaves }
+	}
+
+	fn root(&self) -> H256 {
+		merkleize(&self.leaves).into()
+	}
+
+	fn branch(&self, mut index: usize) -> Vec<H256> {
+		let mut layer = self.leaves.clone();
+		let mut branch = Vec::new();
+		while layer.len() > 1 {
+			branch.push(layer[index ^ 1].into());
+			layer = layer.chunks(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+			index /= 2;
+		}
+		branch
+	}
+
+	fn finality_branch(&self, finalized: &BeaconBlockHeader) -> Vec<H256> {
+		let mut branch: Vec<H256> = vec![uint64_chunk(finalized.slot / SLOTS_PER_EPOCH).into()];
+		branch.extend(self.branch(Self::FINALIZED_CHECKPOINT));
+		branch
+	}
+}
+
+fn header(slot: u64, state_root: H256) -> BeaconBlockHeader {
+	BeaconBlockHeader {
+		slot,
+		proposer_index: slot % 7,
+		parent_root: H256::repeat_byte(slot as u8),
+		state_root,
+		body_root: H256::repeat_byte(0xbb),
+	}
+}
+
+fn bootstrap(slot: u64, current: &Committee, next: &Committee) -> LightClientBootstrap {
+	let state = State::new(&header(0, H256::zero()), &current.committee, &next.committee);
+	LightClientBootstrap {
+		header: header(slot, state.root()),
+		current_sync_committee: current.committee.clone(),
+		current_sync_committee_branch: BoundedVec::truncate_from(
+			state.branch(State::CURRENT_SYNC_COMMITTEE),
+		),
+	}
+}
+
+fn initialize(slot: u64, current: &Committee, next: &Committee) {
+	assert_ok!(SyncCommitteeModule::initialize(
+		RuntimeOrigin::root(),
+		bootstrap(slot, current, next),
+		GENESIS_VALIDATORS_ROOT,
+		forks(),
+	));
+}
+
+/// Builds an update finalizing `finalized_slot`, attested two epochs later and signed by the
+/// first `participants` members of `signer`.
+fn update(
+	finalized_slot: u64,
+	signer: &Committee,
+	participants: usize,
+	current: &Committee,
+	next: &Committee,
+	include_next: bool,
+) -> LightClientUpdate {
+	let finalized_header = header(finalized_slot, H256::repeat_byte(0xf1));
+	let state = State::new(&finalized_header, &current.committee, &next.committee);
+	let attested_header = header(finalized_slot + 2 * SLOTS_PER_EPOCH, state.root());
+	LightClientUpdate {
+		next_sync_committee: include_next.then(|| next.committee.clone()),
+		next_sync_committee_branch: if include_next {
+			BoundedVec::truncate_from(state.branch(State::NEXT_SYNC_COMMITTEE))
+		} else {
+			Default::default()
+		},
+		finality_branch: BoundedVec::truncate_from(state.finality_branch(&finalized_header)),
+		finalized_header,
+		sync_aggregate: signer.sign(&attested_header, participants, FORK_VERSION),
+		signature_slot: attested_header.slot + 1,
+		attested_header,
+	}
+}
+
+#[test]
+fn merkleization_matches_ssz() {
+	// hash_tree_root of an empty `BeaconBlockHeader`, i.e. the root of eight zero chunks.
+	assert_eq!(
+		BeaconBlockHeader::default().hash_tree_root(),
+		H256(hex_literal("c78009fdf07fc56a11f122370658a353aaa542ed63e44c4bc15ff4cd105ab33c")),
+	);
+}
+
+#[test]
+fn initialize_checks_current_sync_committee_branch() {
+	new_test_ext().execute_with(|| {
+		let (current, next) = (Committee::new(0), Committee::new(1));
+		let mut invalid = bootstrap(100, &current, &next);
+		invalid.current_sync_committee_branch[0] = H256::zero();
+
+		assert_noop!(
+			SyncCommitteeModule::initialize(
+				RuntimeOrigin::signed(1),
+				bootstrap(100, &current, &next),
+				GENESIS_VALIDATORS_ROOT,
+				forks(),
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			SyncCommitteeModule::initialize(
+				RuntimeOrigin::root(),
+				invalid,
+				GENESIS_VALIDATORS_ROOT,
+				forks(),
+			),
+			Error::<Test>::InvalidBootstrapBranch
+		);
+
+		initialize(100, &current, &next);
+		assert_eq!(FinalizedHeader::<Test>::get().unwrap().slot, 100);
+		assert_eq!(CurrentSyncCommittee::<Test>::get(), Some(current.committee));
+	});
+}
+
+#[test]
+fn fork_schedule_must_be_ordered() {
+	new_test_ext().execute_with(|| {
+		let (current, next) = (Committee::new(0), Committee::new(1));
+		assert_noop!(
+			SyncCommitteeModule::initialize(
+				RuntimeOrigin::root(),
+				bootstrap(100, &current, &next),
+				GENESIS_VALIDATORS_ROOT,
+				Default::default(),
+			),
+			Error::<Test>::InvalidForkSchedule
+		);
+
+		initialize(100, &current, &next);
+		let mut unordered = forks();
+		unordered
+			.try_push(Fork { epoch: 0, version: [4, 0, 0, 0], layout: StateLayout::Altair })
+			.unwrap();
+		assert_noop!(
+			SyncCommitteeModule::set_fork_schedule(RuntimeOrigin::root(), unordered),
+			Error::<Test>::InvalidForkSchedule
+		);
+		assert_noop!(
+			SyncCommitteeModule::set_fork_schedule(RuntimeOrigin::signed(1), forks()),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn submit_update_advances_finalized_header() {
+	new_test_ext().execute_with(|| {
+		let (current, next) = (Committee::new(0), Committee::new(1));
+		initialize(100, &current, &next);
+
+		let update = update(320, &current, 400, &current, &next, false);
+		let block_root = update.finalized_header.hash_tree_root();
+		assert_ok!(SyncCommitteeModule::submit_update(RuntimeOrigin::signed(1), update));
+
+		assert_eq!(FinalizedHeader::<Test>::get().unwrap().slot, 320);
+		System::assert_last_event(Event::FinalizedHeaderUpdated { slot: 320, block_root }.into());
+	});
+}
+
+#[test]
+fn submit_update_requires_supermajority() {
+	new_test_ext().execute_with(|| {
+		let (current, next) = (Committee::new(0), Committee::new(1));
+		initialize(100, &current, &next);
+
+		assert_noop!(
+			SyncCommitteeModule::submit_update(
+				RuntimeOrigin::signed(1),
+				update(320, &current, 341, &current, &next, false)
+			),
+			Error::<Test>::InsufficientParticipation
+		);
+	});
+}
+
+#[test]
+fn submit_update_rejects_invalid_signature_and_branches() {
+	new_test_ext().execute_with(|| {
+		let (current, next) = (Committee::new(0), Committee::new(1));
+		initialize(100, &current, &next);
+
+		let mut wrong_fork = update(320, &current, 400, &current, &next, false);
+		wrong_fork.sync_aggregate = current.sign(&wrong_fork.attested_header, 400, [4, 0, 0, 0]);
+		assert_noop!(
+			SyncCommitteeModule::submit_update(RuntimeOrigin::signed(1), wrong_fork),
+			Error::<Test>::SignatureVerificationFailed
+		);
+
+		let wrong_signer = update(320, &next, 400, &current, &next, false);
+		assert_noop!(
+			SyncCommitteeModule::submit_update(RuntimeOrigin::signed(1), wrong_signer),
+			Error::<Test>::SignatureVerificationFailed
+		);
+
+		let mut wrong_finality = update(320, &current, 400, &current, &next, false);
+		wrong_finality.finalized_header.slot += 1;
+		assert_noop!(
+			SyncCommitteeModule::submit_update(RuntimeOrigin::signed(1), wrong_finality),
+			Error::<Test>::InvalidFinalityBranch
+		);
+
+		let mut wrong_next = update(320, &current, 400, &current, &next, true);
+		wrong_next.next_sync_committee = Some(current.committee.clone());
+		assert_noop!(
+			SyncCommitteeModule::submit_update(RuntimeOrigin::signed(1), wrong_next),
+			Error::<Test>::InvalidNextSyncCommitteeBranch
+		);
+	});
+}
+
+#[test]
+fn submit_update_signs_with_fork_version_of_signature_slot() {
+	new_test_ext().execute_with(|| {
+		let (current, next) = (Committee::new(0), Committee::new(1));
+		initialize(100, &current, &next);
+
+		// The update is attested at slot 384, the first slot of epoch 12.
+		let mut forks = forks();
+		forks
+			.try_push(Fork { epoch: 12, version: [4, 0, 0, 0], layout: StateLayout::Altair })
+			.unwrap();
+		assert_ok!(SyncCommitteeModule::set_fork_schedule(RuntimeOrigin::root(), forks.clone()));
+		assert_eq!(Forks::<Test>::get(), forks);
+
+		let stale_fork = update(320, &current, 400, &current, &next, false);
+		assert_noop!(
+			SyncCommitteeModule::submit_update(RuntimeOrigin::signed(1), stale_fork.clone()),
+			Error::<Test>::SignatureVerificationFailed
+		);
+
+		let mut new_fork = stale_fork;
+		new_fork.sync_aggregate = current.sign(&new_fork.attested_header, 400, [4, 0, 0, 0]);
+		assert_ok!(SyncCommitteeModule::submit_update(RuntimeOrigin::signed(1), new_fork));
+	});
+}
+
+#[test]
+fn submit_update_rotates_sync_committee() {
+	new_test_ext().execute_with(|| {
+		let (current, next, after_next) = (Committee::new(0), Committee::new(1), Committee::new(2));
+		initialize(100, &current, &next);
+
+		// Without a known next sync committee, updates signed in the next period are rejected.
+		assert_noop!(
+			SyncCommitteeModule::submit_update(
+				RuntimeOrigin::signed(1),
+				update(SLOTS_PER_PERIOD + 32, &next, 400, &next, &after_next, true)
+			),
+			Error::<Test>::UnexpectedSyncCommitteePeriod
+		);
+
+		assert_ok!(SyncCommitteeModule::submit_update(
+			RuntimeOrigin::signed(1),
+			update(320, &current, 400, &current, &next, true)
+		));
+		assert_eq!(NextSyncCommittee::<Test>::get(), Some(next.committee.clone()));
+
+		assert_ok!(SyncCommitteeModule::submit_update(
+			RuntimeOrigin::signed(1),
+			update(SLOTS_PER_PERIOD + 32, &next, 400, &next, &after_next, true)
+		));
+		assert_eq!(CurrentSyncCommittee::<Test>::get(), Some(next.committee));
+		assert_eq!(NextSyncCommittee::<Test>::get(), Some(after_next.committee));
+		System::assert_has_event(Event::SyncCommitteeRotated { period: 1 }.into());
+	});
+}
+
+#[test]
+fn electra_updates() {
+	new_test_ext().execute_with(|| {
+		let forks = fixtures::mainnet_forks();
+		let fork_at_slot =
+			|slot| fork_at_epoch(&forks, compute_epoch_at_slot(slot)).unwrap().version;
+
+		let (fork_version, bootstrap) = fixtures::bootstrap("electra/bootstrap.json");
+		assert_eq!(fork_at_slot(bootstrap.header.slot), fork_version);
+		let period = compute_sync_committee_period(bootstrap.header.slot);
+		assert_ok!(SyncCommitteeModule::initialize(
+			RuntimeOrigin::root(),
+			bootstrap,
+			fixtures::MAINNET_GENESIS_VALIDATORS_ROOT.into(),
+			forks.clone(),
+		));
+
+		for (fork_version, update) in fixtures::updates("electra/updates.json") {
+			let slot = update.finalized_header.slot;
+			assert_eq!(fork_at_slot(update.attested_header.slot), fork_version);
+			assert_ok!(SyncCommitteeModule::submit_update(RuntimeOrigin::signed(1), update));
+			assert_eq!(FinalizedHeader::<Test>::get().unwrap().slot, slot);
+		}
+		// The second update is signed by the next sync committee, after the Fulu fork.
+		System::assert_has_event(Event::SyncCommitteeRotated { period: period + 1 }.into());
+	});
+}
+
+fn hex_literal<const N: usize>(hex: &str) -> [u8; N] {
+	hex::decode(hex.trim_start_matches("0x")).unwrap().try_into().unwrap()
+}
+
+/// Loads light client data in the format of the beacon node API
+/// (`/eth/v1/beacon/light_client/bootstrap/{block_root}` and
+/// `/eth/v1/beacon/light_client/updates`).
+mod fixtures {
+	use super::hex_literal;
+	use crate::types::*;
+	use frame_support::{traits::ConstU32, BoundedVec};
+	use serde_json::Value;
+	use sp_core::H256;
+
+	pub const MAINNET_GENESIS_VALIDATORS_ROOT: [u8; 32] = [
+		0x4b, 0x36, 0x3d, 0xb9, 0x4e, 0x28, 0x61, 0x20, 0xd7, 0x6e, 0xb9, 0x05, 0x34, 0x0f, 0xdd,
+		0x4e, 0x54, 0xbf, 0xe9, 0xf0, 0x6b, 0xf3, 0x3f, 0xf6, 0xcf, 0x5a, 0xd2, 0x7f, 0x51, 0x1b,
+		0xfe, 0x95,
+	];
+
+	fn load(path: &str) -> Value {
+		let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures").join(path);
+		let file = std::fs::read(&path).unwrap_or_else(|_| panic!("missing fixture {:?}", path));
+		serde_json::from_slice(&file).unwrap()
+	}
+
+	/// The forks of mainnet since Altair.
+	pub fn mainnet_forks() -> ForkSchedule {
+		let fork = |epoch, version, layout| Fork { epoch, version, layout };
+		BoundedVec::truncate_from(vec![
+			fork(74240, [1, 0, 0, 0], StateLayout::Altair),
+			fork(144896, [2, 0, 0, 0], StateLayout::Altair),
+			fork(194048, [3, 0, 0, 0], StateLayout::Altair),
+			fork(269568, [4, 0, 0, 0], StateLayout::Altair),
+			fork(364032, [5, 0, 0, 0], StateLayout::Electra),
+			fork(411392, [6, 0, 0, 0], StateLayout::Electra),
+		])
+	}
+
+	/// The mainnet fork version of the fork named by the `version` of a beacon node response.
+	fn fork_version(version: &Value) -> ForkVersion {
+		match version.as_str().unwrap() {
+			"altair" => [1, 0, 0, 0],
+			"bellatrix" => [2, 0, 0, 0],
+			"capella" => [3, 0, 0, 0],
+			"deneb" => [4, 0, 0, 0],
+			"electra" => [5, 0, 0, 0],
+			"fulu" => [6, 0, 0, 0],
+			other => panic!("unsupported fork {}", other),
+		}
+	}
+
+	fn number(value: &Value) -> u64 {
+		value.as_str().unwrap().parse().unwrap()
+	}
+
+	fn root(value: &Value) -> H256 {
+		H256(hex_literal(value.as_str().unwrap()))
+	}
+
+	fn branch<const N: u32>(value: &Value) -> BoundedVec<H256, ConstU32<N>> {
+		let branch: Vec<H256> = value.as_array().unwrap().iter().map(root).collect();
+		let len = branch.len();
+		branch
+			.try_into()
+			.unwrap_or_else(|_| panic!("branch of {} hashes is deeper than {}", len, N))
+	}
+
+	fn header(value: &Value) -> BeaconBlockHeader {
+		// Capella and later wrap the beacon header next to the execution payload header.
+		let value = value.get("beacon").unwrap_or(value);
+		BeaconBlockHeader {
+			slot: number(&value["slot"]),
+			proposer_index: number(&value["proposer_index"]),
+			parent_root: root(&value["parent_root"]),
+			state_root: root(&value["state_root"]),
+			body_root: root(&value["body_root"]),
+		}
+	}
+
+	fn sync_committee(value: &Value) -> SyncCommittee {
+		SyncCommittee {
+			pubkeys: value["pubkeys"]
+				.as_array()
+				.unwrap()
+				.iter()
+				.map(|key| hex_literal(key.as_str().unwrap()))
+				.collect::<Vec<_>>()
+				.try_into()
+				.expect("more than SYNC_COMMITTEE_SIZE public keys"),
+			aggregate_pubkey: hex_literal(value["aggregate_pubkey"].as_str().unwrap()),
+		}
+	}
+
+	pub fn bootstrap(path: &str) -> (ForkVersion, LightClientBootstrap) {
+		let json = load(path);
+		let data = &json["data"];
+		let bootstrap = LightClientBootstrap {
+			header: header(&data["header"]),
+			current_sync_committee: sync_committee(&data["current_sync_committee"]),
+			current_sync_committee_branch: branch(&data["current_sync_committee_branch"]),
+		};
+		(fork_version(&json["version"]), bootstrap)
+	}
+
+	pub fn updates(path: &str) -> Vec<(ForkVersion, LightClientUpdate)> {
+		let json = load(path);
+		json.as_array()
+			.unwrap()
+			.iter()
+			.map(|entry| {
+				let data = &entry["data"];
+				let aggregate = &data["sync_aggregate"];
+				let update = LightClientUpdate {
+					attested_header: header(&data["attested_header"]),
+					next_sync_committee: Some(sync_committee(&data["next_sync_committee"])),
+					next_sync_committee_branch: branch(&data["next_sync_committee_branch"]),
+					finalized_header: header(&data["finalized_header"]),
+					finality_branch: branch(&data["finality_branch"]),
+					sync_aggregate: SyncAggregate {
+						sync_committee_bits: hex_literal(
+							aggregate["sync_committee_bits"].as_str().unwrap(),
+						),
+						sync_committee_signature: hex_literal(
+							aggregate["sync_committee_signature"].as_str().unwrap(),
+						),
+					},
+					signature_slot: number(&data["signature_slot"]),
+				};
+				(fork_version(&entry["version"]), update)
+			})
+			.collect()
+	}
+}
@@ -0,0 +1,249 @@
+//! The subset of the Altair light client data structures and their SSZ merkleization needed to
+//! follow the beacon chain sync committee, with the proof indices of the Altair and the Electra
+//! `BeaconState`.
+//!
+//! See <https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/light-client/sync-protocol.md>
+//! and <https://github.com/ethereum/consensus-specs/blob/dev/specs/electra/light-client/sync-protocol.md>.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{traits::ConstU32, BoundedVec, RuntimeDebug};
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_io::hashing::sha2_256;
+use sp_std::vec::Vec;
+
+pub const SYNC_COMMITTEE_SIZE: u32 = 512;
+pub const SLOTS_PER_EPOCH: u64 = 32;
+pub const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 256;
+pub const DOMAIN_SYNC_COMMITTEE: [u8; 4] = [7, 0, 0, 0];
+
+/// Generalized index of `finalized_checkpoint.root` in the `BeaconState` up to Deneb.
+pub const FINALIZED_ROOT_INDEX: u64 = 105;
+pub const FINALIZED_ROOT_DEPTH: u32 = 6;
+/// Generalized index of `current_sync_committee` in the `BeaconState` up to Deneb.
+pub const CURRENT_SYNC_COMMITTEE_INDEX: u64 = 54;
+/// Generalized index of `next_sync_committee` in the `BeaconState` up to Deneb.
+pub const NEXT_SYNC_COMMITTEE_INDEX: u64 = 55;
+pub const SYNC_COMMITTEE_DEPTH: u32 = 5;
+
+/// Generalized index of `finalized_checkpoint.root` since Electra, whose `BeaconState` has more
+/// than 32 fields and thus one more level.
+pub const FINALIZED_ROOT_INDEX_ELECTRA: u64 = 169;
+pub const FINALIZED_ROOT_DEPTH_ELECTRA: u32 = 7;
+/// Generalized index of `current_sync_committee` in the `BeaconState` since Electra.
+pub const CURRENT_SYNC_COMMITTEE_INDEX_ELECTRA: u64 = 86;
+/// Generalized index of `next_sync_committee` in the `BeaconState` since Electra.
+pub const NEXT_SYNC_COMMITTEE_INDEX_ELECTRA: u64 = 87;
+pub const SYNC_COMMITTEE_DEPTH_ELECTRA: u32 = 6;
+
+/// The most forks a fork schedule lists.
+pub const MAX_FORKS: u32 = 16;
+
+pub type PublicKey = [u8; 48];
+pub type Signature = [u8; 96];
+pub type ForkVersion = [u8; 4];
+pub type ForkSchedule = BoundedVec<Fork, ConstU32<MAX_FORKS>>;
+
+/// The shape of the `BeaconState` merkle tree, which determines where the light client proofs
+/// point into it.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum StateLayout {
+	/// Altair up to Deneb.
+	Altair,
+	/// Electra and later.
+	Electra,
+}
+
+impl StateLayout {
+	/// The generalized index and depth of `finalized_checkpoint.root`.
+	pub fn finalized_root(self) -> (u64, u32) {
+		match self {
+			Self::Altair => (FINALIZED_ROOT_INDEX, FINALIZED_ROOT_DEPTH),
+			Self::Electra => (FINALIZED_ROOT_INDEX_ELECTRA, FINALIZED_ROOT_DEPTH_ELECTRA),
+		}
+	}
+
+	/// The generalized index and depth of `current_sync_committee`.
+	pub fn current_sync_committee(self) -> (u64, u32) {
+		match self {
+			Self::Altair => (CURRENT_SYNC_COMMITTEE_INDEX, SYNC_COMMITTEE_DEPTH),
+			Self::Electra => (CURRENT_SYNC_COMMITTEE_INDEX_ELECTRA, SYNC_COMMITTEE_DEPTH_ELECTRA),
+		}
+	}
+
+	/// The generalized index and depth of `next_sync_committee`.
+	pub fn next_sync_committee(self) -> (u64, u32) {
+		match self {
+			Self::Altair => (NEXT_SYNC_COMMITTEE_INDEX, SYNC_COMMITTEE_DEPTH),
+			Self::Electra => (NEXT_SYNC_COMMITTEE_INDEX_ELECTRA, SYNC_COMMITTEE_DEPTH_ELECTRA),
+		}
+	}
+}
+
+/// A fork of the beacon chain, active from `epoch` until the next fork of the schedule.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Fork {
+	pub epoch: u64,
+	pub version: ForkVersion,
+	pub layout: StateLayout,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct BeaconBlockHeader {
+	pub slot: u64,
+	pub proposer_index: u64,
+	pub parent_root: H256,
+	pub state_root: H256,
+	pub body_root: H256,
+}
+
+impl BeaconBlockHeader {
+	pub fn hash_tree_root(&self) -> H256 {
+		merkleize(&[
+			uint64_chunk(self.slot),
+			uint64_chunk(self.proposer_index),
+			self.parent_root.0,
+			self.state_root.0,
+			self.body_root.0,
+		])
+		.into()
+	}
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SyncCommittee {
+	pub pubkeys: BoundedVec<PublicKey, ConstU32<SYNC_COMMITTEE_SIZE>>,
+	pub aggregate_pubkey: PublicKey,
+}
+
+impl SyncCommittee {
+	pub fn hash_tree_root(&self) -> H256 {
+		let pubkeys: Vec<[u8; 32]> = self.pubkeys.iter().map(public_key_root).collect();
+		hash_pair(&merkleize(&pubkeys), &public_key_root(&self.aggregate_pubkey)).into()
+	}
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SyncAggregate {
+	/// `Bitvector[SYNC_COMMITTEE_SIZE]`, least significant bit first.
+	pub sync_committee_bits: [u8; SYNC_COMMITTEE_SIZE as usize / 8],
+	pub sync_committee_signature: Signature,
+}
+
+impl SyncAggregate {
+	pub fn participation(&self) -> u32 {
+		self.sync_committee_bits.iter().map(|byte| byte.count_ones()).sum()
+	}
+
+	pub fn is_participant(&self, index: usize) -> bool {
+		self.sync_committee_bits[index / 8] >> (index % 8) & 1 == 1
+	}
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct LightClientBootstrap {
+	pub header: BeaconBlockHeader,
+	pub current_sync_committee: SyncCommittee,
+	pub current_sync_committee_branch: BoundedVec<H256, ConstU32<SYNC_COMMITTEE_DEPTH_ELECTRA>>,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct LightClientUpdate {
+	pub attested_header: BeaconBlockHeader,
+	pub next_sync_committee: Option<SyncCommittee>,
+	pub next_sync_committee_branch: BoundedVec<H256, ConstU32<SYNC_COMMITTEE_DEPTH_ELECTRA>>,
+	pub finalized_header: BeaconBlockHeader,
+	pub finality_branch: BoundedVec<H256, ConstU32<FINALIZED_ROOT_DEPTH_ELECTRA>>,
+	pub sync_aggregate: SyncAggregate,
+	pub signature_slot: u64,
+}
+
+pub fn compute_epoch_at_slot(slot: u64) -> u64 {
+	slot / SLOTS_PER_EPOCH
+}
+
+pub fn compute_sync_committee_period(slot: u64) -> u64 {
+	compute_epoch_at_slot(slot) / EPOCHS_PER_SYNC_COMMITTEE_PERIOD
+}
+
+/// The fork of `schedule` active at `epoch`, if the schedule starts at or before it.
+pub fn fork_at_epoch(schedule: &[Fork], epoch: u64) -> Option<&Fork> {
+	schedule.iter().rev().find(|fork| fork.epoch <= epoch)
+}
+
+/// Checks that the forks of `schedule` are ordered by strictly increasing epochs.
+pub fn is_valid_fork_schedule(schedule: &[Fork]) -> bool {
+	!schedule.is_empty() && schedule.windows(2).all(|pair| pair[0].epoch < pair[1].epoch)
+}
+
+pub fn compute_domain(
+	domain_type: [u8; 4],
+	fork_version: ForkVersion,
+	genesis_validators_root: H256,
+) -> H256 {
+	let mut version = [0u8; 32];
+	version[..4].copy_from_slice(&fork_version);
+	let fork_data_root = hash_pair(&version, &genesis_validators_root.0);
+
+	let mut domain = [0u8; 32];
+	domain[..4].copy_from_slice(&domain_type);
+	domain[4..].copy_from_slice(&fork_data_root[..28]);
+	domain.into()
+}
+
+pub fn compute_signing_root(object_root: H256, domain: H256) -> H256 {
+	hash_pair(&object_root.0, &domain.0).into()
+}
+
+/// Checks that `leaf` is at the position given by the generalized `index` in the tree with
+/// `root`, using the sibling hashes in `branch`.
+pub fn is_valid_merkle_branch(
+	leaf: H256,
+	branch: &[H256],
+	depth: u32,
+	index: u64,
+	root: H256,
+) -> bool {
+	if branch.len() != depth as usize {
+		return false
+	}
+	let subtree_index = index % (1 << depth);
+	let mut value = leaf.0;
+	for (i, sibling) in branch.iter().enumerate() {
+		value = if subtree_index >> i & 1 == 1 {
+			hash_pair(&sibling.0, &value)
+		} else {
+			hash_pair(&value, &sibling.0)
+		};
+	}
+	value == root.0
+}
+
+pub fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+	let mut preimage = [0u8; 64];
+	preimage[..32].copy_from_slice(left);
+	preimage[32..].copy_from_slice(right);
+	sha2_256(&preimage)
+}
+
+/// Merkleizes `chunks`, padding with zero chunks to the next power of two.
+pub fn merkleize(chunks: &[[u8; 32]]) -> [u8; 32] {
+	let mut layer = chunks.to_vec();
+	layer.resize(chunks.len().next_power_of_two().max(1), [0u8; 32]);
+	while layer.len() > 1 {
+		layer = layer.chunks(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+	}
+	layer[0]
+}
+
+pub(crate) fn uint64_chunk(value: u64) -> [u8; 32] {
+	let mut chunk = [0u8; 32];
+	chunk[..8].copy_from_slice(&value.to_le_bytes());
+	chunk
+}
+
+fn public_key_root(public_key: &PublicKey) -> [u8; 32] {
+	let mut chunks = [0u8; 64];
+	chunks[..48].copy_from_slice(public_key);
+	sha2_256(&chunks)
+}
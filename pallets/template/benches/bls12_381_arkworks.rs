@@ -0,0 +1,18 @@
+//! The arithmetic, serialization, MSM and pairing benchmarks of `ark-algebra-bench-templates`
+//! for the arkworks BLS12-381 curve, the baseline of `bls12_381_optimized`.
+
+use ark_algebra_bench_templates::*;
+use ark_bls12_381::{
+	fq::Fq, fq12::Fq12, fq2::Fq2, fr::Fr, Bls12_381, G1Projective as G1, G2Projective as G2,
+};
+
+bench!(
+	Name = "Bls12_381",
+	Pairing = Bls12_381,
+	G1 = G1,
+	G2 = G2,
+	ScalarField = Fr,
+	G1BaseField = Fq,
+	G2BaseField = Fq2,
+	TargetField = Fq12,
+);
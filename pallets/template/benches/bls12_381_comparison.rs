@@ -0,0 +1,193 @@
+//! Benchmarks of the BLS12-381 primitives of arkworks against the host accelerated curve of
+//! `bls12_381.rs`, side by side.
+//!
+//! Outside the runtime the host functions run natively, so both backends compute the same
+//! arithmetic and the difference is the cost of marshalling the arguments of the host calls.
+//! The remaining speedup of the runtime benchmarks is the cost of the arithmetic in wasm.
+
+use ark_bls12_381::{Bls12_381, Fr, G1Projective, G2Projective};
+use ark_ec::{pairing::Pairing, CurveGroup, VariableBaseMSM};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{test_rng, UniformRand};
+use criterion::{
+	criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, BenchmarkId, Criterion,
+};
+use pallet_template::bls12_381::{
+	Bls12_381Optimized, BlsFrOptimized, G1AffineOptimized, G1ProjectiveOptimized,
+	G2AffineOptimized, G2ProjectiveOptimized,
+};
+
+const MSM_SIZES: [usize; 3] = [16, 128, 1024];
+
+/// The number of pairs of the pairing benchmarks, that of a Groth16 verification.
+const PAIRS: usize = 4;
+
+/// Re-encodes values of one BLS12-381 implementation as the same values of the other.
+fn convert<A: CanonicalSerialize, B: CanonicalDeserialize>(values: &[A]) -> Vec<B> {
+	values
+		.iter()
+		.map(|value| {
+			let mut bytes = Vec::new();
+			value.serialize_uncompressed(&mut bytes).unwrap();
+			B::deserialize_uncompressed_unchecked(&bytes[..]).unwrap()
+		})
+		.collect()
+}
+
+fn random_points<G: CurveGroup>(n: usize) -> Vec<G::Affine> {
+	let rng = &mut test_rng();
+	G::normalize_batch(&(0..n).map(|_| G::rand(rng)).collect::<Vec<_>>())
+}
+
+fn bench_msm<G: VariableBaseMSM>(
+	group: &mut BenchmarkGroup<WallTime>,
+	backend: &str,
+	bases: &[G::MulBase],
+	scalars: &[G::ScalarField],
+) {
+	for size in MSM_SIZES {
+		group.bench_with_input(BenchmarkId::new(backend, size), &size, |b, &size| {
+			b.iter(|| G::msm(&bases[..size], &scalars[..size]).unwrap())
+		});
+	}
+}
+
+fn msm(c: &mut Criterion) {
+	let size = MSM_SIZES[MSM_SIZES.len() - 1];
+	let rng = &mut test_rng();
+	let scalars: Vec<Fr> = (0..size).map(|_| Fr::rand(rng)).collect();
+	let scalars_host: Vec<BlsFrOptimized> = convert(&scalars);
+
+	let mut group = c.benchmark_group("msm_g1");
+	let bases = random_points::<G1Projective>(size);
+	bench_msm::<G1Projective>(&mut group, "arkworks", &bases, &scalars);
+	let bases_host: Vec<G1AffineOptimized> = convert(&bases);
+	bench_msm::<G1ProjectiveOptimized>(&mut group, "host", &bases_host, &scalars_host);
+	group.finish();
+
+	let mut group = c.benchmark_group("msm_g2");
+	let bases = random_points::<G2Projective>(size);
+	bench_msm::<G2Projective>(&mut group, "arkworks", &bases, &scalars);
+	let bases_host: Vec<G2AffineOptimized> = convert(&bases);
+	bench_msm::<G2ProjectiveOptimized>(&mut group, "host", &bases_host, &scalars_host);
+	group.finish();
+}
+
+fn bench_mul<G: CurveGroup>(
+	group: &mut BenchmarkGroup<WallTime>,
+	backend: &str,
+	base: G::Affine,
+	scalar: G::ScalarField,
+) {
+	let projective: G = base.into();
+	group.bench_function(BenchmarkId::new(backend, "projective"), |b| {
+		b.iter(|| projective * scalar)
+	});
+	group.bench_function(BenchmarkId::new(backend, "affine"), |b| b.iter(|| base * scalar));
+}
+
+fn mul(c: &mut Criterion) {
+	let scalar = [Fr::rand(&mut test_rng())];
+	let scalar_host: Vec<BlsFrOptimized> = convert(&scalar);
+
+	let mut group = c.benchmark_group("mul_g1");
+	let base = random_points::<G1Projective>(1);
+	bench_mul::<G1Projective>(&mut group, "arkworks", base[0], scalar[0]);
+	let base_host: Vec<G1AffineOptimized> = convert(&base);
+	bench_mul::<G1ProjectiveOptimized>(&mut group, "host", base_host[0], scalar_host[0]);
+	group.finish();
+
+	let mut group = c.benchmark_group("mul_g2");
+	let base = random_points::<G2Projective>(1);
+	bench_mul::<G2Projective>(&mut group, "arkworks", base[0], scalar[0]);
+	let base_host: Vec<G2AffineOptimized> = convert(&base);
+	bench_mul::<G2ProjectiveOptimized>(&mut group, "host", base_host[0], scalar_host[0]);
+	group.finish();
+}
+
+fn pairing(c: &mut Criterion) {
+	let (g1, g2) = (random_points::<G1Projective>(PAIRS), random_points::<G2Projective>(PAIRS));
+	let g1_host: Vec<G1AffineOptimized> = convert(&g1);
+	let g2_host: Vec<G2AffineOptimized> = convert(&g2);
+
+	let mut group = c.benchmark_group("multi_miller_loop");
+	group.bench_function("arkworks", |b| {
+		b.iter(|| Bls12_381::multi_miller_loop(g1.clone(), g2.clone()))
+	});
+	group.bench_function("host", |b| {
+		b.iter(|| Bls12_381Optimized::multi_miller_loop(g1_host.clone(), g2_host.clone()))
+	});
+	group.finish();
+
+	let mut group = c.benchmark_group("final_exponentiation");
+	let f = Bls12_381::multi_miller_loop(g1.clone(), g2.clone());
+	group.bench_function("arkworks", |b| b.iter(|| Bls12_381::final_exponentiation(f).unwrap()));
+	let f = Bls12_381Optimized::multi_miller_loop(g1_host.clone(), g2_host.clone());
+	group.bench_function("host", |b| {
+		b.iter(|| Bls12_381Optimized::final_exponentiation(f).unwrap())
+	});
+	group.finish();
+
+	let mut group = c.benchmark_group("multi_pairing");
+	group.bench_function("arkworks", |b| {
+		b.iter(|| Bls12_381::multi_pairing(g1.clone(), g2.clone()))
+	});
+	group.bench_function("host", |b| {
+		b.iter(|| Bls12_381Optimized::multi_pairing(g1_host.clone(), g2_host.clone()))
+	});
+	group.finish();
+}
+
+fn bench_serialization<A: CanonicalSerialize + CanonicalDeserialize>(
+	group: &mut BenchmarkGroup<WallTime>,
+	backend: &str,
+	point: &A,
+) {
+	let mut compressed = Vec::new();
+	point.serialize_compressed(&mut compressed).unwrap();
+	let mut uncompressed = Vec::new();
+	point.serialize_uncompressed(&mut uncompressed).unwrap();
+
+	group.bench_function(BenchmarkId::new(backend, "serialize_compressed"), |b| {
+		b.iter(|| {
+			let mut bytes = Vec::with_capacity(compressed.len());
+			point.serialize_compressed(&mut bytes).unwrap();
+			bytes
+		})
+	});
+	group.bench_function(BenchmarkId::new(backend, "serialize_uncompressed"), |b| {
+		b.iter(|| {
+			let mut bytes = Vec::with_capacity(uncompressed.len());
+			point.serialize_uncompressed(&mut bytes).unwrap();
+			bytes
+		})
+	});
+	group.bench_function(BenchmarkId::new(backend, "deserialize_compressed"), |b| {
+		b.iter(|| A::deserialize_compressed(&compressed[..]).unwrap())
+	});
+	group.bench_function(BenchmarkId::new(backend, "deserialize_uncompressed"), |b| {
+		b.iter(|| A::deserialize_uncompressed(&uncompressed[..]).unwrap())
+	});
+	group.bench_function(BenchmarkId::new(backend, "deserialize_uncompressed_unchecked"), |b| {
+		b.iter(|| A::deserialize_uncompressed_unchecked(&uncompressed[..]).unwrap())
+	});
+}
+
+fn serialization(c: &mut Criterion) {
+	let mut group = c.benchmark_group("serialization_g1");
+	let point = random_points::<G1Projective>(1);
+	let point_host: Vec<G1AffineOptimized> = convert(&point);
+	bench_serialization(&mut group, "arkworks", &point[0]);
+	bench_serialization(&mut group, "host", &point_host[0]);
+	group.finish();
+
+	let mut group = c.benchmark_group("serialization_g2");
+	let point = random_points::<G2Projective>(1);
+	let point_host: Vec<G2AffineOptimized> = convert(&point);
+	bench_serialization(&mut group, "arkworks", &point[0]);
+	bench_serialization(&mut group, "host", &point_host[0]);
+	group.finish();
+}
+
+criterion_group!(benches, msm, mul, pairing, serialization);
+criterion_main!(benches);
@@ -0,0 +1,21 @@
+//! The arithmetic, serialization, MSM and pairing benchmarks of `ark-algebra-bench-templates`
+//! for the host accelerated BLS12-381 curve, with the host functions running natively.
+//!
+//! The fields are those of arkworks, only the group operations and pairings call the host.
+
+use ark_algebra_bench_templates::*;
+use ark_bls12_381::{fq::Fq, fq12::Fq12, fq2::Fq2, fr::Fr};
+use pallet_template::bls12_381::{
+	Bls12_381Optimized, G1ProjectiveOptimized as G1Optimized, G2ProjectiveOptimized as G2Optimized,
+};
+
+bench!(
+	Name = "Bls12_381Optimized",
+	Pairing = Bls12_381Optimized,
+	G1 = G1Optimized,
+	G2 = G2Optimized,
+	ScalarField = Fr,
+	G1BaseField = Fq,
+	G2BaseField = Fq2,
+	TargetField = Fq12,
+);
@@ -0,0 +1,141 @@
+//! Splits the host calls of the accelerated BLS12-381 curve into marshalling and arithmetic.
+//!
+//! For each operation `total` runs it on the host curve, with the host functions running
+//! natively, `arithmetic` runs it with arkworks and `marshalling` encodes and decodes its
+//! arguments and result uncompressed, as both sides of the host call do. The counters of the
+//! `host-call-stats` feature give the host calls and bytes of each operation.
+
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{test_rng, UniformRand};
+use criterion::{criterion_group, criterion_main, Criterion};
+use pallet_template::{
+	bls12_381::{
+		Bls12_381Optimized, BlsFrOptimized, G1AffineOptimized, G1ProjectiveOptimized,
+		G2AffineOptimized,
+	},
+	host_stats,
+};
+
+const MSM_SIZE: usize = 128;
+
+/// The number of pairs of the pairing benchmarks, that of a Groth16 verification.
+const PAIRS: usize = 4;
+
+fn encode<T: CanonicalSerialize>(values: &[T]) -> Vec<Vec<u8>> {
+	values
+		.iter()
+		.map(|value| {
+			let mut bytes = Vec::new();
+			value.serialize_uncompressed(&mut bytes).unwrap();
+			bytes
+		})
+		.collect()
+}
+
+fn decode<T: CanonicalDeserialize>(bytes: &[Vec<u8>]) -> Vec<T> {
+	bytes
+		.iter()
+		.map(|bytes| T::deserialize_uncompressed_unchecked(&bytes[..]).unwrap())
+		.collect()
+}
+
+/// Encodes `values` on one side of a host call and decodes them on the other.
+fn round_trip<T: CanonicalSerialize + CanonicalDeserialize>(values: &[T]) -> Vec<T> {
+	decode(&encode(values))
+}
+
+fn convert<A: CanonicalSerialize, B: CanonicalDeserialize>(values: &[A]) -> Vec<B> {
+	decode(&encode(values))
+}
+
+/// Runs `operation` once and prints its host calls and the bytes they marshal.
+fn print_host_calls<R>(name: &str, operation: impl FnOnce() -> R) {
+	host_stats::take();
+	operation();
+	let total = host_stats::take().total();
+	println!(
+		"{name}: {} host calls, {} bytes in, {} bytes out",
+		total.calls, total.bytes_in, total.bytes_out
+	);
+}
+
+fn msm(c: &mut Criterion) {
+	let rng = &mut test_rng();
+	let scalars: Vec<Fr> = (0..MSM_SIZE).map(|_| Fr::rand(rng)).collect();
+	let bases = G1Projective::normalize_batch(
+		&(0..MSM_SIZE).map(|_| G1Projective::rand(rng)).collect::<Vec<_>>(),
+	);
+	let scalars_host: Vec<BlsFrOptimized> = convert(&scalars);
+	let bases_host: Vec<G1AffineOptimized> = convert(&bases);
+	print_host_calls("msm_g1", || G1ProjectiveOptimized::msm(&bases_host, &scalars_host));
+
+	let mut group = c.benchmark_group("msm_g1");
+	group.bench_function("total", |b| {
+		b.iter(|| G1ProjectiveOptimized::msm(&bases_host, &scalars_host).unwrap())
+	});
+	group.bench_function("arithmetic", |b| b.iter(|| G1Projective::msm(&bases, &scalars).unwrap()));
+	let result = [G1Projective::msm(&bases, &scalars).unwrap().into_affine()];
+	group.bench_function("marshalling", |b| {
+		b.iter(|| (round_trip(&bases), round_trip(&scalars), round_trip(&result)))
+	});
+	group.finish();
+}
+
+fn mul(c: &mut Criterion) {
+	let rng = &mut test_rng();
+	let scalar = [Fr::rand(rng)];
+	let base = [G1Projective::rand(rng).into_affine()];
+	let scalar_host: Vec<BlsFrOptimized> = convert(&scalar);
+	let base_host: Vec<G1AffineOptimized> = convert(&base);
+	print_host_calls("mul_affine_g1", || base_host[0] * scalar_host[0]);
+
+	let mut group = c.benchmark_group("mul_affine_g1");
+	group.bench_function("total", |b| b.iter(|| base_host[0] * scalar_host[0]));
+	group.bench_function("arithmetic", |b| b.iter(|| base[0] * scalar[0]));
+	let result = [(base[0] * scalar[0]).into_affine()];
+	group.bench_function("marshalling", |b| {
+		b.iter(|| (round_trip(&base), round_trip(&scalar), round_trip(&result)))
+	});
+	group.finish();
+}
+
+fn pairing(c: &mut Criterion) {
+	let rng = &mut test_rng();
+	let g1: Vec<G1Affine> = (0..PAIRS).map(|_| G1Projective::rand(rng).into_affine()).collect();
+	let g2: Vec<G2Affine> = (0..PAIRS).map(|_| G2Projective::rand(rng).into_affine()).collect();
+	let g1_host: Vec<G1AffineOptimized> = convert(&g1);
+	let g2_host: Vec<G2AffineOptimized> = convert(&g2);
+	print_host_calls("multi_miller_loop", || {
+		Bls12_381Optimized::multi_miller_loop(g1_host.clone(), g2_host.clone())
+	});
+
+	let mut group = c.benchmark_group("multi_miller_loop");
+	group.bench_function("total", |b| {
+		b.iter(|| Bls12_381Optimized::multi_miller_loop(g1_host.clone(), g2_host.clone()))
+	});
+	group.bench_function("arithmetic", |b| {
+		b.iter(|| Bls12_381::multi_miller_loop(g1.clone(), g2.clone()))
+	});
+	let f = Bls12_381::multi_miller_loop(g1.clone(), g2.clone());
+	group.bench_function("marshalling", |b| {
+		b.iter(|| (round_trip(&g1), round_trip(&g2), round_trip(&[f.0])))
+	});
+	group.finish();
+
+	let f_host = Bls12_381Optimized::multi_miller_loop(g1_host.clone(), g2_host.clone());
+	print_host_calls("final_exponentiation", || Bls12_381Optimized::final_exponentiation(f_host));
+
+	let mut group = c.benchmark_group("final_exponentiation");
+	group.bench_function("total", |b| {
+		b.iter(|| Bls12_381Optimized::final_exponentiation(f_host).unwrap())
+	});
+	group.bench_function("arithmetic", |b| b.iter(|| Bls12_381::final_exponentiation(f).unwrap()));
+	let result = [Bls12_381::final_exponentiation(f).unwrap().0];
+	group.bench_function("marshalling", |b| b.iter(|| (round_trip(&[f.0]), round_trip(&result))));
+	group.finish();
+}
+
+criterion_group!(benches, msm, mul, pairing);
+criterion_main!(benches);
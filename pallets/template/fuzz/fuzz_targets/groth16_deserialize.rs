@@ -0,0 +1,74 @@
+//! Fuzzing of the Groth16 decoding paths of `bls12_381.rs`.
+//!
+//! The input is a verifying key, public inputs and a proof, split at the lengths in its first
+//! four bytes. A leading zero byte replaces the verifying key with the fixture key so that the
+//! fuzzer reaches the input and proof decoding. Nothing may panic, and the arkworks and host
+//! accelerated curves must agree.
+
+#![no_main]
+
+use ark_bls12_381::Bls12_381;
+use libfuzzer_sys::fuzz_target;
+use pallet_template::bls12_381::{self, Bls12_381Optimized};
+
+fuzz_target!(|data: &[u8]| {
+	if data.len() < 4 {
+		return
+	}
+	let (lengths, data) = data.split_at(4);
+	let vk_len = (u16::from_le_bytes([lengths[0], lengths[1]]) as usize).min(data.len());
+	let (vk, data) = data.split_at(vk_len);
+	let inputs_len = (u16::from_le_bytes([lengths[2], lengths[3]]) as usize).min(data.len());
+	let (inputs, proof) = data.split_at(inputs_len);
+	let vk = if lengths[0] == 0 { bls12_381::groth16_fixture().0 } else { vk.to_vec() };
+
+	sp_io::TestExternalities::default().execute_with(|| {
+		assert_eq!(
+			bls12_381::do_verify_groth16(vk.clone(), inputs.to_vec(), proof.to_vec()),
+			bls12_381::do_verify_groth16_optimized(vk.clone(), inputs.to_vec(), proof.to_vec()),
+		);
+		let pvk = bls12_381::prepare_verifying_key_groth16::<Bls12_381>(vk.clone());
+		let pvk_host = bls12_381::prepare_verifying_key_groth16::<Bls12_381Optimized>(vk.clone());
+		assert_eq!(pvk.is_ok(), pvk_host.is_ok());
+		if let (Ok(pvk), Ok(pvk_host)) = (pvk, pvk_host) {
+			let prepared =
+				bls12_381::prepare_inputs_groth16::<Bls12_381>(pvk.clone(), inputs.to_vec());
+			let prepared_host = bls12_381::prepare_inputs_groth16::<Bls12_381Optimized>(
+				pvk_host.clone(),
+				inputs.to_vec(),
+			);
+			assert_eq!(prepared, prepared_host);
+			if let Ok(prepared) = prepared {
+				assert_eq!(
+					bls12_381::verify_with_prepared_inputs_groth16::<Bls12_381>(
+						prepared.clone(),
+						pvk,
+						proof.to_vec(),
+					),
+					bls12_381::verify_with_prepared_inputs_groth16::<Bls12_381Optimized>(
+						prepared,
+						pvk_host,
+						proof.to_vec(),
+					),
+				);
+			}
+		}
+
+		// The paths of registered keys, and prepared keys read back from storage.
+		if let Ok(pvk) = bls12_381::prepare_registered_verifying_key_groth16(&vk) {
+			let valid = bls12_381::verify_prepared_groth16(&pvk, inputs, proof);
+			if let Ok(prepared) =
+				bls12_381::prepare_registered_inputs_groth16::<Bls12_381Optimized>(&pvk, inputs)
+			{
+				assert_eq!(
+					bls12_381::verify_prepared_inputs_groth16::<Bls12_381Optimized>(
+						&pvk, &prepared, proof,
+					),
+					valid
+				);
+			}
+		}
+		let _ = bls12_381::verify_prepared_groth16(&vk, inputs, proof);
+		let _ = bls12_381::prepare_registered_inputs_groth16::<Bls12_381>(&vk, inputs);
+	});
+});
@@ -0,0 +1,74 @@
+//! Differential fuzzing of the host accelerated BLS12-381 curve against arkworks.
+//!
+//! Every 32 bytes of the input are a scalar, and each scalar also gives a point of G1 and G2.
+//! Both implementations must compute the same MSMs, scalar multiplications, Miller loop and
+//! final exponentiation.
+
+#![no_main]
+
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use libfuzzer_sys::fuzz_target;
+use pallet_template::bls12_381::{
+	Bls12_381Optimized, BlsFrOptimized, G1AffineOptimized, G1ProjectiveOptimized,
+	G2AffineOptimized, G2ProjectiveOptimized,
+};
+
+fn serialize(value: impl CanonicalSerialize) -> Vec<u8> {
+	let mut bytes = Vec::new();
+	value.serialize_uncompressed(&mut bytes).unwrap();
+	bytes
+}
+
+/// Re-encodes a value of one BLS12-381 implementation as the same value of the other.
+fn convert<A: CanonicalSerialize, B: CanonicalDeserialize>(a: &A) -> B {
+	let mut bytes = Vec::new();
+	a.serialize_uncompressed(&mut bytes).unwrap();
+	B::deserialize_uncompressed(&bytes[..]).unwrap()
+}
+
+fuzz_target!(|data: &[u8]| {
+	let scalars: Vec<Fr> = data.chunks(32).take(8).map(Fr::from_le_bytes_mod_order).collect();
+	if scalars.is_empty() {
+		return
+	}
+	// Pair each scalar with the point of another one.
+	let g1: Vec<G1Affine> = G1Projective::normalize_batch(
+		&scalars.iter().rev().map(|s| G1Affine::generator() * s).collect::<Vec<_>>(),
+	);
+	let g2: Vec<G2Affine> = G2Projective::normalize_batch(
+		&scalars.iter().rev().map(|s| G2Affine::generator() * s).collect::<Vec<_>>(),
+	);
+
+	sp_io::TestExternalities::default().execute_with(|| {
+		let scalars_host: Vec<BlsFrOptimized> = scalars.iter().map(convert).collect();
+		let g1_host: Vec<G1AffineOptimized> = g1.iter().map(convert).collect();
+		let g2_host: Vec<G2AffineOptimized> = g2.iter().map(convert).collect();
+
+		assert_eq!(
+			serialize(G1Projective::msm(&g1, &scalars).unwrap().into_affine()),
+			serialize(G1ProjectiveOptimized::msm(&g1_host, &scalars_host).unwrap().into_affine()),
+		);
+		assert_eq!(
+			serialize(G2Projective::msm(&g2, &scalars).unwrap().into_affine()),
+			serialize(G2ProjectiveOptimized::msm(&g2_host, &scalars_host).unwrap().into_affine()),
+		);
+
+		let expected = serialize((g1[0] * scalars[0]).into_affine());
+		assert_eq!(serialize((g1_host[0] * scalars_host[0]).into_affine()), expected);
+		assert_eq!(serialize((g1_host[0].into_group() * scalars_host[0]).into_affine()), expected);
+		let expected = serialize((g2[0] * scalars[0]).into_affine());
+		assert_eq!(serialize((g2_host[0] * scalars_host[0]).into_affine()), expected);
+		assert_eq!(serialize((g2_host[0].into_group() * scalars_host[0]).into_affine()), expected);
+
+		let expected = Bls12_381::multi_miller_loop(g1, g2);
+		let actual = Bls12_381Optimized::multi_miller_loop(g1_host, g2_host);
+		assert_eq!(serialize(expected.0), serialize(actual.0));
+		assert_eq!(
+			Bls12_381::final_exponentiation(expected).map(|f| serialize(f.0)),
+			Bls12_381Optimized::final_exponentiation(actual).map(|f| serialize(f.0)),
+		);
+	});
+});
@@ -2,169 +2,504 @@
 
 use super::*;
 
-use crate::bls12_381::BlsFrOptimized;
 #[allow(unused)]
 use crate::Pallet as Template;
+use crate::{
+	bls12_381::{BlsFrOptimized, G1AffineOptimized, G2AffineOptimized},
+	bls_signatures::{self, MinPk, MinSig, Variant},
+	marlin::{
+		self,
+		circuit::{CubeCircuit, RepeatedInputCircuit},
+		Marlin,
+	},
+	plonk::{self, prover},
+	snarkpack,
+};
 use ark_bls12_381::{Bls12_381, Fr as BlsFr};
-use ark_ff::{Fp, MontBackend};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, Field, PrimeField};
 use ark_groth16::Groth16;
-use ark_serialize::{CanonicalDeserialize, Compress, Validate};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_snark::SNARK;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use ark_std::{
+	rand::{rngs::StdRng, SeedableRng},
+	vec,
+	vec::Vec,
+};
+use frame_benchmarking::{benchmarks, whitelisted_caller, BenchmarkError};
+use frame_support::{
+	traits::{Currency, EnsureOrigin, Get, ReservableCurrency, UnfilteredDispatchable},
+	BoundedVec,
+};
 use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, Hash};
+
+/// Creates `n` BLS signers and returns their public keys, the signed messages and the aggregate
+/// signature. With `same_message` all signers sign the same message, as in
+/// `fast_aggregate_verify`.
+fn bls_signers<V: Variant>(n: u32, same_message: bool) -> (Vec<Vec<u8>>, Vec<Vec<u8>>, Vec<u8>) {
+	let mut public_keys = Vec::new();
+	let mut messages = Vec::new();
+	let mut signatures = Vec::new();
+	for i in 0..n {
+		let secret_key = BlsFrOptimized::from(i as u64 + 1);
+		let message = if same_message { vec![0u8; 32] } else { vec![i as u8; 32] };
+		public_keys.push(bls_signatures::public_key::<V>(secret_key));
+		signatures.push(bls_signatures::sign::<V>(secret_key, &message, V::DST).unwrap());
+		messages.push(message);
+	}
+	let signature = bls_signatures::aggregate_signatures::<V>(&signatures).unwrap();
+	(public_keys, messages, signature)
+}
+
+fn kzg_encode_point(point: impl CanonicalSerialize) -> Vec<u8> {
+	let mut bytes = Vec::new();
+	point.serialize_compressed(&mut bytes).unwrap();
+	bytes
+}
+
+/// Installs a setup with known `tau` and creates `n` valid KZG openings for it, returned as
+/// commitments, evaluation points, values and proofs.
+fn kzg_openings<T: Config>(n: u32) -> (Vec<Vec<u8>>, Vec<Vec<u8>>, Vec<Vec<u8>>, Vec<Vec<u8>>) {
+	let tau = BlsFrOptimized::from(0x4844u64);
+	let tau_g2 = (G2AffineOptimized::generator() * tau).into_affine();
+	KzgTrustedSetup::<T>::put(<[u8; 96]>::try_from(kzg_encode_point(tau_g2)).unwrap());
+
+	let g1 = G1AffineOptimized::generator();
+	let (mut commitments, mut zs, mut ys, mut proofs) =
+		(Vec::new(), Vec::new(), Vec::new(), Vec::new());
+	for i in 0..n {
+		// Any `p` with `p(tau) = a` and `p(z) = y` has the proof `[(a - y) / (tau - z)]G1`.
+		let a = BlsFrOptimized::from(3 * i as u64 + 1);
+		let z = BlsFrOptimized::from(3 * i as u64 + 2);
+		let y = BlsFrOptimized::from(3 * i as u64 + 3);
+		let q = (a - y) * (tau - z).inverse().unwrap();
+		commitments.push(kzg_encode_point((g1 * a).into_affine()));
+		zs.push(z.into_bigint().to_bytes_be());
+		ys.push(y.into_bigint().to_bytes_be());
+		proofs.push(kzg_encode_point((g1 * q).into_affine()));
+	}
+	(commitments, zs, ys, proofs)
+}
+
+/// Registers the verifying key of a PLONK proof for the cube circuit with
+/// `plonk::MAX_PUBLIC_INPUTS` public inputs and returns its id, the public inputs and the proof.
+fn plonk_registered_proof<T: Config, E: Pairing>(curve: plonk::Curve) -> (u32, Vec<u8>, Vec<u8>) {
+	let circuit =
+		prover::cube_circuit_with_inputs::<E::ScalarField>(3, plonk::MAX_PUBLIC_INPUTS as usize);
+	let pk = prover::setup::<E>(&circuit, E::ScalarField::from(0x5eedu64));
+	let proof = prover::prove(&pk, &circuit);
+	let id = NextPlonkVerifyingKeyId::<T>::get();
+	let vk = BoundedVec::try_from(utils::serialize_argument(pk.vk)).unwrap();
+	PlonkVerifyingKeys::<T>::insert(id, (curve, vk));
+	NextPlonkVerifyingKeyId::<T>::put(id + 1);
+	(id, utils::serialize_argument(circuit.public_inputs()), utils::serialize_argument(proof))
+}
+
+/// Indexes the cube circuit with `marlin::MAX_PUBLIC_INPUTS` public inputs for Marlin and
+/// returns the uncompressed index verifying key, the public inputs and a proof.
+fn marlin_proof() -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+	let mut rng = StdRng::seed_from_u64(0x5eed);
+	let circuit = RepeatedInputCircuit::<BlsFr>::new(3, marlin::MAX_PUBLIC_INPUTS as usize);
+	let (pk, vk) = Marlin::<Bls12_381>::circuit_specific_setup(
+		RepeatedInputCircuit { x: None, ..circuit },
+		&mut rng,
+	)
+	.unwrap();
+	let proof = Marlin::<Bls12_381>::prove(&pk, circuit, &mut rng).unwrap();
+	(
+		utils::serialize_argument(vk),
+		utils::serialize_argument(circuit.public_inputs()),
+		utils::serialize_argument(proof),
+	)
+}
+
+/// Registers the index verifying key of a Marlin proof for the cube circuit and returns its id,
+/// the public inputs and the proof.
+fn marlin_registered_proof<T: Config>() -> (u32, Vec<u8>, Vec<u8>) {
+	let (vk, inputs, proof) = marlin_proof();
+	let id = NextMarlinVerifyingKeyId::<T>::get();
+	MarlinVerifyingKeys::<T>::insert(id, BoundedVec::try_from(vk).unwrap());
+	NextMarlinVerifyingKeyId::<T>::put(id + 1);
+	(id, inputs, proof)
+}
+
+/// Stores the prepared verifying key of the Groth16 fixtures and returns its id, the public
+/// inputs and the proof.
+fn groth16_registered_proof<T: Config>() -> (u32, Vec<u8>, Vec<u8>) {
+	let (vk, c, proof) = bls12_381::groth16_fixture();
+	let pvk = bls12_381::prepare_registered_verifying_key_groth16(&vk).unwrap();
+	let id = NextGroth16VerifyingKeyId::<T>::get();
+	Groth16VerifyingKeyHashes::<T>::insert(id, T::Hashing::hash(&vk));
+	Groth16VerifyingKeyIds::<T>::insert(T::Hashing::hash(&vk), id);
+	Groth16PreparedVerifyingKeys::<T>::insert(id, BoundedVec::try_from(pvk).unwrap());
+	NextGroth16VerifyingKeyId::<T>::put(id + 1);
+	(id, utils::serialize_argument(vec![c]), proof)
+}
+
+/// Registers the fixture key and stores its prepared public inputs, returning their handle and
+/// the proof.
+fn groth16_stored_inputs<T: Config>() -> (u32, Vec<u8>) {
+	let (id, inputs, proof) = groth16_registered_proof::<T>();
+	let pvk = Groth16PreparedVerifyingKeys::<T>::get(id).unwrap();
+	let prepared_inputs =
+		bls12_381::prepare_registered_inputs_groth16::<Bls12_381>(&pvk, &inputs).unwrap();
+	let expires_at = frame_system::Pallet::<T>::block_number() + T::PreparedInputsLifetime::get();
+	let handle = NextPreparedInputsHandle::<T>::get();
+	let inputs = BoundedVec::try_from(inputs).unwrap();
+	PreparedInputs::<T>::insert(handle, (id, prepared_inputs, inputs, expires_at));
+	NextPreparedInputsHandle::<T>::put(handle + 1);
+	(handle, proof)
+}
+
+/// The fixture key and proof with `n` public inputs. The key repeats its point of the fixture
+/// input for the `n - 1` added inputs, whose values sum to zero, so the proof still verifies
+/// and each input costs a full scalar multiplication.
+fn groth16_host_proof(n: u32) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+	let (vk, c, proof) = bls12_381::groth16_fixture();
+	let mut vk = ark_groth16::VerifyingKey::<Bls12_381>::deserialize_uncompressed(&vk[..]).unwrap();
+	let point = vk.gamma_abc_g1[1];
+	let (mut inputs, mut sum) = (vec![c], BlsFr::from(0u64));
+	for i in 1..n {
+		let input = if i + 1 == n { -sum } else { BlsFr::from(i as u64) };
+		sum += input;
+		vk.gamma_abc_g1.push(point);
+		inputs.push(input);
+	}
+	let inputs = inputs.into_iter().flat_map(utils::serialize_argument).collect();
+	(utils::serialize_argument(vk), inputs, proof)
+}
+
+/// The `groth16_verification_host` call, which only exists with the `experimental` feature.
+#[cfg(feature = "experimental")]
+fn groth16_host_call<T: Config>(
+	vk: Vec<u8>,
+	inputs: Vec<u8>,
+	proof: Vec<u8>,
+) -> Result<Call<T>, BenchmarkError> {
+	Ok(Call::<T>::groth16_verification_host { vk, inputs, proof })
+}
+
+/// Skips the benchmark of `groth16_verification_host` without the `experimental` feature.
+#[cfg(not(feature = "experimental"))]
+fn groth16_host_call<T: Config>(
+	_vk: Vec<u8>,
+	_inputs: Vec<u8>,
+	_proof: Vec<u8>,
+) -> Result<Call<T>, BenchmarkError> {
+	Err(BenchmarkError::Skip)
+}
+
+/// Registers a Groth16 verifying key for the cube circuit, installs an aggregation setup and
+/// returns the key id with the public inputs and SnarkPack aggregate of `n` distinct proofs.
+fn snarkpack_registered_aggregate<T: Config>(n: usize) -> (u32, Vec<Vec<u8>>, Vec<u8>) {
+	type E = bls12_381::Bls12_381Optimized;
+	let mut rng = StdRng::seed_from_u64(0x5eed);
+	let (pk, vk) = Groth16::<E>::circuit_specific_setup(
+		CubeCircuit::<BlsFrOptimized> { x: None, repetitions: 1 },
+		&mut rng,
+	)
+	.unwrap();
+	let circuits: Vec<_> =
+		(0..n).map(|i| CubeCircuit::<BlsFrOptimized>::new(i as u64 + 3, 1)).collect();
+	let proofs: Vec<_> = circuits
+		.iter()
+		.map(|circuit| Groth16::<E>::prove(&pk, *circuit, &mut rng).unwrap())
+		.collect();
+	let public_inputs: Vec<_> = circuits.iter().map(|circuit| circuit.public_inputs()).collect();
+	let srs = snarkpack::prover::ProverSrs::<E>::setup(n, &mut rng);
+	let aggregate = snarkpack::prover::aggregate(&srs, &vk, &public_inputs, &proofs);
+
+	let id = NextGroth16VerifyingKeyId::<T>::get();
+	Groth16VerifyingKeys::<T>::insert(
+		id,
+		BoundedVec::try_from(utils::serialize_argument(vk)).unwrap(),
+	);
+	NextGroth16VerifyingKeyId::<T>::put(id + 1);
+	AggregationSrs::<T>::put(BoundedVec::try_from(utils::serialize_argument(srs.vk)).unwrap());
+	(
+		id,
+		public_inputs.into_iter().map(utils::serialize_argument).collect(),
+		utils::serialize_argument(aggregate),
+	)
+}
 
 benchmarks! {
 	groth16_verification {
 		let caller: T::AccountId = whitelisted_caller();
+		let (vk, c, proof) = bls12_381::groth16_fixture();
+		let c = utils::serialize_argument(c);
+	}: _(RawOrigin::Signed(caller), vk, c, proof)
 
-		let vk = <Groth16<bls12_381::Bls12_381Optimized> as SNARK<BlsFrOptimized>>::VerifyingKey::deserialize_with_mode(
-			bls12_381::VK_SERIALIZED,
-			Compress::Yes,
-			Validate::No,
-		)
-		.unwrap();
-		let vk = utils::serialize_argument(vk);
+	groth16_verification_optimized {
+		let caller: T::AccountId = whitelisted_caller();
+		let (vk, c, proof) = bls12_381::groth16_fixture();
+		let c = utils::serialize_argument(c);
+	}: _(RawOrigin::Signed(caller), vk, c, proof)
 
-		let c = Fp::<MontBackend<ark_bls12_381::FrConfig, 4>, 4>::deserialize_with_mode(bls12_381::C_SERIALIZED, Compress::Yes, Validate::No).unwrap();
+	// `groth16_verification_optimized` of a registered key, which reads its prepared form
+	// instead of preparing it.
+	groth16_verification_optimized_prepared {
+		let caller: T::AccountId = whitelisted_caller();
+		let (id, _, _) = groth16_registered_proof::<T>();
+		Groth16VerifyingKeyStatuses::<T>::insert(id, VerifyingKeyStatus::Approved);
+		let (vk, c, proof) = bls12_381::groth16_fixture();
 		let c = utils::serialize_argument(c);
+	}: groth16_verification_optimized(RawOrigin::Signed(caller), vk, c, proof)
 
-		let proof = <Groth16<Bls12_381> as SNARK<BlsFr>>::Proof::deserialize_with_mode(
-			bls12_381::PROOF_SERIALIZED,
-			Compress::Yes,
-			Validate::No,
-		)
-		.unwrap();
-		let proof = utils::serialize_argument(proof);
-	}: _(RawOrigin::Signed(caller), vk, c, proof)
+	// Against `groth16_verification_optimized`, with the whole verification in one host call.
+	// Skipped without the `experimental` feature.
+	groth16_verification_host {
+		let n in 1 .. bls12_381::GROTH16_MAX_PUBLIC_INPUTS;
+		let caller: T::AccountId = whitelisted_caller();
+		let (vk, inputs, proof) = groth16_host_proof(n);
+		let call = groth16_host_call::<T>(vk, inputs, proof)?;
+	}: { call.dispatch_bypass_filter(RawOrigin::Signed(caller).into())?; }
 
-	groth16_verification_optimized {
+	groth16_prepare_inputs {
 		let caller: T::AccountId = whitelisted_caller();
+		let (id, inputs, _) = groth16_registered_proof::<T>();
+	}: _(RawOrigin::Signed(caller), id, inputs)
 
-		let vk = <Groth16<bls12_381::Bls12_381Optimized> as SNARK<BlsFrOptimized>>::VerifyingKey::deserialize_with_mode(
-			bls12_381::VK_SERIALIZED,
-			Compress::Yes,
-			Validate::No,
-		)
-		.unwrap();
-		let vk = utils::serialize_argument(vk);
+	groth16_optimized_prepare_inputs {
+		let caller: T::AccountId = whitelisted_caller();
+		let (id, inputs, _) = groth16_registered_proof::<T>();
+	}: _(RawOrigin::Signed(caller), id, inputs)
 
-		let c = Fp::<MontBackend<ark_bls12_381::FrConfig, 4>, 4>::deserialize_with_mode(bls12_381::C_SERIALIZED, Compress::Yes, Validate::No).unwrap();
+	groth16_prepare_verifying_key {
+		let caller: T::AccountId = whitelisted_caller();
+		let (vk, _, _) = bls12_381::groth16_fixture();
+	}: _(RawOrigin::Signed(caller), vk)
 
-		let c = utils::serialize_argument(c);
+	groth16_optimized_prepare_verifying_key {
+		let caller: T::AccountId = whitelisted_caller();
+		let (vk, _, _) = bls12_381::groth16_fixture();
+	}: _(RawOrigin::Signed(caller), vk)
 
-		let proof = <Groth16<ark_bls12_381::Bls12_381> as SNARK<BlsFr>>::Proof::deserialize_with_mode(
-			bls12_381::PROOF_SERIALIZED,
-			Compress::Yes,
-			Validate::No,
-		)
-		.unwrap();
-		let proof = utils::serialize_argument(proof);
-	}: _(RawOrigin::Signed(caller), vk, c, proof)
+	groth16_verify_with_prepared_inputs {
+		let caller: T::AccountId = whitelisted_caller();
+		let (handle, proof) = groth16_stored_inputs::<T>();
+	}: _(RawOrigin::Signed(caller), handle, proof)
 
-	groth16_prepare_inputs {
+	groth16_optimized_verify_with_prepared_inputs {
 		let caller: T::AccountId = whitelisted_caller();
-		let vk = <Groth16<bls12_381::Bls12_381Optimized> as SNARK<BlsFrOptimized>>::VerifyingKey::deserialize_with_mode(
-			bls12_381::VK_SERIALIZED,
-			Compress::Yes,
-			Validate::No,
-		)
-		.unwrap();
-		let vk = utils::serialize_argument(vk);
-		let pvk = bls12_381::prepare_verifying_key_groth16::<ark_bls12_381::Bls12_381>(vk).unwrap();
+		let (handle, proof) = groth16_stored_inputs::<T>();
+	}: _(RawOrigin::Signed(caller), handle, proof)
 
-		let c: Fp::<MontBackend<ark_bls12_381::FrConfig, 4>, 4> = Fp::deserialize_with_mode(bls12_381::C_SERIALIZED, Compress::Yes, Validate::No).unwrap();
-		let c = utils::serialize_argument(c);
-	}: _(RawOrigin::Signed(caller), pvk, c)
+	bls_verify {
+		let caller: T::AccountId = whitelisted_caller();
+		let (mut public_keys, mut messages, signature) = bls_signers::<MinPk>(1, false);
+	}: _(RawOrigin::Signed(caller), public_keys.remove(0), messages.remove(0), signature)
 
-	groth16_optimized_prepare_inputs {
+	bls_aggregate_verify {
+		let n in 1 .. bls_signatures::MAX_AGGREGATE_SIGNERS;
 		let caller: T::AccountId = whitelisted_caller();
-		let vk = <Groth16<bls12_381::Bls12_381Optimized> as SNARK<BlsFrOptimized>>::VerifyingKey::deserialize_with_mode(
-			bls12_381::VK_SERIALIZED,
-			Compress::Yes,
-			Validate::No,
-		)
-		.unwrap();
-		let vk = utils::serialize_argument(vk);
+		let (public_keys, messages, signature) = bls_signers::<MinPk>(n, false);
+	}: _(RawOrigin::Signed(caller), public_keys, messages, signature)
 
-		let pvk = bls12_381::prepare_verifying_key_groth16::<bls12_381::Bls12_381Optimized>(vk).unwrap();
+	bls_fast_aggregate_verify {
+		let n in 1 .. bls_signatures::MAX_FAST_AGGREGATE_SIGNERS;
+		let caller: T::AccountId = whitelisted_caller();
+		let (public_keys, mut messages, signature) = bls_signers::<MinPk>(n, true);
+	}: _(RawOrigin::Signed(caller), public_keys, messages.remove(0), signature)
 
-		let c = Fp::<MontBackend<ark_bls12_381::FrConfig, 4>, 4>::deserialize_with_mode(bls12_381::C_SERIALIZED, Compress::Yes, Validate::No).unwrap();
-		let c = utils::serialize_argument(c);
-	}: _(RawOrigin::Signed(caller), pvk, c)
+	bls_verify_min_sig {
+		let caller: T::AccountId = whitelisted_caller();
+		let (mut public_keys, mut messages, signature) = bls_signers::<MinSig>(1, false);
+	}: _(RawOrigin::Signed(caller), public_keys.remove(0), messages.remove(0), signature)
 
-	groth16_prepare_verifying_key {
+	bls_aggregate_verify_min_sig {
+		let n in 1 .. bls_signatures::MAX_AGGREGATE_SIGNERS;
+		let caller: T::AccountId = whitelisted_caller();
+		let (public_keys, messages, signature) = bls_signers::<MinSig>(n, false);
+	}: _(RawOrigin::Signed(caller), public_keys, messages, signature)
+
+	bls_fast_aggregate_verify_min_sig {
+		let n in 1 .. bls_signatures::MAX_FAST_AGGREGATE_SIGNERS;
+		let caller: T::AccountId = whitelisted_caller();
+		let (public_keys, mut messages, signature) = bls_signers::<MinSig>(n, true);
+	}: _(RawOrigin::Signed(caller), public_keys, messages.remove(0), signature)
+
+	kzg_set_trusted_setup {
+		let tau_g2 = kzg::ETHEREUM_SETUP_G2.to_vec();
+	}: _(RawOrigin::Root, tau_g2)
+
+	verify_kzg_proof {
+		let caller: T::AccountId = whitelisted_caller();
+		let (mut commitments, mut zs, mut ys, mut proofs) = kzg_openings::<T>(1);
+	}: _(
+		RawOrigin::Signed(caller),
+		commitments.remove(0),
+		zs.remove(0),
+		ys.remove(0),
+		proofs.remove(0)
+	)
+
+	verify_kzg_proof_batch {
+		let n in 1 .. kzg::MAX_BATCH_SIZE;
+		let caller: T::AccountId = whitelisted_caller();
+		let (commitments, zs, ys, proofs) = kzg_openings::<T>(n);
+	}: _(RawOrigin::Signed(caller), commitments, zs, ys, proofs)
+
+	plonk_register_vk {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let circuit = prover::cube_circuit::<BlsFr>(3, 2);
+		let pk = prover::setup::<Bls12_381>(&circuit, BlsFr::from(0x5eedu64));
+		let x_2 = BoundedVec::try_from(utils::serialize_argument(pk.vk.x_2)).unwrap();
+		PlonkSrs::<T>::insert(plonk::Curve::Bls12_381, x_2);
+		let vk = utils::serialize_argument(pk.vk);
+	}: _(RawOrigin::Signed(caller), plonk::Curve::Bls12_381, vk)
+
+	plonk_set_srs {
+		let circuit = prover::cube_circuit::<BlsFr>(3, 2);
+		let pk = prover::setup::<Bls12_381>(&circuit, BlsFr::from(0x5eedu64));
+		let x_2 = utils::serialize_argument(pk.vk.x_2);
+	}: _(RawOrigin::Root, plonk::Curve::Bls12_381, x_2)
+
+	plonk_remove_vk {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let (id, ..) = plonk_registered_proof::<T, Bls12_381>(plonk::Curve::Bls12_381);
+		let deposit = T::VerifyingKeyDeposit::get();
+		T::Currency::reserve(&caller, deposit)?;
+		PlonkVerifyingKeyDeposits::<T>::insert(id, (caller.clone(), deposit));
+	}: _(RawOrigin::Signed(caller), id)
+
+	plonk_verify {
 		let caller: T::AccountId = whitelisted_caller();
+		let (id, inputs, proof) = plonk_registered_proof::<T, Bls12_381>(plonk::Curve::Bls12_381);
+	}: _(RawOrigin::Signed(caller), id, inputs, proof)
 
-		let vk = <Groth16<ark_bls12_381::Bls12_381> as SNARK<BlsFrOptimized>>::VerifyingKey::deserialize_with_mode(
-			bls12_381::VK_SERIALIZED,
-			Compress::Yes,
-			Validate::No,
-		)
-		.unwrap();
-		let vk = utils::serialize_argument(vk);
+	plonk_verify_optimized {
+		let caller: T::AccountId = whitelisted_caller();
+		let (id, inputs, proof) = plonk_registered_proof::<T, Bls12_381>(plonk::Curve::Bls12_381);
+	}: _(RawOrigin::Signed(caller), id, inputs, proof)
+
+	plonk_verify_bn254 {
+		let caller: T::AccountId = whitelisted_caller();
+		let (id, inputs, proof) = plonk_registered_proof::<T, ark_bn254::Bn254>(plonk::Curve::Bn254);
+	}: plonk_verify(RawOrigin::Signed(caller), id, inputs, proof)
 
+	marlin_register_vk {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let (vk, ..) = marlin_proof();
+		let tau_h = marlin::VerifyingKey::<Bls12_381>::deserialize_uncompressed(&vk[..]).unwrap().tau_h;
+		MarlinSrs::<T>::put(BoundedVec::try_from(utils::serialize_argument(tau_h)).unwrap());
 	}: _(RawOrigin::Signed(caller), vk)
 
-	groth16_optimized_prepare_verifying_key {
+	marlin_set_srs {
+		let (vk, ..) = marlin_proof();
+		let tau_h = marlin::VerifyingKey::<Bls12_381>::deserialize_uncompressed(&vk[..]).unwrap().tau_h;
+	}: _(RawOrigin::Root, utils::serialize_argument(tau_h))
+
+	marlin_remove_vk {
 		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let (id, ..) = marlin_registered_proof::<T>();
+		let deposit = T::VerifyingKeyDeposit::get();
+		T::Currency::reserve(&caller, deposit)?;
+		MarlinVerifyingKeyDeposits::<T>::insert(id, (caller.clone(), deposit));
+	}: _(RawOrigin::Signed(caller), id)
 
-		let vk = <Groth16<bls12_381::Bls12_381Optimized> as SNARK<BlsFrOptimized>>::VerifyingKey::deserialize_with_mode(
-			bls12_381::VK_SERIALIZED,
-			Compress::Yes,
-			Validate::No,
-		)
-		.unwrap();
-		let vk = utils::serialize_argument(vk);
+	marlin_verify {
+		let caller: T::AccountId = whitelisted_caller();
+		let (id, inputs, proof) = marlin_registered_proof::<T>();
+	}: _(RawOrigin::Signed(caller), id, inputs, proof)
+
+	marlin_verify_optimized {
+		let caller: T::AccountId = whitelisted_caller();
+		let (id, inputs, proof) = marlin_registered_proof::<T>();
+	}: _(RawOrigin::Signed(caller), id, inputs, proof)
 
+	groth16_register_vk {
+		let caller: T::AccountId = whitelisted_caller();
+		let (vk, _, _) = bls12_381::groth16_fixture();
 	}: _(RawOrigin::Signed(caller), vk)
 
-	groth16_verify_with_prepared_inputs {
+	// Against `groth16_verification_optimized`, which prepares the key on every call.
+	groth16_verify_prepared {
 		let caller: T::AccountId = whitelisted_caller();
-		let vk = <Groth16<bls12_381::Bls12_381Optimized> as SNARK<BlsFrOptimized>>::VerifyingKey::deserialize_with_mode(
-			bls12_381::VK_SERIALIZED,
-			Compress::Yes,
-			Validate::No,
-		)
-		.unwrap();
-		let vk = utils::serialize_argument(vk);
+		let (id, inputs, proof) = groth16_registered_proof::<T>();
+	}: _(RawOrigin::Signed(caller), id, inputs, proof)
 
-		let pvk = bls12_381::prepare_verifying_key_groth16::<ark_bls12_381::Bls12_381>(vk).unwrap();
+	groth16_set_aggregation_srs {
+		let srs = snarkpack::prover::ProverSrs::<Bls12_381>::setup(2, &mut StdRng::seed_from_u64(0x5eed));
+		let srs = utils::serialize_argument(srs.vk);
+	}: _(RawOrigin::Root, srs)
 
-		let c = Fp::<MontBackend<ark_bls12_381::FrConfig, 4>, 4>::deserialize_with_mode(bls12_381::C_SERIALIZED, Compress::Yes, Validate::No).unwrap();
-		let c = utils::serialize_argument(c);
+	// Aggregates of `2^n` proofs.
+	groth16_verify_aggregate {
+		let l in 1 .. snarkpack::rounds(snarkpack::MAX_AGGREGATED_PROOFS as usize);
+		let caller: T::AccountId = whitelisted_caller();
+		let (id, inputs, proof) = snarkpack_registered_aggregate::<T>(1 << l);
+	}: _(RawOrigin::Signed(caller), id, inputs, proof)
 
-		let inputs = bls12_381::prepare_inputs_groth16::<ark_bls12_381::Bls12_381>(pvk.clone(), c).unwrap();
+	groth16_request_verification {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let (id, inputs, proof) = groth16_registered_proof::<T>();
+	}: _(RawOrigin::Signed(caller), id, inputs, proof)
 
-		let proof = <Groth16<ark_bls12_381::Bls12_381> as SNARK<BlsFrOptimized>>::Proof::deserialize_with_mode(
-			bls12_381::PROOF_SERIALIZED,
-			Compress::Yes,
-			Validate::No,
-		)
-		.unwrap();
-		let proof = utils::serialize_argument(proof);
-	}: _(RawOrigin::Signed(caller), inputs, pvk, proof)
+	set_result_submitters {
+		let submitters = (0..offchain::MAX_RESULT_SUBMITTERS)
+			.map(|i| frame_benchmarking::account("submitter", i, 0))
+			.collect::<Vec<T::AccountId>>();
+	}: _(RawOrigin::Root, submitters)
 
-	groth16_optimized_verify_with_prepared_inputs {
+	submit_verification_result {
 		let caller: T::AccountId = whitelisted_caller();
-		let vk = <Groth16<bls12_381::Bls12_381Optimized> as SNARK<BlsFrOptimized>>::VerifyingKey::deserialize_with_mode(
-			bls12_381::VK_SERIALIZED,
-			Compress::Yes,
-			Validate::No,
-		)
-		.unwrap();
-		let vk = utils::serialize_argument(vk);
-		let pvk = bls12_381::prepare_verifying_key_groth16::<bls12_381::Bls12_381Optimized>(vk).unwrap();
-		let c = Fp::<MontBackend<ark_bls12_381::FrConfig, 4>, 4>::deserialize_with_mode(bls12_381::C_SERIALIZED, Compress::Yes, Validate::No).unwrap();
-		let c = utils::serialize_argument(c);
-		let inputs = bls12_381::prepare_inputs_groth16::<bls12_381::Bls12_381Optimized>(pvk.clone(), c).unwrap();
-		let proof = <Groth16<ark_bls12_381::Bls12_381> as SNARK<BlsFrOptimized>>::Proof::deserialize_with_mode(
-			bls12_381::PROOF_SERIALIZED,
-			Compress::Yes,
-			Validate::No,
-		)
-		.unwrap();
-		let proof = utils::serialize_argument(proof);
-	}: _(RawOrigin::Signed(caller), inputs, pvk, proof)
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let (id, inputs, proof) = groth16_registered_proof::<T>();
+		let inputs = BoundedVec::try_from(inputs).unwrap();
+		let proof = BoundedVec::try_from(proof).unwrap();
+		let deposit = T::VerificationRequestDeposit::get();
+		T::Currency::reserve(&caller, deposit)?;
+		VerificationRequests::<T>::insert(0, (caller.clone(), id, inputs, proof));
+		VerificationRequestDeposits::<T>::insert(0, deposit);
+		ResultSubmitters::<T>::put(BoundedVec::try_from(vec![caller.clone()]).unwrap());
+	}: _(RawOrigin::Signed(caller), 0, true)
+
+	// A proof that does not verify, recorded rather than failing the call.
+	groth16_verify_audited {
+		let caller: T::AccountId = whitelisted_caller();
+		let (id, _, proof) = groth16_registered_proof::<T>();
+		let inputs = utils::serialize_argument(vec![BlsFr::from(1u64)]);
+	}: _(RawOrigin::Signed(caller), id, inputs, proof)
+
+	groth16_approve_vk {
+		let origin = T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let (id, _, _) = groth16_registered_proof::<T>();
+	}: _<T::RuntimeOrigin>(origin, id)
+
+	groth16_freeze_vk {
+		let origin = T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let (id, _, _) = groth16_registered_proof::<T>();
+	}: _<T::RuntimeOrigin>(origin, id)
+
+	groth16_deprecate_vk {
+		let origin = T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let (id, _, _) = groth16_registered_proof::<T>();
+	}: _<T::RuntimeOrigin>(origin, id)
+
+	// With a previous version, whose grace period starts.
+	groth16_publish_circuit_version {
+		let origin = T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let name = vec![0; MAX_CIRCUIT_NAME_LEN as usize];
+		let (id, _, _) = groth16_registered_proof::<T>();
+		let bounded_name = CircuitName::try_from(name.clone()).unwrap();
+		CircuitVersions::<T>::insert(&bounded_name, 1, CircuitVersion { vk_id: id, expires_at: None });
+		LatestCircuitVersions::<T>::insert(&bounded_name, 1);
+	}: _<T::RuntimeOrigin>(origin, name, id)
+
+	groth16_verify_circuit {
+		let caller: T::AccountId = whitelisted_caller();
+		let name = vec![0; MAX_CIRCUIT_NAME_LEN as usize];
+		let (id, inputs, proof) = groth16_registered_proof::<T>();
+		let bounded_name = CircuitName::try_from(name.clone()).unwrap();
+		CircuitVersions::<T>::insert(&bounded_name, 1, CircuitVersion { vk_id: id, expires_at: None });
+	}: _(RawOrigin::Signed(caller), name, 1, inputs, proof)
 
 	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
 }
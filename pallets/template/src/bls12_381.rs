@@ -1,49 +1,139 @@
-use ark_bls12_381::{Bls12_381, Fr as BlsFr};
-use ark_ec::pairing::Pairing;
-use ark_ff::Fp;
+use ark_bls12_381::Bls12_381;
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{PrimeField, Zero};
 use ark_groth16::Groth16;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
 use ark_snark::SNARK;
-use ark_std::{
-	io::{Cursor, Error},
-	vec,
-	vec::Vec,
-};
-use frame_support::assert_ok;
+use ark_std::vec::Vec;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
 pub use sp_ark_bls12_381::{
 	fr::Fr as BlsFrOptimized, Bls12_381 as Bls12_381_Host, G1Affine as G1Affine_Host,
 	G1Projective as G1ProjectiveOptimized_Host, G2Affine as G2Affine_Host,
 	G2Projective as G2ProjectiveOptimized_Host, HostFunctions as Bls12_381HostFunctions,
 };
 
+#[cfg(feature = "host-call-stats")]
+use crate::host_stats::{self, HostCall};
 use crate::utils::serialize_argument;
 
+/// Upper bound of the uncompressed Groth16 verifying keys the pallet registers, enough for 34
+/// public inputs.
+pub const GROTH16_MAX_VERIFYING_KEY_LEN: u32 = 4096;
+
+/// Upper bound of the uncompressed public inputs of a queued Groth16 proof.
+pub const GROTH16_MAX_PUBLIC_INPUTS_LEN: u32 = 2048;
+
+/// The most public inputs of a Groth16 verifying key within `GROTH16_MAX_VERIFYING_KEY_LEN`.
+pub const GROTH16_MAX_PUBLIC_INPUTS: u32 = 34;
+
+/// Size of an uncompressed BLS12-381 scalar.
+pub const GROTH16_SCALAR_LEN: u32 = 32;
+
+/// Size of an uncompressed Groth16 proof.
+pub const GROTH16_PROOF_LEN: u32 = 2 * 96 + 192;
+
+/// Upper bound of the uncompressed prepared Groth16 verifying keys the pallet stores.
+pub const GROTH16_MAX_PREPARED_VERIFYING_KEY_LEN: u32 = 8192;
+
+/// The BLS12-381 implementation a Groth16 proof was verified with.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Groth16Backend {
+	/// The curve of arkworks, computed in the runtime.
+	Bls12_381,
+	/// The curve of `sp-ark-bls12-381`, with the expensive operations in host calls.
+	Bls12_381Optimized,
+	/// The curve of arkworks, computed natively in the `groth16_verify_bls12_381` host call.
+	HostGroth16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Groth16Error {
+	/// The verifying key does not decode.
+	InvalidVerifyingKey,
+	/// The proof does not decode.
+	InvalidProof,
+	/// The public inputs do not decode or do not match the verifying key.
+	PublicInputMismatch,
+}
+
 pub struct HostBls12_381 {}
 
+/// Forwards the curve operations to the host. With the `host-call-stats` feature every call and
+/// the bytes it marshals are counted in [`crate::host_stats`].
 impl Bls12_381HostFunctions for HostBls12_381 {
 	fn bls12_381_multi_miller_loop(a: Vec<Vec<u8>>, b: Vec<Vec<u8>>) -> Result<Vec<u8>, ()> {
-		sp_io::elliptic_curves::bls12_381_multi_miller_loop(a, b)
+		#[cfg(feature = "host-call-stats")]
+		let bytes_in = host_stats::len(&a) + host_stats::len(&b);
+		let result = sp_io::elliptic_curves::bls12_381_multi_miller_loop(a, b);
+		#[cfg(feature = "host-call-stats")]
+		host_stats::record(
+			HostCall::MultiMillerLoop,
+			bytes_in,
+			result.as_ref().map_or(0, Vec::len),
+		);
+		result
 	}
 	fn bls12_381_final_exponentiation(f12: Vec<u8>) -> Result<Vec<u8>, ()> {
-		sp_io::elliptic_curves::bls12_381_final_exponentiation(f12)
+		#[cfg(feature = "host-call-stats")]
+		let bytes_in = f12.len();
+		let result = sp_io::elliptic_curves::bls12_381_final_exponentiation(f12);
+		#[cfg(feature = "host-call-stats")]
+		host_stats::record(
+			HostCall::FinalExponentiation,
+			bytes_in,
+			result.as_ref().map_or(0, Vec::len),
+		);
+		result
 	}
 	fn bls12_381_msm_g1(bases: Vec<Vec<u8>>, bigints: Vec<Vec<u8>>) -> Vec<u8> {
-		sp_io::elliptic_curves::bls12_381_msm_g1(bases, bigints)
+		#[cfg(feature = "host-call-stats")]
+		let bytes_in = host_stats::len(&bases) + host_stats::len(&bigints);
+		let result = sp_io::elliptic_curves::bls12_381_msm_g1(bases, bigints);
+		#[cfg(feature = "host-call-stats")]
+		host_stats::record(HostCall::MsmG1, bytes_in, result.len());
+		result
 	}
 	fn bls12_381_msm_g2(bases: Vec<Vec<u8>>, bigints: Vec<Vec<u8>>) -> Vec<u8> {
-		sp_io::elliptic_curves::bls12_381_msm_g2(bases, bigints)
+		#[cfg(feature = "host-call-stats")]
+		let bytes_in = host_stats::len(&bases) + host_stats::len(&bigints);
+		let result = sp_io::elliptic_curves::bls12_381_msm_g2(bases, bigints);
+		#[cfg(feature = "host-call-stats")]
+		host_stats::record(HostCall::MsmG2, bytes_in, result.len());
+		result
 	}
 	fn bls12_381_mul_projective_g1(base: Vec<u8>, scalar: Vec<u8>) -> Vec<u8> {
-		sp_io::elliptic_curves::bls12_381_mul_projective_g1(base, scalar)
+		#[cfg(feature = "host-call-stats")]
+		let bytes_in = base.len() + scalar.len();
+		let result = sp_io::elliptic_curves::bls12_381_mul_projective_g1(base, scalar);
+		#[cfg(feature = "host-call-stats")]
+		host_stats::record(HostCall::MulProjectiveG1, bytes_in, result.len());
+		result
 	}
 	fn bls12_381_mul_affine_g1(base: Vec<u8>, scalar: Vec<u8>) -> Vec<u8> {
-		sp_io::elliptic_curves::bls12_381_mul_affine_g1(base, scalar)
+		#[cfg(feature = "host-call-stats")]
+		let bytes_in = base.len() + scalar.len();
+		let result = sp_io::elliptic_curves::bls12_381_mul_affine_g1(base, scalar);
+		#[cfg(feature = "host-call-stats")]
+		host_stats::record(HostCall::MulAffineG1, bytes_in, result.len());
+		result
 	}
 	fn bls12_381_mul_projective_g2(base: Vec<u8>, scalar: Vec<u8>) -> Vec<u8> {
-		sp_io::elliptic_curves::bls12_381_mul_projective_g2(base, scalar)
+		#[cfg(feature = "host-call-stats")]
+		let bytes_in = base.len() + scalar.len();
+		let result = sp_io::elliptic_curves::bls12_381_mul_projective_g2(base, scalar);
+		#[cfg(feature = "host-call-stats")]
+		host_stats::record(HostCall::MulProjectiveG2, bytes_in, result.len());
+		result
 	}
 	fn bls12_381_mul_affine_g2(base: Vec<u8>, scalar: Vec<u8>) -> Vec<u8> {
-		sp_io::elliptic_curves::bls12_381_mul_affine_g2(base, scalar)
+		#[cfg(feature = "host-call-stats")]
+		let bytes_in = base.len() + scalar.len();
+		let result = sp_io::elliptic_curves::bls12_381_mul_affine_g2(base, scalar);
+		#[cfg(feature = "host-call-stats")]
+		host_stats::record(HostCall::MulAffineG2, bytes_in, result.len());
+		result
 	}
 }
 
@@ -95,121 +185,290 @@ pub const C_SERIALIZED: &[u8] = &[
 	209, 70, 78, 67, 71, 89, 204, 203, 208, 132, 24,
 ];
 
-pub fn do_verify_groth16(vk: Vec<u8>, c: Vec<u8>, proof: Vec<u8>) -> Result<(), Error> {
-	let cursor = Cursor::new(&vk);
-	let vk = <Groth16<Bls12_381> as SNARK<BlsFr>>::VerifyingKey::deserialize_with_mode(
-		cursor,
-		Compress::No,
-		Validate::No,
-	)
-	.unwrap();
-
-	let cursor = Cursor::new(&c);
-	let c = Fp::deserialize_with_mode(cursor, Compress::No, Validate::No).unwrap();
-
-	let cursor = Cursor::new(&proof);
-	let proof = <Groth16<Bls12_381> as SNARK<BlsFr>>::Proof::deserialize_with_mode(
-		cursor,
-		Compress::No,
-		Validate::No,
-	)
-	.unwrap();
-
-	assert_ok!(Groth16::<Bls12_381>::verify(&vk, &[c], &proof));
-
-	Ok(())
+/// The fixture key and proof of the single public input `c`, decoded from their compressed
+/// encodings in `VK_SERIALIZED`, `C_SERIALIZED` and `PROOF_SERIALIZED`. The key and proof are
+/// uncompressed, as the Groth16 calls take them; `c` is left to the caller to encode on its own
+/// or as a list of inputs.
+pub fn groth16_fixture() -> (Vec<u8>, ark_bls12_381::Fr, Vec<u8>) {
+	let vk = ark_groth16::VerifyingKey::<Bls12_381>::deserialize_compressed(VK_SERIALIZED)
+		.expect("the fixture key is valid; qed");
+	let c = ark_bls12_381::Fr::deserialize_compressed(C_SERIALIZED)
+		.expect("the fixture input is valid; qed");
+	let proof = ark_groth16::Proof::<Bls12_381>::deserialize_compressed(PROOF_SERIALIZED)
+		.expect("the fixture proof is valid; qed");
+	(serialize_argument(vk), c, serialize_argument(proof))
 }
 
-pub fn do_verify_groth16_optimized(vk: Vec<u8>, c: Vec<u8>, proof: Vec<u8>) -> Result<(), Error> {
-	let cursor = Cursor::new(&vk);
-	let vk = <Groth16<Bls12_381Optimized> as SNARK<BlsFrOptimized>>::VerifyingKey::deserialize_with_mode(
-					cursor,
-					Compress::No,
-					Validate::No,
-				)
-				.unwrap();
-
-	let cursor = Cursor::new(&c);
-	let c = Fp::deserialize_with_mode(cursor, Compress::No, Validate::No).unwrap();
+/// Verifies `proof` of the single public input `c` against `vk`, all uncompressed, on `Curve`.
+fn verify_groth16<Curve: Pairing>(vk: &[u8], c: &[u8], proof: &[u8]) -> Result<bool, Groth16Error> {
+	let vk =
+		ark_groth16::VerifyingKey::<Curve>::deserialize_with_mode(vk, Compress::No, Validate::Yes)
+			.map_err(|_| Groth16Error::InvalidVerifyingKey)?;
+	let c = Curve::ScalarField::deserialize_with_mode(c, Compress::No, Validate::Yes)
+		.map_err(|_| Groth16Error::PublicInputMismatch)?;
+	let proof =
+		ark_groth16::Proof::<Curve>::deserialize_with_mode(proof, Compress::No, Validate::Yes)
+			.map_err(|_| Groth16Error::InvalidProof)?;
+	<Groth16<Curve> as SNARK<Curve::ScalarField>>::verify(&vk, &[c], &proof)
+		.map_err(|_| Groth16Error::PublicInputMismatch)
+}
 
-	let cursor = Cursor::new(&proof);
+/// Verifies `proof` of the public `inputs`, uncompressed scalars one after the other, against
+/// `vk`, all uncompressed, on `Curve`.
+pub fn verify_groth16_inputs<Curve: Pairing>(
+	vk: &[u8],
+	inputs: &[u8],
+	proof: &[u8],
+) -> Result<bool, Groth16Error> {
+	let vk =
+		ark_groth16::VerifyingKey::<Curve>::deserialize_with_mode(vk, Compress::No, Validate::Yes)
+			.map_err(|_| Groth16Error::InvalidVerifyingKey)?;
+	let scalar_len = Curve::ScalarField::default().uncompressed_size();
+	if inputs.len() % scalar_len != 0 {
+		return Err(Groth16Error::PublicInputMismatch)
+	}
+	let inputs = inputs
+		.chunks(scalar_len)
+		.map(|input| {
+			Curve::ScalarField::deserialize_with_mode(input, Compress::No, Validate::Yes)
+				.map_err(|_| Groth16Error::PublicInputMismatch)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
 	let proof =
-		<Groth16<Bls12_381Optimized> as SNARK<BlsFrOptimized>>::Proof::deserialize_with_mode(
-			cursor,
-			Compress::No,
-			Validate::No,
-		)
-		.unwrap();
+		ark_groth16::Proof::<Curve>::deserialize_with_mode(proof, Compress::No, Validate::Yes)
+			.map_err(|_| Groth16Error::InvalidProof)?;
+	<Groth16<Curve> as SNARK<Curve::ScalarField>>::verify(&vk, &inputs, &proof)
+		.map_err(|_| Groth16Error::PublicInputMismatch)
+}
 
-	assert_ok!(Groth16::<Bls12_381Optimized>::verify(&vk, &[c], &proof));
+pub fn do_verify_groth16(vk: Vec<u8>, c: Vec<u8>, proof: Vec<u8>) -> Result<bool, Groth16Error> {
+	verify_groth16::<Bls12_381>(&vk, &c, &proof)
+}
 
-	Ok(())
+pub fn do_verify_groth16_optimized(
+	vk: Vec<u8>,
+	c: Vec<u8>,
+	proof: Vec<u8>,
+) -> Result<bool, Groth16Error> {
+	verify_groth16::<Bls12_381Optimized>(&vk, &c, &proof)
 }
 
-pub fn prepare_inputs_groth16<Curve: Pairing>(pvk: Vec<u8>, c: Vec<u8>) -> Result<Vec<u8>, Error> {
-	let cursor = Cursor::new(&pvk);
+pub fn prepare_inputs_groth16<Curve: Pairing>(
+	pvk: Vec<u8>,
+	c: Vec<u8>,
+) -> Result<Vec<u8>, Groth16Error> {
 	let pvk = ark_groth16::PreparedVerifyingKey::<Curve>::deserialize_with_mode(
-		cursor,
+		&pvk[..],
 		Compress::No,
-		Validate::No,
+		Validate::Yes,
 	)
-	.unwrap();
-
-	let cursor = Cursor::new(&c);
-	let c = Curve::ScalarField::deserialize_with_mode(cursor, Compress::No, Validate::No).unwrap();
-
-	let inputs = Groth16::<Curve>::prepare_inputs(&pvk, &[c]).unwrap();
-
+	.map_err(|_| Groth16Error::InvalidVerifyingKey)?;
+	let c = Curve::ScalarField::deserialize_with_mode(&c[..], Compress::No, Validate::Yes)
+		.map_err(|_| Groth16Error::PublicInputMismatch)?;
+	let inputs = Groth16::<Curve>::prepare_inputs(&pvk, &[c])
+		.map_err(|_| Groth16Error::PublicInputMismatch)?;
 	Ok(serialize_argument(inputs))
 }
 
-pub fn prepare_verifying_key_groth16<
-	Curve: Pairing<ScalarField = ark_ff::Fp<ark_ff::MontBackend<ark_bls12_381::FrConfig, 4>, 4>>,
->(
-	vk: Vec<u8>,
-) -> Result<Vec<u8>, Error> {
-	let cursor = Cursor::new(&vk);
-	let vk = <Groth16<Curve> as SNARK<BlsFrOptimized>>::VerifyingKey::deserialize_with_mode(
-		cursor,
+pub fn prepare_verifying_key_groth16<Curve: Pairing>(vk: Vec<u8>) -> Result<Vec<u8>, Groth16Error> {
+	let vk = ark_groth16::VerifyingKey::<Curve>::deserialize_with_mode(
+		&vk[..],
 		Compress::No,
-		Validate::No,
+		Validate::Yes,
 	)
-	.unwrap();
-	let pvk = ark_groth16::verifier::prepare_verifying_key::<Curve>(&vk);
-	let mut serialized_pvk = vec![0u8; pvk.serialized_size(Compress::No)];
-	let mut cursor = Cursor::new(&mut serialized_pvk[..]);
-	let _ = &pvk.serialize_uncompressed(&mut cursor).unwrap();
-	Ok(serialized_pvk)
+	.map_err(|_| Groth16Error::InvalidVerifyingKey)?;
+	Ok(serialize_argument(ark_groth16::prepare_verifying_key(&vk)))
 }
 
-pub fn verify_with_prepared_inputs_groth16<
-	Curve: Pairing<ScalarField = ark_ff::Fp<ark_ff::MontBackend<ark_bls12_381::FrConfig, 4>, 4>>,
->(
+/// Checks that the uncompressed Groth16 verifying key `vk` decodes to valid points of `Curve`.
+pub fn validate_verifying_key_groth16<Curve: Pairing>(vk: &[u8]) -> Result<(), Groth16Error> {
+	ark_groth16::VerifyingKey::<Curve>::deserialize_with_mode(vk, Compress::No, Validate::Yes)
+		.map(|_| ())
+		.map_err(|_| Groth16Error::InvalidVerifyingKey)
+}
+
+pub fn verify_with_prepared_inputs_groth16<Curve: Pairing>(
 	inputs: Vec<u8>,
 	pvk: Vec<u8>,
 	proof: Vec<u8>,
-) -> Result<(), Error> {
-	let cursor = Cursor::new(&pvk);
+) -> Result<bool, Groth16Error> {
 	let pvk = ark_groth16::PreparedVerifyingKey::<Curve>::deserialize_with_mode(
-		cursor,
+		&pvk[..],
 		Compress::No,
-		Validate::No,
+		Validate::Yes,
 	)
-	.unwrap();
-	let cursor = Cursor::new(&proof);
-	let proof = <Groth16<Curve> as SNARK<BlsFrOptimized>>::Proof::deserialize_with_mode(
-		cursor,
+	.map_err(|_| Groth16Error::InvalidVerifyingKey)?;
+	let proof =
+		ark_groth16::Proof::<Curve>::deserialize_with_mode(&proof[..], Compress::No, Validate::Yes)
+			.map_err(|_| Groth16Error::InvalidProof)?;
+	let inputs = Curve::G1::deserialize_with_mode(&inputs[..], Compress::No, Validate::Yes)
+		.map_err(|_| Groth16Error::PublicInputMismatch)?;
+	Groth16::<Curve>::verify_proof_with_prepared_inputs(&pvk, &proof, &inputs)
+		.map_err(|_| Groth16Error::InvalidProof)
+}
+
+/// Validates the uncompressed Groth16 verifying key `vk` and returns its uncompressed prepared
+/// form for the host accelerated curve, with `e(alpha, beta)` and the negated `gamma` and
+/// `delta` precomputed.
+pub fn prepare_registered_verifying_key_groth16(vk: &[u8]) -> Result<Vec<u8>, Groth16Error> {
+	let vk = ark_groth16::VerifyingKey::<Bls12_381Optimized>::deserialize_with_mode(
+		vk,
 		Compress::No,
-		Validate::No,
+		Validate::Yes,
 	)
-	.unwrap();
-	let cursor = Cursor::new(&inputs);
-	let inputs = <Curve as ark_ec::pairing::Pairing>::G1::deserialize_with_mode(
-		cursor,
+	.map_err(|_| Groth16Error::InvalidVerifyingKey)?;
+	Ok(serialize_argument(ark_groth16::prepare_verifying_key(&vk)))
+}
+
+/// Size of the uncompressed G1 point of prepared public inputs.
+pub const GROTH16_PREPARED_INPUTS_LEN: usize = 96;
+
+fn decode_prepared_verifying_key<Curve: Pairing>(
+	pvk: &[u8],
+) -> Result<ark_groth16::PreparedVerifyingKey<Curve>, Groth16Error> {
+	ark_groth16::PreparedVerifyingKey::deserialize_with_mode(pvk, Compress::No, Validate::No)
+		.map_err(|_| Groth16Error::InvalidVerifyingKey)
+}
+
+/// Decodes the verifying key a prepared key from `prepare_registered_verifying_key_groth16`
+/// starts with, which decodes on either BLS12-381 curve.
+fn decode_registered_verifying_key<Curve: Pairing>(
+	pvk: &[u8],
+) -> Result<ark_groth16::VerifyingKey<Curve>, Groth16Error> {
+	ark_groth16::VerifyingKey::deserialize_with_mode(pvk, Compress::No, Validate::No)
+		.map_err(|_| Groth16Error::InvalidVerifyingKey)
+}
+
+/// A BLS12-381 curve that verifies against the prepared keys from
+/// `prepare_registered_verifying_key_groth16`, which are prepared for the host accelerated
+/// curve.
+pub trait RegisteredKeyCurve: Pairing {
+	/// Checks `proof` for the prepared public inputs against the prepared key `pvk`.
+	fn verify_registered(
+		pvk: &[u8],
+		proof: &ark_groth16::Proof<Self>,
+		prepared_inputs: &Self::G1,
+	) -> Result<bool, Groth16Error>;
+}
+
+impl RegisteredKeyCurve for Bls12_381Optimized {
+	fn verify_registered(
+		pvk: &[u8],
+		proof: &ark_groth16::Proof<Self>,
+		prepared_inputs: &Self::G1,
+	) -> Result<bool, Groth16Error> {
+		let pvk = decode_prepared_verifying_key::<Self>(pvk)?;
+		Groth16::<Self>::verify_proof_with_prepared_inputs(&pvk, proof, prepared_inputs)
+			.map_err(|_| Groth16Error::InvalidProof)
+	}
+}
+
+/// The precomputed G2 points of the host accelerated curve are plain affine points, which do
+/// not decode as the line coefficients of arkworks. Instead of preparing the key again, which
+/// costs a pairing, `e(alpha, -beta)` joins the pairing product of the check.
+impl RegisteredKeyCurve for Bls12_381 {
+	fn verify_registered(
+		pvk: &[u8],
+		proof: &ark_groth16::Proof<Self>,
+		prepared_inputs: &Self::G1,
+	) -> Result<bool, Groth16Error> {
+		let vk = decode_registered_verifying_key::<Self>(pvk)?;
+		Ok(Self::multi_pairing(
+			[proof.a, prepared_inputs.into_affine(), proof.c, vk.alpha_g1],
+			[proof.b, -vk.gamma_g2, -vk.delta_g2, -vk.beta_g2],
+		)
+		.is_zero())
+	}
+}
+
+fn decode_proof<Curve: Pairing>(proof: &[u8]) -> Result<ark_groth16::Proof<Curve>, Groth16Error> {
+	ark_groth16::Proof::deserialize_with_mode(proof, Compress::No, Validate::Yes)
+		.map_err(|_| Groth16Error::InvalidProof)
+}
+
+/// `gamma_abc_0 + sum x_i gamma_abc_i` for the uncompressed public inputs `x_i`, as
+/// `Groth16::prepare_inputs` computes it, from the verifying key alone.
+fn prepare_inputs<Curve: Pairing>(
+	vk: &ark_groth16::VerifyingKey<Curve>,
+	public_inputs: &[u8],
+) -> Result<Curve::G1, Groth16Error> {
+	let public_inputs = Vec::<Curve::ScalarField>::deserialize_with_mode(
+		public_inputs,
 		Compress::No,
-		Validate::No,
+		Validate::Yes,
 	)
-	.unwrap();
-	Groth16::<Curve>::verify_proof_with_prepared_inputs(&pvk, &proof, &inputs).unwrap();
-	Ok(())
+	.map_err(|_| Groth16Error::PublicInputMismatch)?;
+	if public_inputs.len() + 1 != vk.gamma_abc_g1.len() {
+		return Err(Groth16Error::PublicInputMismatch)
+	}
+	let mut prepared_inputs = vk.gamma_abc_g1[0].into_group();
+	for (input, base) in public_inputs.iter().zip(&vk.gamma_abc_g1[1..]) {
+		prepared_inputs += base.mul_bigint(input.into_bigint());
+	}
+	Ok(prepared_inputs)
+}
+
+/// Verifies `proof` for the uncompressed public inputs against an uncompressed prepared key
+/// from `prepare_registered_verifying_key_groth16`.
+pub fn verify_prepared_groth16(
+	pvk: &[u8],
+	public_inputs: &[u8],
+	proof: &[u8],
+) -> Result<bool, Groth16Error> {
+	let pvk = decode_prepared_verifying_key::<Bls12_381Optimized>(pvk)?;
+	let prepared_inputs = prepare_inputs(&pvk.vk, public_inputs)?;
+	let proof = decode_proof(proof)?;
+	Groth16::<Bls12_381Optimized>::verify_proof_with_prepared_inputs(&pvk, &proof, &prepared_inputs)
+		.map_err(|_| Groth16Error::InvalidProof)
+}
+
+/// Verifies `proof` of the single uncompressed public input `c` against an uncompressed
+/// prepared key from `prepare_registered_verifying_key_groth16`.
+pub fn verify_prepared_groth16_input(
+	pvk: &[u8],
+	c: &[u8],
+	proof: &[u8],
+) -> Result<bool, Groth16Error> {
+	let pvk = decode_prepared_verifying_key::<Bls12_381Optimized>(pvk)?;
+	let c = BlsFrOptimized::deserialize_with_mode(c, Compress::No, Validate::Yes)
+		.map_err(|_| Groth16Error::PublicInputMismatch)?;
+	if pvk.vk.gamma_abc_g1.len() != 2 {
+		return Err(Groth16Error::PublicInputMismatch)
+	}
+	let prepared_inputs = Groth16::<Bls12_381Optimized>::prepare_inputs(&pvk, &[c])
+		.map_err(|_| Groth16Error::PublicInputMismatch)?;
+	let proof = decode_proof(proof)?;
+	Groth16::<Bls12_381Optimized>::verify_proof_with_prepared_inputs(&pvk, &proof, &prepared_inputs)
+		.map_err(|_| Groth16Error::InvalidProof)
+}
+
+/// Prepares the uncompressed public inputs on either BLS12-381 curve for an uncompressed
+/// prepared key from `prepare_registered_verifying_key_groth16`, returning the uncompressed point
+/// `gamma_abc_0 + sum x_i gamma_abc_i`.
+pub fn prepare_registered_inputs_groth16<Curve: Pairing>(
+	pvk: &[u8],
+	public_inputs: &[u8],
+) -> Result<[u8; GROTH16_PREPARED_INPUTS_LEN], Groth16Error> {
+	let vk = decode_registered_verifying_key::<Curve>(pvk)?;
+	let prepared_inputs = prepare_inputs(&vk, public_inputs)?;
+	let mut bytes = [0u8; GROTH16_PREPARED_INPUTS_LEN];
+	prepared_inputs
+		.serialize_uncompressed(&mut bytes[..])
+		.map_err(|_| Groth16Error::InvalidVerifyingKey)?;
+	Ok(bytes)
+}
+
+/// Verifies `proof` on either BLS12-381 curve against an uncompressed prepared key from
+/// `prepare_registered_verifying_key_groth16` and inputs prepared by
+/// `prepare_registered_inputs_groth16`.
+pub fn verify_prepared_inputs_groth16<Curve: RegisteredKeyCurve>(
+	pvk: &[u8],
+	prepared_inputs: &[u8],
+	proof: &[u8],
+) -> Result<bool, Groth16Error> {
+	let prepared_inputs =
+		Curve::G1::deserialize_with_mode(prepared_inputs, Compress::No, Validate::No)
+			.map_err(|_| Groth16Error::PublicInputMismatch)?;
+	let proof = decode_proof::<Curve>(proof)?;
+	Curve::verify_registered(pvk, &proof, &prepared_inputs)
 }
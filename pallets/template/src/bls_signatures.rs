@@ -0,0 +1,237 @@
+//! BLS signatures over BLS12-381 following the IETF BLS signature draft
+//! (<https://datatracker.ietf.org/doc/draft-irtf-cfrg-bls-signature/>).
+//!
+//! Both variants are supported: `MinPk` (public keys in G1, signatures in G2) and `MinSig`
+//! (public keys in G2, signatures in G1). All pairing checks run on [`Bls12_381Optimized`], so
+//! the Miller loops and the final exponentiation are executed by the host.
+
+use crate::{
+	bls12_381::{Bls12_381Optimized, BlsFrOptimized, G1AffineOptimized, G2AffineOptimized},
+	hash_to_curve,
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::Zero;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use ark_std::{vec, vec::Vec};
+
+/// Domain separation tag of the `MinPk` basic scheme.
+pub const DST_MIN_PK_NUL: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+/// Domain separation tag of the `MinPk` proof-of-possession scheme.
+pub const DST_MIN_PK_POP: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+/// Domain separation tag of the `MinSig` basic scheme.
+pub const DST_MIN_SIG_NUL: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_";
+/// Domain separation tag of the `MinSig` proof-of-possession scheme.
+pub const DST_MIN_SIG_POP: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+
+/// The most public keys the pallet verifies an aggregate signature of distinct messages against,
+/// each of them costing a Miller loop.
+pub const MAX_AGGREGATE_SIGNERS: u32 = 64;
+/// The most public keys the pallet verifies an aggregate signature of one message against, which
+/// only costs a G1 or G2 addition per key.
+pub const MAX_FAST_AGGREGATE_SIGNERS: u32 = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+	/// A public key could not be decoded or is not in the prime order subgroup.
+	InvalidPublicKey,
+	/// A signature could not be decoded or is not in the prime order subgroup.
+	InvalidSignature,
+	/// The message could not be hashed to the curve.
+	HashToCurve,
+	/// No public keys or signatures were supplied.
+	EmptyInput,
+	/// The number of public keys and messages differ.
+	LengthMismatch,
+}
+
+/// A BLS signature variant, i.e. the assignment of public keys and signatures to G1 and G2.
+pub trait Variant {
+	type PublicKey: AffineRepr<ScalarField = BlsFrOptimized>;
+	type Signature: AffineRepr<ScalarField = BlsFrOptimized>;
+
+	/// The default domain separation tag, used by the proof-of-possession scheme.
+	const DST: &'static [u8];
+
+	/// Hashes `message` into the signature group.
+	fn hash_to_signature_group(message: &[u8], dst: &[u8]) -> Result<Self::Signature, Error>;
+
+	/// Checks `prod e(pk_i, H(m_i)) == e(g, signature)` with a single multi-pairing.
+	fn pairing_check(
+		public_keys: Vec<Self::PublicKey>,
+		hashes: Vec<Self::Signature>,
+		signature: Self::Signature,
+	) -> bool;
+}
+
+/// Public keys in G1 (48 bytes), signatures in G2 (96 bytes).
+pub struct MinPk;
+
+/// Public keys in G2 (96 bytes), signatures in G1 (48 bytes).
+pub struct MinSig;
+
+impl Variant for MinPk {
+	type PublicKey = G1AffineOptimized;
+	type Signature = G2AffineOptimized;
+
+	const DST: &'static [u8] = DST_MIN_PK_POP;
+
+	fn hash_to_signature_group(message: &[u8], dst: &[u8]) -> Result<Self::Signature, Error> {
+		hash_to_curve::hash_to_g2(message, dst).map_err(|_| Error::HashToCurve)
+	}
+
+	fn pairing_check(
+		mut public_keys: Vec<Self::PublicKey>,
+		mut hashes: Vec<Self::Signature>,
+		signature: Self::Signature,
+	) -> bool {
+		public_keys.push(-G1AffineOptimized::generator());
+		hashes.push(signature);
+		Bls12_381Optimized::multi_pairing(public_keys, hashes).is_zero()
+	}
+}
+
+impl Variant for MinSig {
+	type PublicKey = G2AffineOptimized;
+	type Signature = G1AffineOptimized;
+
+	const DST: &'static [u8] = DST_MIN_SIG_POP;
+
+	fn hash_to_signature_group(message: &[u8], dst: &[u8]) -> Result<Self::Signature, Error> {
+		hash_to_curve::hash_to_g1(message, dst).map_err(|_| Error::HashToCurve)
+	}
+
+	fn pairing_check(
+		mut public_keys: Vec<Self::PublicKey>,
+		mut hashes: Vec<Self::Signature>,
+		signature: Self::Signature,
+	) -> bool {
+		hashes.push(-signature);
+		public_keys.push(G2AffineOptimized::generator());
+		Bls12_381Optimized::multi_pairing(hashes, public_keys).is_zero()
+	}
+}
+
+/// Decodes a compressed public key, including the subgroup check.
+pub fn decode_public_key<V: Variant>(bytes: &[u8]) -> Result<V::PublicKey, Error> {
+	let public_key = V::PublicKey::deserialize_with_mode(bytes, Compress::Yes, Validate::Yes)
+		.map_err(|_| Error::InvalidPublicKey)?;
+	if public_key.is_zero() {
+		return Err(Error::InvalidPublicKey)
+	}
+	Ok(public_key)
+}
+
+/// Decodes a compressed signature, including the subgroup check.
+pub fn decode_signature<V: Variant>(bytes: &[u8]) -> Result<V::Signature, Error> {
+	V::Signature::deserialize_with_mode(bytes, Compress::Yes, Validate::Yes)
+		.map_err(|_| Error::InvalidSignature)
+}
+
+fn encode_compressed(point: impl CanonicalSerialize) -> Vec<u8> {
+	let mut bytes = vec![0u8; point.compressed_size()];
+	point
+		.serialize_compressed(&mut bytes[..])
+		.expect("buffer has the compressed size; qed");
+	bytes
+}
+
+/// Derives the compressed public key of `secret_key`.
+pub fn public_key<V: Variant>(secret_key: BlsFrOptimized) -> Vec<u8> {
+	encode_compressed(V::PublicKey::generator() * secret_key)
+}
+
+/// Signs `message` with `secret_key`, returning the compressed signature.
+///
+/// This is not constant time and only meant for tests and benchmarks.
+pub fn sign<V: Variant>(
+	secret_key: BlsFrOptimized,
+	message: &[u8],
+	dst: &[u8],
+) -> Result<Vec<u8>, Error> {
+	Ok(encode_compressed(V::hash_to_signature_group(message, dst)? * secret_key))
+}
+
+/// Verifies a single signature on `message`.
+pub fn verify<V: Variant>(
+	public_key: &[u8],
+	message: &[u8],
+	signature: &[u8],
+	dst: &[u8],
+) -> Result<bool, Error> {
+	let public_key = decode_public_key::<V>(public_key)?;
+	let signature = decode_signature::<V>(signature)?;
+	let hash = V::hash_to_signature_group(message, dst)?;
+	Ok(V::pairing_check(vec![public_key], vec![hash], signature))
+}
+
+/// Verifies an aggregate signature over distinct `(public_key, message)` pairs.
+///
+/// In the basic scheme the caller is responsible for rejecting duplicate messages.
+pub fn aggregate_verify<V: Variant>(
+	public_keys: &[Vec<u8>],
+	messages: &[Vec<u8>],
+	signature: &[u8],
+	dst: &[u8],
+) -> Result<bool, Error> {
+	if public_keys.is_empty() {
+		return Err(Error::EmptyInput)
+	}
+	if public_keys.len() != messages.len() {
+		return Err(Error::LengthMismatch)
+	}
+	let public_keys = public_keys
+		.iter()
+		.map(|public_key| decode_public_key::<V>(public_key))
+		.collect::<Result<Vec<_>, _>>()?;
+	let hashes = messages
+		.iter()
+		.map(|message| V::hash_to_signature_group(message, dst))
+		.collect::<Result<Vec<_>, _>>()?;
+	let signature = decode_signature::<V>(signature)?;
+	Ok(V::pairing_check(public_keys, hashes, signature))
+}
+
+/// Verifies an aggregate signature of several signers on the same `message`.
+///
+/// Only sound if every public key comes with a verified proof of possession.
+pub fn fast_aggregate_verify<V: Variant>(
+	public_keys: &[Vec<u8>],
+	message: &[u8],
+	signature: &[u8],
+	dst: &[u8],
+) -> Result<bool, Error> {
+	let aggregate = aggregate_public_keys_unchecked::<V>(public_keys)?;
+	let signature = decode_signature::<V>(signature)?;
+	let hash = V::hash_to_signature_group(message, dst)?;
+	Ok(V::pairing_check(vec![aggregate], vec![hash], signature))
+}
+
+fn aggregate_public_keys_unchecked<V: Variant>(
+	public_keys: &[Vec<u8>],
+) -> Result<V::PublicKey, Error> {
+	if public_keys.is_empty() {
+		return Err(Error::EmptyInput)
+	}
+	let mut aggregate = V::PublicKey::zero().into_group();
+	for public_key in public_keys {
+		aggregate += decode_public_key::<V>(public_key)?;
+	}
+	Ok(aggregate.into_affine())
+}
+
+/// Aggregates compressed public keys into a single compressed public key.
+pub fn aggregate_public_keys<V: Variant>(public_keys: &[Vec<u8>]) -> Result<Vec<u8>, Error> {
+	Ok(encode_compressed(aggregate_public_keys_unchecked::<V>(public_keys)?))
+}
+
+/// Aggregates compressed signatures into a single compressed signature.
+pub fn aggregate_signatures<V: Variant>(signatures: &[Vec<u8>]) -> Result<Vec<u8>, Error> {
+	if signatures.is_empty() {
+		return Err(Error::EmptyInput)
+	}
+	let mut aggregate = V::Signature::zero().into_group();
+	for signature in signatures {
+		aggregate += decode_signature::<V>(signature)?;
+	}
+	Ok(encode_compressed(aggregate.into_affine()))
+}
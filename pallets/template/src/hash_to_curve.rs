@@ -0,0 +1,160 @@
+//! Hashing to G1 and G2 of BLS12-381 as specified in RFC 9380
+//! (<https://www.rfc-editor.org/rfc/rfc9380.html>), suites `BLS12381G1_XMD:SHA-256_SSWU_RO_` and
+//! `BLS12381G2_XMD:SHA-256_SSWU_RO_`.
+//!
+//! `expand_message_xmd` and `hash_to_field` are implemented here, the simplified SWU map to the
+//! isogenous curve and the isogeny use the arkworks curve parameters, and cofactor clearing is a
+//! scalar multiplication by `h_eff` on the host accelerated projective types.
+
+use crate::{
+	bls12_381::{
+		G1AffineOptimized, G1ProjectiveOptimized, G2AffineOptimized, G2ProjectiveOptimized,
+	},
+	utils::serialize_argument,
+};
+use ark_bls12_381::{Fq, Fq2};
+use ark_ec::{
+	hashing::{curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurve},
+	short_weierstrass::{Affine, SWCurveConfig},
+	CurveGroup, Group,
+};
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, Compress, Validate};
+use ark_std::vec::Vec;
+use sha2::{Digest, Sha256};
+
+/// Output size of SHA-256 in bytes.
+const B_IN_BYTES: usize = 32;
+/// Input block size of SHA-256 in bytes.
+const S_IN_BYTES: usize = 64;
+/// `L = ceil((ceil(log2(p)) + k) / 8)` for BLS12-381 and `k = 128`.
+const L: usize = 64;
+
+/// `h_eff = 1 - x` for G1.
+const H_EFF_G1: &[u64] = &[0xd201000000010001];
+/// `h_eff = 3 * (x^2 - 1) * h2` for G2, in little endian limbs.
+const H_EFF_G2: &[u64] = &[
+	0xe8020005aaa95551,
+	0x59894c0adebbf6b4,
+	0xe954cbc06689f6a3,
+	0x2ec0ec69d7477c1a,
+	0x6d82bf015d1212b0,
+	0x329c2f178731db95,
+	0x9986ff031508ffe1,
+	0x88e2a8e9145ad768,
+	0x584c6a0ea91b3528,
+	0x0bc69f08f2ee75b3,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+	/// The requested output of `expand_message_xmd` is longer than `255 * 32` bytes.
+	OutputTooLong,
+	/// The simplified SWU map or the isogeny failed.
+	MapToCurve,
+}
+
+/// `expand_message_xmd` with SHA-256 (RFC 9380, section 5.3.1).
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Result<Vec<u8>, Error> {
+	let ell = (len_in_bytes + B_IN_BYTES - 1) / B_IN_BYTES;
+	if ell > 255 || len_in_bytes > u16::MAX as usize {
+		return Err(Error::OutputTooLong)
+	}
+
+	// Tags longer than 255 bytes are hashed first (RFC 9380, section 5.3.3).
+	let oversize_dst;
+	let dst = if dst.len() > 255 {
+		oversize_dst =
+			Sha256::new().chain_update(b"H2C-OVERSIZE-DST-").chain_update(dst).finalize();
+		&oversize_dst[..]
+	} else {
+		dst
+	};
+	let dst_prime = [dst, &[dst.len() as u8]].concat();
+
+	let b_0 = Sha256::new()
+		.chain_update([0u8; S_IN_BYTES])
+		.chain_update(msg)
+		.chain_update((len_in_bytes as u16).to_be_bytes())
+		.chain_update([0u8])
+		.chain_update(&dst_prime)
+		.finalize();
+
+	let mut b_i = Sha256::new()
+		.chain_update(b_0)
+		.chain_update([1u8])
+		.chain_update(&dst_prime)
+		.finalize();
+	let mut uniform_bytes = Vec::with_capacity(ell * B_IN_BYTES);
+	uniform_bytes.extend_from_slice(&b_i);
+	for i in 2..=ell {
+		let mut xored = [0u8; B_IN_BYTES];
+		for (x, (a, b)) in xored.iter_mut().zip(b_0.iter().zip(b_i.iter())) {
+			*x = a ^ b;
+		}
+		b_i = Sha256::new()
+			.chain_update(xored)
+			.chain_update([i as u8])
+			.chain_update(&dst_prime)
+			.finalize();
+		uniform_bytes.extend_from_slice(&b_i);
+	}
+	uniform_bytes.truncate(len_in_bytes);
+	Ok(uniform_bytes)
+}
+
+/// `hash_to_field` into `Fq` (RFC 9380, section 5.2).
+pub fn hash_to_fq(msg: &[u8], dst: &[u8], count: usize) -> Result<Vec<Fq>, Error> {
+	let uniform_bytes = expand_message_xmd(msg, dst, count * L)?;
+	Ok(uniform_bytes.chunks(L).map(Fq::from_be_bytes_mod_order).collect())
+}
+
+/// `hash_to_field` into `Fq2` (RFC 9380, section 5.2).
+pub fn hash_to_fq2(msg: &[u8], dst: &[u8], count: usize) -> Result<Vec<Fq2>, Error> {
+	let uniform_bytes = expand_message_xmd(msg, dst, count * 2 * L)?;
+	Ok(uniform_bytes
+		.chunks(2 * L)
+		.map(|e| {
+			Fq2::new(Fq::from_be_bytes_mod_order(&e[..L]), Fq::from_be_bytes_mod_order(&e[L..]))
+		})
+		.collect())
+}
+
+/// Simplified SWU map to the 11-isogenous curve followed by the isogeny to G1, without cofactor
+/// clearing.
+pub fn map_to_curve_g1(u: Fq) -> Result<G1ProjectiveOptimized, Error> {
+	let map = WBMap::<ark_bls12_381::g1::Config>::new().map_err(|_| Error::MapToCurve)?;
+	to_optimized(map.map_to_curve(u).map_err(|_| Error::MapToCurve)?)
+}
+
+/// Simplified SWU map to the 3-isogenous curve followed by the isogeny to G2, without cofactor
+/// clearing.
+pub fn map_to_curve_g2(u: Fq2) -> Result<G2ProjectiveOptimized, Error> {
+	let map = WBMap::<ark_bls12_381::g2::Config>::new().map_err(|_| Error::MapToCurve)?;
+	to_optimized(map.map_to_curve(u).map_err(|_| Error::MapToCurve)?)
+}
+
+/// `hash_to_curve` for G1 (RFC 9380, section 3).
+pub fn hash_to_g1(msg: &[u8], dst: &[u8]) -> Result<G1AffineOptimized, Error> {
+	let u = hash_to_fq(msg, dst, 2)?;
+	let r = map_to_curve_g1(u[0])? + map_to_curve_g1(u[1])?;
+	Ok(r.mul_bigint(H_EFF_G1).into_affine())
+}
+
+/// `hash_to_curve` for G2 (RFC 9380, section 3).
+pub fn hash_to_g2(msg: &[u8], dst: &[u8]) -> Result<G2AffineOptimized, Error> {
+	let u = hash_to_fq2(msg, dst, 2)?;
+	let r = map_to_curve_g2(u[0])? + map_to_curve_g2(u[1])?;
+	Ok(r.mul_bigint(H_EFF_G2).into_affine())
+}
+
+/// Re-encodes a point of the arkworks curve as the host accelerated point with the same
+/// coordinates.
+fn to_optimized<P, Q>(point: Affine<P>) -> Result<Q, Error>
+where
+	P: SWCurveConfig,
+	Q: CanonicalDeserialize,
+{
+	Q::deserialize_with_mode(&serialize_argument(point)[..], Compress::No, Validate::No)
+		.map_err(|_| Error::MapToCurve)
+}
@@ -0,0 +1,21 @@
+//! An experimental host function verifying a whole Groth16 proof over BLS12-381 in one call.
+//!
+//! The host accelerated curve of `bls12_381.rs` still decodes the key and the proof and computes
+//! the public input MSM in wasm, and serializes the arguments of each of its host calls. Here the
+//! runtime passes the encoded key, inputs and proof as they are and the node does the rest with
+//! arkworks natively. Nodes running a runtime that calls it need `groth16::HostFunctions` in the
+//! host functions of their executor.
+
+use sp_runtime_interface::runtime_interface;
+
+#[runtime_interface]
+pub trait Groth16 {
+	/// Verifies `proof` of the public `inputs`, uncompressed scalars one after the other, against
+	/// `vk`, all uncompressed. Returns `false` for arguments that do not decode as well.
+	fn groth16_verify_bls12_381(vk: &[u8], inputs: &[u8], proof: &[u8]) -> bool {
+		matches!(
+			crate::bls12_381::verify_groth16_inputs::<ark_bls12_381::Bls12_381>(vk, inputs, proof),
+			Ok(true)
+		)
+	}
+}
@@ -0,0 +1,80 @@
+//! Counters of the host calls of `HostBls12_381` and of the bytes marshalled across the wasm
+//! boundary by them, compiled in with the `host-call-stats` feature.
+//!
+//! The counters accumulate until [`take`] reads and resets them, so taking them before and
+//! after dispatching an extrinsic gives the host calls of that extrinsic. They only exist in
+//! native code, for the benchmarks and tests of the pallet; a runtime has no way to report
+//! them, so the feature implies `std` and is not one of the runtime.
+
+use frame_support::RuntimeDebug;
+
+/// The host functions of the BLS12-381 curve.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum HostCall {
+	MultiMillerLoop,
+	FinalExponentiation,
+	MsmG1,
+	MsmG2,
+	MulProjectiveG1,
+	MulAffineG1,
+	MulProjectiveG2,
+	MulAffineG2,
+}
+
+const HOST_CALLS: usize = 8;
+
+/// The calls of one host function and the bytes of their arguments and results.
+#[derive(Clone, Copy, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct CallStats {
+	pub calls: u32,
+	pub bytes_in: u64,
+	pub bytes_out: u64,
+}
+
+/// The counters of every host function, indexed by [`HostCall`].
+#[derive(Clone, Copy, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct HostCallStats([CallStats; HOST_CALLS]);
+
+impl HostCallStats {
+	pub fn get(&self, call: HostCall) -> CallStats {
+		self.0[call as usize]
+	}
+
+	/// The counters summed over all host functions.
+	pub fn total(&self) -> CallStats {
+		self.0.iter().fold(CallStats::default(), |total, stats| CallStats {
+			calls: total.calls + stats.calls,
+			bytes_in: total.bytes_in + stats.bytes_in,
+			bytes_out: total.bytes_out + stats.bytes_out,
+		})
+	}
+}
+
+std::thread_local! {
+	// Per thread, so that parallel tests do not count each other's calls.
+	static STATS: core::cell::RefCell<HostCallStats> = Default::default();
+}
+
+fn with_stats<R>(f: impl FnOnce(&mut HostCallStats) -> R) -> R {
+	STATS.with(|stats| f(&mut stats.borrow_mut()))
+}
+
+/// Counts a call of `call` with arguments of `bytes_in` and a result of `bytes_out` bytes.
+pub fn record(call: HostCall, bytes_in: usize, bytes_out: usize) {
+	with_stats(|stats| {
+		let stats = &mut stats.0[call as usize];
+		stats.calls = stats.calls.saturating_add(1);
+		stats.bytes_in = stats.bytes_in.saturating_add(bytes_in as u64);
+		stats.bytes_out = stats.bytes_out.saturating_add(bytes_out as u64);
+	})
+}
+
+/// Returns the counters since the last call and resets them.
+pub fn take() -> HostCallStats {
+	with_stats(core::mem::take)
+}
+
+/// The total length of the byte vectors of a host call argument.
+pub(crate) fn len(bytes: &[Vec<u8>]) -> usize {
+	bytes.iter().map(Vec::len).sum()
+}
@@ -0,0 +1,187 @@
+//! Verification of KZG polynomial commitment openings over BLS12-381, compatible with the
+//! `verify_kzg_proof` of EIP-4844 (<https://eips.ethereum.org/EIPS/eip-4844>).
+//!
+//! Commitments and proofs are compressed G1 points (48 bytes), evaluation points and values are
+//! big endian scalars (32 bytes). Only `[tau]G2` of the trusted setup is needed for verifying.
+
+use crate::bls12_381::{
+	Bls12_381Optimized, BlsFrOptimized, G1AffineOptimized, G1ProjectiveOptimized, G2AffineOptimized,
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{BigInteger, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use ark_std::{vec, vec::Vec};
+use sha2::{Digest, Sha256};
+
+/// `[tau]G2` of the Ethereum KZG ceremony, i.e. the second G2 point of `trusted_setup.txt`.
+pub const ETHEREUM_SETUP_G2: [u8; 96] = [
+	0xb5, 0xbf, 0xd7, 0xdd, 0x8c, 0xde, 0xb1, 0x28, 0x84, 0x3b, 0xc2, 0x87, 0x23, 0x0a, 0xf3, 0x89,
+	0x26, 0x18, 0x70, 0x75, 0xcb, 0xfb, 0xef, 0xa8, 0x10, 0x09, 0xa2, 0xce, 0x61, 0x5a, 0xc5, 0x3d,
+	0x29, 0x14, 0xe5, 0x87, 0x0c, 0xb4, 0x52, 0xd2, 0xaf, 0xaa, 0xab, 0x24, 0xf3, 0x49, 0x9f, 0x72,
+	0x18, 0x5c, 0xbf, 0xee, 0x53, 0x49, 0x27, 0x14, 0x73, 0x44, 0x29, 0xb7, 0xb3, 0x86, 0x08, 0xe2,
+	0x39, 0x26, 0xc9, 0x11, 0xcc, 0xec, 0xea, 0xc9, 0xa3, 0x68, 0x51, 0x47, 0x7b, 0xa4, 0xc6, 0x0b,
+	0x08, 0x70, 0x41, 0xde, 0x62, 0x10, 0x00, 0xed, 0xc9, 0x8e, 0xda, 0xda, 0x20, 0xc1, 0xde, 0xf2,
+];
+
+/// The most openings the pallet verifies in one batch.
+pub const MAX_BATCH_SIZE: u32 = 64;
+
+/// Domain separator of the Fiat-Shamir challenge used for batch verification.
+const BATCH_DOMAIN: &[u8] = b"RCKZGBATCH___V1_";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+	/// A commitment or proof is not a valid compressed G1 point.
+	InvalidPoint,
+	/// An evaluation point or value is not a canonical scalar.
+	InvalidScalar,
+	/// The trusted setup point is not a valid compressed G2 point.
+	InvalidSetup,
+	/// The batch inputs have different lengths.
+	LengthMismatch,
+	/// The batch has no openings.
+	EmptyBatch,
+}
+
+/// A single opening claim `p(z) = y` for the polynomial committed to in `commitment`.
+pub struct Opening {
+	pub commitment: G1AffineOptimized,
+	pub z: BlsFrOptimized,
+	pub y: BlsFrOptimized,
+	pub proof: G1AffineOptimized,
+}
+
+impl Opening {
+	pub fn decode(commitment: &[u8], z: &[u8], y: &[u8], proof: &[u8]) -> Result<Self, Error> {
+		Ok(Self {
+			commitment: decode_g1(commitment)?,
+			z: decode_scalar(z)?,
+			y: decode_scalar(y)?,
+			proof: decode_g1(proof)?,
+		})
+	}
+}
+
+/// Decodes a non-empty batch of openings given as parallel lists.
+pub fn decode_openings(
+	commitments: &[Vec<u8>],
+	zs: &[Vec<u8>],
+	ys: &[Vec<u8>],
+	proofs: &[Vec<u8>],
+) -> Result<Vec<Opening>, Error> {
+	let n = commitments.len();
+	if zs.len() != n || ys.len() != n || proofs.len() != n {
+		return Err(Error::LengthMismatch)
+	}
+	if n == 0 {
+		return Err(Error::EmptyBatch)
+	}
+	(0..n)
+		.map(|i| Opening::decode(&commitments[i], &zs[i], &ys[i], &proofs[i]))
+		.collect()
+}
+
+pub fn decode_setup(tau_g2: &[u8]) -> Result<G2AffineOptimized, Error> {
+	if tau_g2.len() != 96 {
+		return Err(Error::InvalidSetup)
+	}
+	G2AffineOptimized::deserialize_with_mode(tau_g2, Compress::Yes, Validate::Yes)
+		.map_err(|_| Error::InvalidSetup)
+}
+
+/// Decodes a compressed G1 point, rejecting encodings of the point at infinity with any other bit
+/// set, which arkworks ignores but the ZCash encoding used by EIP-4844 requires to be zero.
+fn decode_g1(bytes: &[u8]) -> Result<G1AffineOptimized, Error> {
+	if bytes.len() != 48 {
+		return Err(Error::InvalidPoint)
+	}
+	if bytes[0] & 0x40 != 0 && (bytes[0] != 0xc0 || bytes[1..].iter().any(|byte| *byte != 0)) {
+		return Err(Error::InvalidPoint)
+	}
+	G1AffineOptimized::deserialize_with_mode(bytes, Compress::Yes, Validate::Yes)
+		.map_err(|_| Error::InvalidPoint)
+}
+
+/// Decodes a 32 byte big endian scalar, rejecting non canonical encodings.
+fn decode_scalar(bytes: &[u8]) -> Result<BlsFrOptimized, Error> {
+	if bytes.len() != 32 {
+		return Err(Error::InvalidScalar)
+	}
+	let mut le = bytes.to_vec();
+	le.reverse();
+	BlsFrOptimized::deserialize_with_mode(&le[..], Compress::No, Validate::Yes)
+		.map_err(|_| Error::InvalidScalar)
+}
+
+/// Checks `e(C - [y]G1, -G2) * e(proof, [tau]G2 - [z]G2) == 1`.
+pub fn verify_kzg_proof(tau_g2: G2AffineOptimized, opening: &Opening) -> bool {
+	let g1 = G1AffineOptimized::generator();
+	let g2 = G2AffineOptimized::generator();
+	let p_minus_y = opening.commitment.into_group() - g1 * opening.y;
+	let x_minus_z = tau_g2.into_group() - g2 * opening.z;
+	Bls12_381Optimized::multi_pairing(
+		[p_minus_y.into_affine(), opening.proof],
+		[-g2, x_minus_z.into_affine()],
+	)
+	.is_zero()
+}
+
+/// Verifies all `openings` with one multi-Miller loop over two pairs, by checking a random
+/// linear combination `sum r^i (C_i - [y_i]G1 + [z_i]proof_i)` against `sum r^i proof_i`.
+pub fn verify_kzg_proof_batch(tau_g2: G2AffineOptimized, openings: &[Opening]) -> bool {
+	if openings.is_empty() {
+		return true
+	}
+	let r_powers = challenge_powers(openings);
+	let g1 = G1AffineOptimized::generator();
+
+	let mut bases = Vec::with_capacity(openings.len() * 2 + 1);
+	let mut scalars = Vec::with_capacity(openings.len() * 2 + 1);
+	let mut y_sum = BlsFrOptimized::zero();
+	for (opening, r) in openings.iter().zip(&r_powers) {
+		bases.push(opening.commitment);
+		scalars.push(*r);
+		bases.push(opening.proof);
+		scalars.push(*r * opening.z);
+		y_sum += *r * opening.y;
+	}
+	bases.push(g1);
+	scalars.push(-y_sum);
+
+	let lhs = G1ProjectiveOptimized::msm(&bases, &scalars).expect("equal lengths; qed");
+	let proofs: Vec<_> = openings.iter().map(|opening| opening.proof).collect();
+	let proof_sum = G1ProjectiveOptimized::msm(&proofs, &r_powers).expect("equal lengths; qed");
+
+	Bls12_381Optimized::multi_pairing(
+		[lhs.into_affine(), proof_sum.into_affine()],
+		[-G2AffineOptimized::generator(), tau_g2],
+	)
+	.is_zero()
+}
+
+/// Derives the powers `1, r, r^2, ...` of a challenge binding all openings.
+fn challenge_powers(openings: &[Opening]) -> Vec<BlsFrOptimized> {
+	let mut hasher = Sha256::new().chain_update(BATCH_DOMAIN);
+	hasher.update((openings.len() as u64).to_be_bytes());
+	for opening in openings {
+		let mut buffer = vec![0u8; opening.commitment.compressed_size()];
+		opening
+			.commitment
+			.serialize_compressed(&mut buffer[..])
+			.expect("sized buffer; qed");
+		hasher.update(&buffer);
+		opening.proof.serialize_compressed(&mut buffer[..]).expect("sized buffer; qed");
+		hasher.update(&buffer);
+		hasher.update(opening.z.into_bigint().to_bytes_be());
+		hasher.update(opening.y.into_bigint().to_bytes_be());
+	}
+	let r = BlsFrOptimized::from_be_bytes_mod_order(&hasher.finalize());
+
+	let mut powers = Vec::with_capacity(openings.len());
+	let mut power = BlsFrOptimized::from(1u64);
+	for _ in openings {
+		powers.push(power);
+		power *= r;
+	}
+	powers
+}
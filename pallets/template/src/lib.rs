@@ -6,6 +6,10 @@
 /// <https://docs.substrate.io/reference/frame-pallets/>
 pub use pallet::*;
 
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{traits::ConstU32, BoundedVec, RuntimeDebug};
+use scale_info::TypeInfo;
+
 #[cfg(test)]
 mod mock;
 
@@ -16,33 +20,160 @@ mod tests;
 mod benchmarking;
 
 pub mod bls12_381;
+pub mod bls_signatures;
+pub mod hash_to_curve;
+#[cfg(feature = "experimental")]
+pub mod host;
+#[cfg(feature = "host-call-stats")]
+pub mod host_stats;
+pub mod kzg;
+pub mod marlin;
+pub mod migrations;
+pub mod offchain;
+pub mod plonk;
+pub mod snarkpack;
 pub mod utils;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+/// Lets other pallets react to Groth16 proofs verified against registered keys, for example to
+/// mint, vote or unlock funds for the prover.
+pub trait OnProofVerified<AccountId> {
+	/// Called after `who` proved the statement `public_inputs` for the registered key `vk_id`.
+	fn on_proof_verified(who: &AccountId, vk_id: u32, public_inputs: &[u8]);
+}
+
+impl<AccountId> OnProofVerified<AccountId> for () {
+	fn on_proof_verified(_who: &AccountId, _vk_id: u32, _public_inputs: &[u8]) {}
+}
+
+/// The review status of a registered Groth16 verifying key, set by `Config::AdminOrigin`.
+#[derive(
+	Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub enum VerifyingKeyStatus {
+	/// Registered, but not reviewed yet.
+	#[default]
+	Pending,
+	/// Reviewed and approved, for example to gate funds with its proofs.
+	Approved,
+	/// Proofs against the key are refused until it is approved again.
+	Frozen,
+	/// Superseded by another key. Proofs against it still verify.
+	Deprecated,
+}
+
+/// The curves Groth16 verifying keys can be registered for.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum Groth16Curve {
+	Bls12_381,
+}
+
+/// Upper bound of the names of circuits.
+pub const MAX_CIRCUIT_NAME_LEN: u32 = 64;
+
+/// The name of a circuit with versioned Groth16 keys.
+pub type CircuitName = BoundedVec<u8, ConstU32<MAX_CIRCUIT_NAME_LEN>>;
+
+/// A version of a named circuit, published by `Config::AdminOrigin`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct CircuitVersion<BlockNumber> {
+	/// The registered Groth16 key of the version.
+	pub vk_id: u32,
+	/// Once a newer version is published, the block from which proofs of this one are refused.
+	pub expires_at: Option<BlockNumber>,
+}
 
 #[frame_support::pallet]
 pub mod pallet {
+	#[cfg(feature = "experimental")]
+	use crate::host;
 	use crate::{
 		bls12_381,
-		bls12_381::{Bls12_381Optimized, BlsFrOptimized},
-		utils,
+		bls12_381::{Bls12_381Optimized, Groth16Backend},
+		bls_signatures::{self, MinPk, MinSig, Variant},
+		kzg, marlin,
+		offchain::{self, VerificationResultPayload},
+		plonk, snarkpack, CircuitName, CircuitVersion, OnProofVerified, VerifyingKeyStatus,
+		WeightInfo,
+	};
+	use ark_std::vec::Vec;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ReservableCurrency},
+	};
+	use frame_system::{
+		offchain::{AppCrypto, CreateSignedTransaction, SignedPayload},
+		pallet_prelude::*,
 	};
-	use ark_bls12_381::Fr as BlsFr;
-	use ark_ff::Fp;
-	use ark_groth16::Groth16;
-	use ark_serialize::{CanonicalDeserialize, Compress, Validate};
-	use ark_snark::SNARK;
-	use ark_std::{io::Cursor, vec, vec::Vec};
-	use frame_support::pallet_prelude::*;
-	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Hash, IdentifyAccount};
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// The version of the pallet's storage, see `crate::migrations`.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: CreateSignedTransaction<Call<Self>> + frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The number of blocks stored prepared inputs stay available for verification.
+		#[pallet::constant]
+		type PreparedInputsLifetime: Get<Self::BlockNumber>;
+
+		/// The keys the offchain worker signs verification results with.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+		/// Whether the offchain worker submits results in signed transactions rather than in
+		/// unsigned transactions with a signed payload.
+		#[pallet::constant]
+		type SignedResults: Get<bool>;
+
+		/// The most queued requests the offchain worker verifies per block.
+		#[pallet::constant]
+		type MaxOffchainVerifications: Get<u32>;
+
+		/// The priority of unsigned transactions with verification results.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		/// Handler of the Groth16 proofs verified against registered keys.
+		type OnProofVerified: OnProofVerified<Self::AccountId>;
+
+		/// The origin that approves, freezes and deprecates registered Groth16 verifying keys
+		/// and publishes versions of circuits.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The number of blocks proofs of a circuit version are still accepted for after a newer
+		/// version was published.
+		#[pallet::constant]
+		type CircuitGracePeriod: Get<Self::BlockNumber>;
+
+		/// The currency verifying key deposits are reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved for each registered PLONK or Marlin verifying key until its
+		/// owner removes it.
+		#[pallet::constant]
+		type VerifyingKeyDeposit: Get<BalanceOf<Self>>;
+
+		/// The deposit reserved for each queued offchain verification request until its result
+		/// is submitted.
+		#[pallet::constant]
+		type VerificationRequestDeposit: Get<BalanceOf<Self>>;
+
+		/// Weight information for the extrinsics of this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	// The pallet's runtime storage items.
@@ -53,205 +184,1682 @@ pub mod pallet {
 	// https://docs.substrate.io/main-docs/build/runtime-storage/#declaring-storage-items
 	pub type Something<T> = StorageValue<_, u32>;
 
+	#[pallet::type_value]
+	pub fn DefaultKzgTrustedSetup() -> [u8; 96] {
+		kzg::ETHEREUM_SETUP_G2
+	}
+
+	/// The compressed `[tau]G2` point of the KZG trusted setup, the Ethereum ceremony by default.
+	#[pallet::storage]
+	pub type KzgTrustedSetup<T> = StorageValue<_, [u8; 96], ValueQuery, DefaultKzgTrustedSetup>;
+
+	/// Registered PLONK verifying keys in their uncompressed encoding.
+	#[pallet::storage]
+	pub type PlonkVerifyingKeys<T> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		(plonk::Curve, BoundedVec<u8, ConstU32<{ plonk::MAX_VERIFYING_KEY_LEN }>>),
+	>;
+
+	#[pallet::storage]
+	pub type NextPlonkVerifyingKeyId<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// The owners of the registered PLONK verifying keys and the deposits reserved for them.
+	#[pallet::storage]
+	pub type PlonkVerifyingKeyDeposits<T: Config> =
+		StorageMap<_, Twox64Concat, u32, (T::AccountId, BalanceOf<T>)>;
+
+	/// The uncompressed `[x]G2` point of the universal setup PLONK verifying keys of each curve
+	/// are registered for.
+	#[pallet::storage]
+	pub type PlonkSrs<T> =
+		StorageMap<_, Twox64Concat, plonk::Curve, BoundedVec<u8, ConstU32<{ plonk::MAX_SRS_LEN }>>>;
+
+	/// Registered Marlin index verifying keys over BLS12-381 in their uncompressed encoding.
+	#[pallet::storage]
+	pub type MarlinVerifyingKeys<T> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		BoundedVec<u8, ConstU32<{ marlin::MAX_VERIFYING_KEY_LEN }>>,
+	>;
+
+	#[pallet::storage]
+	pub type NextMarlinVerifyingKeyId<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// The owners of the registered Marlin index verifying keys and the deposits reserved for
+	/// them.
+	#[pallet::storage]
+	pub type MarlinVerifyingKeyDeposits<T: Config> =
+		StorageMap<_, Twox64Concat, u32, (T::AccountId, BalanceOf<T>)>;
+
+	/// The uncompressed `[tau]G2` point of the universal parameters Marlin index verifying keys
+	/// are registered for.
+	#[pallet::storage]
+	pub type MarlinSrs<T> = StorageValue<_, BoundedVec<u8, ConstU32<{ marlin::MAX_SRS_LEN }>>>;
+
+	/// Registered Groth16 verifying keys over BLS12-381 in their uncompressed encoding.
+	#[pallet::storage]
+	pub type Groth16VerifyingKeys<T> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		BoundedVec<u8, ConstU32<{ bls12_381::GROTH16_MAX_VERIFYING_KEY_LEN }>>,
+	>;
+
+	/// The hashes of the registered Groth16 verifying keys, by which events identify them.
+	#[pallet::storage]
+	pub type Groth16VerifyingKeyHashes<T: Config> = StorageMap<_, Twox64Concat, u32, T::Hash>;
+
+	/// The id of the last registered Groth16 verifying key with a hash, through which
+	/// verifications against unregistered keys find its prepared form.
+	#[pallet::storage]
+	pub type Groth16VerifyingKeyIds<T: Config> = StorageMap<_, Identity, T::Hash, u32>;
+
+	/// The registered Groth16 verifying keys prepared for the host accelerated curve, in their
+	/// uncompressed encoding.
+	#[pallet::storage]
+	pub type Groth16PreparedVerifyingKeys<T> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		BoundedVec<u8, ConstU32<{ bls12_381::GROTH16_MAX_PREPARED_VERIFYING_KEY_LEN }>>,
+	>;
+
+	#[pallet::storage]
+	pub type NextGroth16VerifyingKeyId<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// The review status of the registered Groth16 verifying keys.
+	#[pallet::storage]
+	pub type Groth16VerifyingKeyStatuses<T> =
+		StorageMap<_, Twox64Concat, u32, VerifyingKeyStatus, ValueQuery>;
+
+	/// The versions of the named circuits by name and version, starting at 1.
+	#[pallet::storage]
+	pub type CircuitVersions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CircuitName,
+		Twox64Concat,
+		u32,
+		CircuitVersion<T::BlockNumber>,
+	>;
+
+	/// The latest version of each named circuit.
+	#[pallet::storage]
+	pub type LatestCircuitVersions<T> = StorageMap<_, Blake2_128Concat, CircuitName, u32>;
+
+	/// Groth16 public inputs prepared for a registered key, with the key id, the inputs as
+	/// submitted and the block from which on they are expired.
+	#[pallet::storage]
+	pub type PreparedInputs<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		(
+			u32,
+			[u8; bls12_381::GROTH16_PREPARED_INPUTS_LEN],
+			BoundedVec<u8, ConstU32<{ bls12_381::GROTH16_MAX_PUBLIC_INPUTS_LEN }>>,
+			T::BlockNumber,
+		),
+	>;
+
+	#[pallet::storage]
+	pub type NextPreparedInputsHandle<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// The oldest handle that may still hold prepared inputs. As the lifetime is constant,
+	/// handles expire in the order they were issued.
+	#[pallet::storage]
+	pub type OldestPreparedInputsHandle<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Groth16 proofs queued for the offchain worker, with the requester, the key id and the
+	/// uncompressed public inputs and proof.
+	#[pallet::storage]
+	pub type VerificationRequests<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		(
+			T::AccountId,
+			u32,
+			BoundedVec<u8, ConstU32<{ bls12_381::GROTH16_MAX_PUBLIC_INPUTS_LEN }>>,
+			BoundedVec<u8, ConstU32<{ bls12_381::GROTH16_PROOF_LEN }>>,
+		),
+	>;
+
+	/// The deposits reserved from the requesters of the queued verification requests.
+	#[pallet::storage]
+	pub type VerificationRequestDeposits<T: Config> =
+		StorageMap<_, Twox64Concat, u32, BalanceOf<T>>;
+
+	#[pallet::storage]
+	pub type NextVerificationRequestId<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// The accounts trusted to submit the results of offchain verification.
+	#[pallet::storage]
+	pub type ResultSubmitters<T: Config> = StorageValue<
+		_,
+		BoundedVec<T::AccountId, ConstU32<{ offchain::MAX_RESULT_SUBMITTERS }>>,
+		ValueQuery,
+	>;
+
+	/// The uncompressed verifier setup of SnarkPack aggregates over BLS12-381.
+	#[pallet::storage]
+	pub type AggregationSrs<T> =
+		StorageValue<_, BoundedVec<u8, ConstU32<{ snarkpack::VERIFIER_SRS_LEN }>>>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Circuits registered at genesis, as their name, the curve and the uncompressed
+		/// Groth16 verifying key. Each key is registered as approved and published as the next
+		/// version of its circuit.
+		pub circuits: Vec<(Vec<u8>, Groth16Curve, Vec<u8>)>,
+		/// The accounts trusted to submit the results of offchain verification.
+		pub result_submitters: Vec<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { circuits: Vec::new(), result_submitters: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let result_submitters: BoundedVec<_, _> =
+				self.result_submitters.clone().try_into().expect("too many result submitters");
+			ResultSubmitters::<T>::put(result_submitters);
+			for (name, Groth16Curve::Bls12_381, vk) in &self.circuits {
+				let name: CircuitName = name.clone().try_into().expect("circuit name too long");
+				let vk_id = Pallet::<T>::register_groth16_verifying_key(
+					vk.clone(),
+					VerifyingKeyStatus::Approved,
+				)
+				.unwrap_or_else(|error| {
+					panic!("invalid verifying key of circuit {:?}: {:?}", name, error)
+				});
+				Pallet::<T>::publish_circuit_version(name, vk_id);
+			}
+		}
+	}
+
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/main-docs/build/events-errors/
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// Successfull groth16 verification event
-		VerificationSuccess { who: T::AccountId },
+		/// `who` proved the statement with hash `inputs_hash` for the PLONK key `vk_id`. The
+		/// hashes are taken over the uncompressed encodings.
+		PlonkVerified { who: T::AccountId, vk_id: u32, inputs_hash: T::Hash, proof_hash: T::Hash },
+		/// `who` proved the statement with hash `inputs_hash` for the Marlin key `vk_id`. The
+		/// hashes are taken over the uncompressed encodings.
+		MarlinVerified { who: T::AccountId, vk_id: u32, inputs_hash: T::Hash, proof_hash: T::Hash },
+		/// `who` opened the commitments with hash `commitments_hash` at the points and values
+		/// with hash `inputs_hash`. The hashes are taken over the compressed encodings, and over
+		/// their SCALE encoded lists for batches.
+		KzgVerified {
+			who: T::AccountId,
+			commitments_hash: T::Hash,
+			inputs_hash: T::Hash,
+			proof_hash: T::Hash,
+		},
+		/// `who` submitted a valid BLS signature with hash `signature_hash` of the messages with
+		/// hash `messages_hash` by the public keys with hash `public_keys_hash`. The hashes of
+		/// several keys or messages are taken over their SCALE encoded lists.
+		BlsSignatureVerified {
+			who: T::AccountId,
+			public_keys_hash: T::Hash,
+			messages_hash: T::Hash,
+			signature_hash: T::Hash,
+		},
+		/// `who` proved the statement with hash `inputs_hash` for the Groth16 key with hash
+		/// `vk_hash`. The hashes are taken over the uncompressed encodings, and over their SCALE
+		/// encoded list for the inputs of aggregates.
+		Groth16Verified {
+			who: T::AccountId,
+			vk_hash: T::Hash,
+			inputs_hash: T::Hash,
+			proof_hash: T::Hash,
+			backend: Groth16Backend,
+		},
+		/// A Groth16 proof of `who` did not verify, recorded by `groth16_verify_audited` and for
+		/// offchain verification requests
+		VerificationFailed {
+			who: T::AccountId,
+			vk_hash: T::Hash,
+			inputs_hash: T::Hash,
+			proof_hash: T::Hash,
+			backend: Groth16Backend,
+		},
+		/// A PLONK verifying key was registered under `id`
+		PlonkVerifyingKeyRegistered { id: u32, who: T::AccountId },
+		/// The PLONK verifying key `id` was removed and its deposit returned
+		PlonkVerifyingKeyRemoved { id: u32 },
+		/// Root set the universal setup of PLONK verifying keys over `curve`
+		PlonkSrsSet { curve: plonk::Curve },
+		/// A Marlin index verifying key was registered under `id`
+		MarlinVerifyingKeyRegistered { id: u32, who: T::AccountId },
+		/// The Marlin index verifying key `id` was removed and its deposit returned
+		MarlinVerifyingKeyRemoved { id: u32 },
+		/// Root set the universal parameters of Marlin index verifying keys
+		MarlinSrsSet,
+		/// A Groth16 verifying key was registered under `id`
+		Groth16VerifyingKeyRegistered { id: u32, who: T::AccountId },
+		/// The admin approved the Groth16 verifying key `id`
+		Groth16VerifyingKeyApproved { id: u32 },
+		/// The admin froze the Groth16 verifying key `id`
+		Groth16VerifyingKeyFrozen { id: u32 },
+		/// The admin deprecated the Groth16 verifying key `id`
+		Groth16VerifyingKeyDeprecated { id: u32 },
+		/// The admin published the Groth16 verifying key `vk_id` as `version` of the circuit
+		/// `name`
+		CircuitVersionPublished { name: CircuitName, version: u32, vk_id: u32 },
+		/// Public inputs for the key `vk_id` were prepared and stored under `handle` until
+		/// `expires_at`
+		Groth16InputsPrepared {
+			handle: u32,
+			vk_id: u32,
+			expires_at: T::BlockNumber,
+			who: T::AccountId,
+		},
+		/// A Groth16 proof for the key `vk_id` was queued for offchain verification
+		VerificationRequested { request_id: u32, vk_id: u32, who: T::AccountId },
+		/// The offchain worker verified the queued request `request_id` of `who`
+		VerificationRequestProcessed { request_id: u32, who: T::AccountId, valid: bool },
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Verification of groth16 proof failed
 		VerificationFailed,
+		/// A BLS public key is malformed or not in the prime order subgroup
+		InvalidPublicKey,
+		/// A BLS signature is malformed or not in the prime order subgroup
+		InvalidSignature,
+		/// A message could not be hashed to the curve
+		HashToCurveFailed,
+		/// No public keys were supplied
+		NoPublicKeys,
+		/// The number of public keys and messages differ
+		LengthMismatch,
+		/// Verification of a BLS signature failed
+		SignatureVerificationFailed,
+		/// A KZG commitment or proof is not a valid compressed G1 point
+		InvalidKzgPoint,
+		/// A KZG evaluation point or value is not a canonical scalar
+		InvalidKzgScalar,
+		/// The KZG trusted setup is not a valid compressed G2 point
+		InvalidTrustedSetup,
+		/// Verification of a KZG proof failed
+		KzgVerificationFailed,
+		/// A PLONK verifying key is malformed or too large
+		InvalidPlonkVerifyingKey,
+		/// A PLONK proof is malformed
+		InvalidPlonkProof,
+		/// The public inputs are malformed or do not match the verifying key
+		InvalidPublicInputs,
+		/// No verifying key is registered under the given id
+		UnknownVerifyingKey,
+		/// The verifying key's curve is not supported by this backend
+		UnsupportedCurve,
+		/// Verification of a PLONK proof failed
+		PlonkVerificationFailed,
+		/// A Marlin index verifying key is malformed or too large
+		InvalidMarlinVerifyingKey,
+		/// A Marlin proof is malformed
+		InvalidMarlinProof,
+		/// Verification of a Marlin proof failed
+		MarlinVerificationFailed,
+		/// A Groth16 verifying key is malformed or too large
+		InvalidGroth16VerifyingKey,
+		/// The aggregation setup is malformed or inconsistent
+		InvalidAggregationSrs,
+		/// No aggregation setup has been set
+		MissingAggregationSrs,
+		/// An aggregate proof is malformed
+		InvalidAggregateProof,
+		/// Verification of an aggregate proof failed
+		AggregateVerificationFailed,
+		/// A Groth16 proof is malformed
+		InvalidGroth16Proof,
+		/// No prepared inputs are stored under the given handle
+		UnknownPreparedInputs,
+		/// The prepared inputs under the given handle have expired
+		PreparedInputsExpired,
+		/// No verification request is queued under the given id
+		UnknownVerificationRequest,
+		/// The account may not submit offchain verification results
+		NotResultSubmitter,
+		/// More result submitters than allowed
+		TooManyResultSubmitters,
+		/// The verifying key was frozen by the admin
+		VerifyingKeyFrozen,
+		/// A circuit name is longer than `MAX_CIRCUIT_NAME_LEN`
+		InvalidCircuitName,
+		/// The circuit has no version of the given number
+		UnknownCircuitVersion,
+		/// The grace period of the circuit version has passed
+		CircuitVersionExpired,
+		/// More public keys than an aggregate BLS signature may be verified against
+		TooManyPublicKeys,
+		/// A KZG batch has no openings
+		EmptyKzgBatch,
+		/// A KZG batch has more than `kzg::MAX_BATCH_SIZE` openings
+		KzgBatchTooLarge,
+		/// All ids of a kind of registration were handed out
+		IdsExhausted,
+		/// No universal setup was set for PLONK verifying keys over the curve
+		PlonkSrsNotSet,
+		/// The `[x]G2` point of a PLONK universal setup is malformed
+		InvalidPlonkSrs,
+		/// The PLONK verifying key was not derived from the universal setup set by root
+		PlonkSrsMismatch,
+		/// Only the account that registered the verifying key may remove it
+		NotVerifyingKeyOwner,
+		/// No universal parameters were set for Marlin index verifying keys
+		MarlinSrsNotSet,
+		/// The `[tau]G2` point of Marlin universal parameters is malformed
+		InvalidMarlinSrs,
+		/// The Marlin index verifying key was not indexed from the parameters set by root
+		MarlinSrsMismatch,
+		/// An aggregate has more than `snarkpack::MAX_AGGREGATED_PROOFS` proofs
+		TooManyAggregatedProofs,
+		/// The registered verifying key was not approved by the admin
+		VerifyingKeyNotApproved,
+	}
+
+	impl<T> From<bls_signatures::Error> for Error<T> {
+		fn from(error: bls_signatures::Error) -> Self {
+			match error {
+				bls_signatures::Error::InvalidPublicKey => Error::InvalidPublicKey,
+				bls_signatures::Error::InvalidSignature => Error::InvalidSignature,
+				bls_signatures::Error::HashToCurve => Error::HashToCurveFailed,
+				bls_signatures::Error::EmptyInput => Error::NoPublicKeys,
+				bls_signatures::Error::LengthMismatch => Error::LengthMismatch,
+			}
+		}
+	}
+
+	impl<T> From<kzg::Error> for Error<T> {
+		fn from(error: kzg::Error) -> Self {
+			match error {
+				kzg::Error::InvalidPoint => Error::InvalidKzgPoint,
+				kzg::Error::InvalidScalar => Error::InvalidKzgScalar,
+				kzg::Error::InvalidSetup => Error::InvalidTrustedSetup,
+				kzg::Error::LengthMismatch => Error::LengthMismatch,
+				kzg::Error::EmptyBatch => Error::EmptyKzgBatch,
+			}
+		}
+	}
+
+	impl<T> From<plonk::Error> for Error<T> {
+		fn from(error: plonk::Error) -> Self {
+			match error {
+				plonk::Error::InvalidVerifyingKey => Error::InvalidPlonkVerifyingKey,
+				plonk::Error::InvalidProof => Error::InvalidPlonkProof,
+				plonk::Error::PublicInputMismatch => Error::InvalidPublicInputs,
+				plonk::Error::InvalidSrs => Error::InvalidPlonkSrs,
+				plonk::Error::SrsMismatch => Error::PlonkSrsMismatch,
+			}
+		}
+	}
+
+	impl<T> From<marlin::Error> for Error<T> {
+		fn from(error: marlin::Error) -> Self {
+			match error {
+				marlin::Error::InvalidVerifyingKey => Error::InvalidMarlinVerifyingKey,
+				marlin::Error::PublicInputMismatch => Error::InvalidPublicInputs,
+				marlin::Error::InvalidProof |
+				marlin::Error::Synthesis |
+				marlin::Error::DegenerateChallenge => Error::InvalidMarlinProof,
+				marlin::Error::InvalidSrs => Error::InvalidMarlinSrs,
+				marlin::Error::SrsMismatch => Error::MarlinSrsMismatch,
+			}
+		}
+	}
+
+	impl<T> From<bls12_381::Groth16Error> for Error<T> {
+		fn from(error: bls12_381::Groth16Error) -> Self {
+			match error {
+				bls12_381::Groth16Error::InvalidVerifyingKey => Error::InvalidGroth16VerifyingKey,
+				bls12_381::Groth16Error::InvalidProof => Error::InvalidGroth16Proof,
+				bls12_381::Groth16Error::PublicInputMismatch => Error::InvalidPublicInputs,
+			}
+		}
+	}
+
+	impl<T> From<snarkpack::Error> for Error<T> {
+		fn from(error: snarkpack::Error) -> Self {
+			match error {
+				snarkpack::Error::InvalidVerifyingKey => Error::InvalidGroth16VerifyingKey,
+				snarkpack::Error::InvalidProof => Error::InvalidAggregateProof,
+				snarkpack::Error::PublicInputMismatch => Error::InvalidPublicInputs,
+				snarkpack::Error::InvalidSrs => Error::InvalidAggregationSrs,
+			}
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Removes expired prepared inputs, oldest first, as long as the weight allows.
+		fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let step = T::DbWeight::get().reads_writes(1, 1);
+			let mut used = T::DbWeight::get().reads_writes(2, 1);
+			if remaining_weight.any_lt(used) {
+				return Weight::zero()
+			}
+			let next = NextPreparedInputsHandle::<T>::get();
+			let mut oldest = OldestPreparedInputsHandle::<T>::get();
+			while oldest != next && remaining_weight.all_gte(used.saturating_add(step)) {
+				used = used.saturating_add(step);
+				match PreparedInputs::<T>::get(oldest) {
+					Some((.., expires_at)) if expires_at > now => break,
+					Some(_) => PreparedInputs::<T>::remove(oldest),
+					None => {},
+				}
+				oldest = oldest.wrapping_add(1);
+			}
+			OldestPreparedInputsHandle::<T>::put(oldest);
+			used
+		}
+
+		fn offchain_worker(now: T::BlockNumber) {
+			// Failures leave the requests queued for the next block.
+			let _ = Self::process_verification_requests(now);
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
+		/// Verifies a Groth16 proof of a single public input against an uncompressed verifying
+		/// key with the arkworks BLS12-381 curve.
 		#[pallet::call_index(0)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		#[pallet::weight(T::WeightInfo::groth16_verification())]
 		pub fn groth16_verification(
 			origin: OriginFor<T>,
 			vk: Vec<u8>,
 			c: Vec<u8>,
 			proof: Vec<u8>,
 		) -> DispatchResult {
-			let who = ensure_signed(origin).unwrap();
-
-			let cursor = Cursor::new(&vk);
-			let vk = <Groth16<Bls12_381Optimized> as SNARK<BlsFrOptimized>>::VerifyingKey::deserialize_with_mode(
-				cursor,
-				Compress::No,
-				Validate::No,
-			)
-			.unwrap();
-			let vk = utils::serialize_argument(vk);
-
-			let cursor = Cursor::new(&c);
-			let c: ark_ff::Fp<ark_ff::MontBackend<ark_bls12_381::FrConfig, 4>, 4> =
-				Fp::deserialize_with_mode(cursor, Compress::No, Validate::No).unwrap();
-			let c = utils::serialize_argument(c);
-
-			let cursor = Cursor::new(&proof);
-			let proof =
-				<Groth16<ark_bls12_381::Bls12_381> as SNARK<BlsFr>>::Proof::deserialize_with_mode(
-					cursor,
-					Compress::No,
-					Validate::No,
-				)
-				.unwrap();
-			let proof = utils::serialize_argument(proof);
-
-			let result = crate::bls12_381::do_verify_groth16(vk, c, proof);
-
-			if result.is_ok() {
-				Self::deposit_event(Event::VerificationSuccess { who });
-				Ok(())
-			} else {
-				Err(Error::<T>::VerificationFailed.into())
-			}
+			let who = ensure_signed(origin)?;
+			let (vk_hash, inputs_hash, proof_hash) =
+				(T::Hashing::hash(&vk), T::Hashing::hash(&c), T::Hashing::hash(&proof));
+			let valid = bls12_381::do_verify_groth16(vk, c, proof);
+			ensure!(valid.map_err(Error::<T>::from)?, Error::<T>::VerificationFailed);
+			Self::deposit_event(Event::Groth16Verified {
+				who,
+				vk_hash,
+				inputs_hash,
+				proof_hash,
+				backend: Groth16Backend::Bls12_381,
+			});
+			Ok(())
 		}
 
+		/// Like `groth16_verification`, with the host accelerated BLS12-381 curve. A key that is
+		/// registered is not prepared again, its stored prepared form is used and the saved
+		/// weight refunded. Registered keys must have been approved by the admin.
 		#[pallet::call_index(1)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		#[pallet::weight(
+			T::WeightInfo::groth16_verification_optimized()
+				.max(T::WeightInfo::groth16_verification_optimized_prepared())
+		)]
 		pub fn groth16_verification_optimized(
 			origin: OriginFor<T>,
 			vk: Vec<u8>,
 			c: Vec<u8>,
 			proof: Vec<u8>,
-		) -> DispatchResult {
-			let who = ensure_signed(origin).unwrap();
-
-			let cursor = Cursor::new(&vk);
-			let vk = <Groth16<Bls12_381Optimized> as SNARK<BlsFrOptimized>>::VerifyingKey::deserialize_with_mode(
-				cursor,
-				Compress::No,
-				Validate::No,
-			)
-			.unwrap();
-			let vk = utils::serialize_argument(vk);
-
-			let cursor = Cursor::new(&c);
-			let c: ark_ff::Fp<ark_ff::MontBackend<ark_bls12_381::FrConfig, 4>, 4> =
-				Fp::deserialize_with_mode(cursor, Compress::No, Validate::No).unwrap();
-			let c = utils::serialize_argument(c);
-
-			let cursor = Cursor::new(&proof);
-			let proof =
-				<Groth16<ark_bls12_381::Bls12_381> as SNARK<BlsFr>>::Proof::deserialize_with_mode(
-					cursor,
-					Compress::No,
-					Validate::No,
-				)
-				.unwrap();
-			let proof = utils::serialize_argument(proof);
-
-			let result = crate::bls12_381::do_verify_groth16_optimized(vk, c, proof);
-
-			if result.is_ok() {
-				Self::deposit_event(Event::VerificationSuccess { who });
-				Ok(())
-			} else {
-				Err(Error::<T>::VerificationFailed.into())
-			}
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (vk_hash, inputs_hash, proof_hash) =
+				(T::Hashing::hash(&vk), T::Hashing::hash(&c), T::Hashing::hash(&proof));
+			let pvk = match Groth16VerifyingKeyIds::<T>::get(vk_hash) {
+				Some(vk_id) => {
+					Self::ensure_approved(vk_id)?;
+					Groth16PreparedVerifyingKeys::<T>::get(vk_id)
+				},
+				None => None,
+			};
+			let (valid, actual_weight) = match pvk {
+				Some(pvk) => (
+					bls12_381::verify_prepared_groth16_input(&pvk, &c, &proof),
+					T::WeightInfo::groth16_verification_optimized_prepared(),
+				),
+				None => (
+					bls12_381::do_verify_groth16_optimized(vk, c, proof),
+					T::WeightInfo::groth16_verification_optimized(),
+				),
+			};
+			ensure!(valid.map_err(Error::<T>::from)?, Error::<T>::VerificationFailed);
+			Self::deposit_event(Event::Groth16Verified {
+				who,
+				vk_hash,
+				inputs_hash,
+				proof_hash,
+				backend: Groth16Backend::Bls12_381Optimized,
+			});
+			Ok(Some(actual_weight).into())
 		}
 
+		/// Prepares public inputs for a registered Groth16 key with the arkworks BLS12-381 curve
+		/// and stores them under a handle for `PreparedInputsLifetime` blocks.
 		#[pallet::call_index(2)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		#[pallet::weight(T::WeightInfo::groth16_prepare_inputs())]
 		pub fn groth16_prepare_inputs(
-			_origin: OriginFor<T>,
-			pvk: Vec<u8>,
-			c: Vec<u8>,
+			origin: OriginFor<T>,
+			vk_id: u32,
+			public_inputs: Vec<u8>,
 		) -> DispatchResult {
-			let _inputs =
-				crate::bls12_381::prepare_inputs_groth16::<ark_bls12_381::Bls12_381>(pvk, c)
-					.unwrap();
-			Ok(())
+			let who = ensure_signed(origin)?;
+			Self::store_prepared_inputs::<ark_bls12_381::Bls12_381>(who, vk_id, public_inputs)
 		}
 
+		/// Prepares public inputs for a registered Groth16 key with the host accelerated curve
+		/// and stores them under a handle for `PreparedInputsLifetime` blocks.
 		#[pallet::call_index(3)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		#[pallet::weight(T::WeightInfo::groth16_optimized_prepare_inputs())]
 		pub fn groth16_optimized_prepare_inputs(
-			_origin: OriginFor<T>,
-			pvk: Vec<u8>,
-			c: Vec<u8>,
+			origin: OriginFor<T>,
+			vk_id: u32,
+			public_inputs: Vec<u8>,
 		) -> DispatchResult {
-			let _inputs =
-				crate::bls12_381::prepare_inputs_groth16::<bls12_381::Bls12_381Optimized>(pvk, c)
-					.unwrap();
-			Ok(())
+			let who = ensure_signed(origin)?;
+			Self::store_prepared_inputs::<Bls12_381Optimized>(who, vk_id, public_inputs)
 		}
 
+		/// Verifies a Groth16 proof with the arkworks BLS12-381 curve against the public inputs
+		/// stored under `handle` and consumes them on success.
 		#[pallet::call_index(4)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		#[pallet::weight(T::WeightInfo::groth16_verify_with_prepared_inputs())]
 		pub fn groth16_verify_with_prepared_inputs(
-			_origin: OriginFor<T>,
-			inputs: Vec<u8>,
-			pvk: Vec<u8>,
+			origin: OriginFor<T>,
+			handle: u32,
 			proof: Vec<u8>,
 		) -> DispatchResult {
-			crate::bls12_381::verify_with_prepared_inputs_groth16::<ark_bls12_381::Bls12_381>(
-				inputs, pvk, proof,
+			let who = ensure_signed(origin)?;
+			Self::verify_prepared_inputs::<ark_bls12_381::Bls12_381>(
+				who,
+				handle,
+				proof,
+				Groth16Backend::Bls12_381,
 			)
-			.unwrap();
-			Ok(())
 		}
 
+		/// Verifies a Groth16 proof with the host accelerated curve against the public inputs
+		/// stored under `handle` and consumes them on success.
 		#[pallet::call_index(5)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		#[pallet::weight(T::WeightInfo::groth16_optimized_verify_with_prepared_inputs())]
 		pub fn groth16_optimized_verify_with_prepared_inputs(
-			_origin: OriginFor<T>,
-			inputs: Vec<u8>,
-			pvk: Vec<u8>,
+			origin: OriginFor<T>,
+			handle: u32,
 			proof: Vec<u8>,
 		) -> DispatchResult {
-			crate::bls12_381::verify_with_prepared_inputs_groth16::<bls12_381::Bls12_381Optimized>(
-				inputs, pvk, proof,
+			let who = ensure_signed(origin)?;
+			Self::verify_prepared_inputs::<Bls12_381Optimized>(
+				who,
+				handle,
+				proof,
+				Groth16Backend::Bls12_381Optimized,
 			)
-			.unwrap();
-			Ok(())
 		}
 
+		/// Validates an uncompressed Groth16 verifying key with the arkworks BLS12-381 curve and
+		/// registers it with its prepared form for the host accelerated curve, which every
+		/// verification of a registered key reads.
 		#[pallet::call_index(6)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
-		pub fn groth16_prepare_verifying_key(_origin: OriginFor<T>, vk: Vec<u8>) -> DispatchResult {
-			let cursor = Cursor::new(&vk);
-			let vk = <Groth16<ark_bls12_381::Bls12_381> as SNARK<BlsFrOptimized>>::VerifyingKey::deserialize_with_mode(
-				cursor,
-				Compress::No,
-				Validate::No,
-			)
-			.unwrap();
-			let vk = utils::serialize_argument(vk);
-
-			let _pvk =
-				crate::bls12_381::prepare_verifying_key_groth16::<ark_bls12_381::Bls12_381>(vk)
-					.unwrap();
+		#[pallet::weight(T::WeightInfo::groth16_prepare_verifying_key())]
+		pub fn groth16_prepare_verifying_key(origin: OriginFor<T>, vk: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			bls12_381::validate_verifying_key_groth16::<ark_bls12_381::Bls12_381>(&vk)
+				.map_err(Error::<T>::from)?;
+			let id = Self::register_groth16_verifying_key(vk, VerifyingKeyStatus::Pending)?;
+			Self::deposit_event(Event::Groth16VerifyingKeyRegistered { id, who });
 			Ok(())
 		}
 
+		/// Like `groth16_prepare_verifying_key`, validating the key with the host accelerated
+		/// BLS12-381 curve only.
 		#[pallet::call_index(7)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		#[pallet::weight(T::WeightInfo::groth16_optimized_prepare_verifying_key())]
 		pub fn groth16_optimized_prepare_verifying_key(
-			_origin: OriginFor<T>,
+			origin: OriginFor<T>,
 			vk: Vec<u8>,
 		) -> DispatchResult {
-			let cursor = Cursor::new(&vk);
-			let vk = <Groth16<bls12_381::Bls12_381Optimized> as SNARK<BlsFrOptimized>>::VerifyingKey::deserialize_with_mode(
-				cursor,
-				Compress::No,
-				Validate::No,
-			)
-			.unwrap();
-			let vk = utils::serialize_argument(vk);
+			let who = ensure_signed(origin)?;
+			let id = Self::register_groth16_verifying_key(vk, VerifyingKeyStatus::Pending)?;
+			Self::deposit_event(Event::Groth16VerifyingKeyRegistered { id, who });
+			Ok(())
+		}
+
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::bls_verify())]
+		pub fn bls_verify(
+			origin: OriginFor<T>,
+			public_key: Vec<u8>,
+			message: Vec<u8>,
+			signature: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let valid =
+				bls_signatures::verify::<MinPk>(&public_key, &message, &signature, MinPk::DST);
+			let (public_keys_hash, messages_hash) =
+				(T::Hashing::hash(&public_key), T::Hashing::hash(&message));
+			Self::deposit_signature_result(who, valid, public_keys_hash, messages_hash, &signature)
+		}
+
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::bls_aggregate_verify(public_keys.len() as u32))]
+		pub fn bls_aggregate_verify(
+			origin: OriginFor<T>,
+			public_keys: Vec<Vec<u8>>,
+			messages: Vec<Vec<u8>>,
+			signature: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				public_keys.len() <= bls_signatures::MAX_AGGREGATE_SIGNERS as usize,
+				Error::<T>::TooManyPublicKeys
+			);
+			let valid = bls_signatures::aggregate_verify::<MinPk>(
+				&public_keys,
+				&messages,
+				&signature,
+				MinPk::DST,
+			);
+			let (public_keys_hash, messages_hash) =
+				(T::Hashing::hash_of(&public_keys), T::Hashing::hash_of(&messages));
+			Self::deposit_signature_result(who, valid, public_keys_hash, messages_hash, &signature)
+		}
+
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::bls_fast_aggregate_verify(public_keys.len() as u32))]
+		pub fn bls_fast_aggregate_verify(
+			origin: OriginFor<T>,
+			public_keys: Vec<Vec<u8>>,
+			message: Vec<u8>,
+			signature: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				public_keys.len() <= bls_signatures::MAX_FAST_AGGREGATE_SIGNERS as usize,
+				Error::<T>::TooManyPublicKeys
+			);
+			let valid = bls_signatures::fast_aggregate_verify::<MinPk>(
+				&public_keys,
+				&message,
+				&signature,
+				MinPk::DST,
+			);
+			let (public_keys_hash, messages_hash) =
+				(T::Hashing::hash_of(&public_keys), T::Hashing::hash(&message));
+			Self::deposit_signature_result(who, valid, public_keys_hash, messages_hash, &signature)
+		}
+
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::bls_verify_min_sig())]
+		pub fn bls_verify_min_sig(
+			origin: OriginFor<T>,
+			public_key: Vec<u8>,
+			message: Vec<u8>,
+			signature: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let valid =
+				bls_signatures::verify::<MinSig>(&public_key, &message, &signature, MinSig::DST);
+			let (public_keys_hash, messages_hash) =
+				(T::Hashing::hash(&public_key), T::Hashing::hash(&message));
+			Self::deposit_signature_result(who, valid, public_keys_hash, messages_hash, &signature)
+		}
+
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::bls_aggregate_verify_min_sig(public_keys.len() as u32))]
+		pub fn bls_aggregate_verify_min_sig(
+			origin: OriginFor<T>,
+			public_keys: Vec<Vec<u8>>,
+			messages: Vec<Vec<u8>>,
+			signature: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				public_keys.len() <= bls_signatures::MAX_AGGREGATE_SIGNERS as usize,
+				Error::<T>::TooManyPublicKeys
+			);
+			let valid = bls_signatures::aggregate_verify::<MinSig>(
+				&public_keys,
+				&messages,
+				&signature,
+				MinSig::DST,
+			);
+			let (public_keys_hash, messages_hash) =
+				(T::Hashing::hash_of(&public_keys), T::Hashing::hash_of(&messages));
+			Self::deposit_signature_result(who, valid, public_keys_hash, messages_hash, &signature)
+		}
+
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::bls_fast_aggregate_verify_min_sig(public_keys.len() as u32))]
+		pub fn bls_fast_aggregate_verify_min_sig(
+			origin: OriginFor<T>,
+			public_keys: Vec<Vec<u8>>,
+			message: Vec<u8>,
+			signature: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				public_keys.len() <= bls_signatures::MAX_FAST_AGGREGATE_SIGNERS as usize,
+				Error::<T>::TooManyPublicKeys
+			);
+			let valid = bls_signatures::fast_aggregate_verify::<MinSig>(
+				&public_keys,
+				&message,
+				&signature,
+				MinSig::DST,
+			);
+			let (public_keys_hash, messages_hash) =
+				(T::Hashing::hash_of(&public_keys), T::Hashing::hash(&message));
+			Self::deposit_signature_result(who, valid, public_keys_hash, messages_hash, &signature)
+		}
+
+		/// Replaces the `[tau]G2` point of the KZG trusted setup.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::kzg_set_trusted_setup())]
+		pub fn kzg_set_trusted_setup(origin: OriginFor<T>, tau_g2: Vec<u8>) -> DispatchResult {
+			ensure_root(origin)?;
+			kzg::decode_setup(&tau_g2).map_err(Error::<T>::from)?;
+			let tau_g2: [u8; 96] =
+				tau_g2.try_into().map_err(|_| Error::<T>::InvalidTrustedSetup)?;
+			KzgTrustedSetup::<T>::put(tau_g2);
+			Ok(())
+		}
 
-			let _pvk = crate::bls12_381::prepare_verifying_key_groth16::<
-				bls12_381::Bls12_381Optimized,
-			>(vk)
-			.unwrap();
+		/// Verifies that the polynomial committed to in `commitment` evaluates to `y` at `z`.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::verify_kzg_proof())]
+		pub fn verify_kzg_proof(
+			origin: OriginFor<T>,
+			commitment: Vec<u8>,
+			z: Vec<u8>,
+			y: Vec<u8>,
+			proof: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let tau_g2 = Self::kzg_trusted_setup()?;
+			let opening =
+				kzg::Opening::decode(&commitment, &z, &y, &proof).map_err(Error::<T>::from)?;
+			ensure!(kzg::verify_kzg_proof(tau_g2, &opening), Error::<T>::KzgVerificationFailed);
+			Self::deposit_event(Event::KzgVerified {
+				who,
+				commitments_hash: T::Hashing::hash(&commitment),
+				inputs_hash: T::Hashing::hash_of(&(z, y)),
+				proof_hash: T::Hashing::hash(&proof),
+			});
 			Ok(())
 		}
+
+		/// Verifies a batch of KZG openings with a single multi-Miller loop.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::verify_kzg_proof_batch(commitments.len() as u32))]
+		pub fn verify_kzg_proof_batch(
+			origin: OriginFor<T>,
+			commitments: Vec<Vec<u8>>,
+			zs: Vec<Vec<u8>>,
+			ys: Vec<Vec<u8>>,
+			proofs: Vec<Vec<u8>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				commitments.len() <= kzg::MAX_BATCH_SIZE as usize,
+				Error::<T>::KzgBatchTooLarge
+			);
+			let tau_g2 = Self::kzg_trusted_setup()?;
+			let openings =
+				kzg::decode_openings(&commitments, &zs, &ys, &proofs).map_err(Error::<T>::from)?;
+			ensure!(
+				kzg::verify_kzg_proof_batch(tau_g2, &openings),
+				Error::<T>::KzgVerificationFailed
+			);
+			Self::deposit_event(Event::KzgVerified {
+				who,
+				commitments_hash: T::Hashing::hash_of(&commitments),
+				inputs_hash: T::Hashing::hash_of(&(zs, ys)),
+				proof_hash: T::Hashing::hash_of(&proofs),
+			});
+			Ok(())
+		}
+
+		/// Registers an uncompressed PLONK verifying key for `curve`.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::plonk_register_vk())]
+		pub fn plonk_register_vk(
+			origin: OriginFor<T>,
+			curve: plonk::Curve,
+			vk: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let srs = PlonkSrs::<T>::get(curve).ok_or(Error::<T>::PlonkSrsNotSet)?;
+			match curve {
+				plonk::Curve::Bls12_381 =>
+					plonk::validate_verifying_key::<ark_bls12_381::Bls12_381>(&vk, &srs),
+				plonk::Curve::Bn254 => plonk::validate_verifying_key::<ark_bn254::Bn254>(&vk, &srs),
+			}
+			.map_err(Error::<T>::from)?;
+			let vk: BoundedVec<_, _> =
+				vk.try_into().map_err(|_| Error::<T>::InvalidPlonkVerifyingKey)?;
+
+			let id = NextPlonkVerifyingKeyId::<T>::get();
+			let next_id = id.checked_add(1).ok_or(Error::<T>::IdsExhausted)?;
+			let deposit = T::VerifyingKeyDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+			PlonkVerifyingKeys::<T>::insert(id, (curve, vk));
+			PlonkVerifyingKeyDeposits::<T>::insert(id, (who.clone(), deposit));
+			NextPlonkVerifyingKeyId::<T>::put(next_id);
+			Self::deposit_event(Event::PlonkVerifyingKeyRegistered { id, who });
+			Ok(())
+		}
+
+		/// Verifies a PLONK proof against a registered key with the plain arkworks curves.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::plonk_verify().max(T::WeightInfo::plonk_verify_bn254()))]
+		pub fn plonk_verify(
+			origin: OriginFor<T>,
+			vk_id: u32,
+			public_inputs: Vec<u8>,
+			proof: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (curve, vk) =
+				PlonkVerifyingKeys::<T>::get(vk_id).ok_or(Error::<T>::UnknownVerifyingKey)?;
+			let valid = match curve {
+				plonk::Curve::Bls12_381 => plonk::verify_serialized::<ark_bls12_381::Bls12_381>(
+					&vk,
+					&public_inputs,
+					&proof,
+				),
+				plonk::Curve::Bn254 =>
+					plonk::verify_serialized::<ark_bn254::Bn254>(&vk, &public_inputs, &proof),
+			};
+			Self::deposit_plonk_result(who, vk_id, &public_inputs, &proof, valid)
+		}
+
+		/// Verifies a PLONK proof against a registered key with the host accelerated
+		/// BLS12-381 curve.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::plonk_verify_optimized())]
+		pub fn plonk_verify_optimized(
+			origin: OriginFor<T>,
+			vk_id: u32,
+			public_inputs: Vec<u8>,
+			proof: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (curve, vk) =
+				PlonkVerifyingKeys::<T>::get(vk_id).ok_or(Error::<T>::UnknownVerifyingKey)?;
+			ensure!(curve == plonk::Curve::Bls12_381, Error::<T>::UnsupportedCurve);
+			let valid = plonk::verify_serialized::<Bls12_381Optimized>(&vk, &public_inputs, &proof);
+			Self::deposit_plonk_result(who, vk_id, &public_inputs, &proof, valid)
+		}
+
+		/// Registers an uncompressed Marlin index verifying key over BLS12-381, indexed from
+		/// the universal parameters set by root, and reserves `VerifyingKeyDeposit` for it.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::marlin_register_vk())]
+		pub fn marlin_register_vk(origin: OriginFor<T>, vk: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let srs = MarlinSrs::<T>::get().ok_or(Error::<T>::MarlinSrsNotSet)?;
+			marlin::validate_verifying_key::<ark_bls12_381::Bls12_381>(&vk, &srs)
+				.map_err(Error::<T>::from)?;
+			let vk: BoundedVec<_, _> =
+				vk.try_into().map_err(|_| Error::<T>::InvalidMarlinVerifyingKey)?;
+
+			let id = NextMarlinVerifyingKeyId::<T>::get();
+			let next_id = id.checked_add(1).ok_or(Error::<T>::IdsExhausted)?;
+			let deposit = T::VerifyingKeyDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+			MarlinVerifyingKeys::<T>::insert(id, vk);
+			MarlinVerifyingKeyDeposits::<T>::insert(id, (who.clone(), deposit));
+			NextMarlinVerifyingKeyId::<T>::put(next_id);
+			Self::deposit_event(Event::MarlinVerifyingKeyRegistered { id, who });
+			Ok(())
+		}
+
+		/// Verifies a Marlin proof against a registered key with the plain arkworks curve.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::marlin_verify())]
+		pub fn marlin_verify(
+			origin: OriginFor<T>,
+			vk_id: u32,
+			public_inputs: Vec<u8>,
+			proof: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let vk = MarlinVerifyingKeys::<T>::get(vk_id).ok_or(Error::<T>::UnknownVerifyingKey)?;
+			let valid =
+				marlin::verify_serialized::<ark_bls12_381::Bls12_381>(&vk, &public_inputs, &proof);
+			Self::deposit_marlin_result(who, vk_id, &public_inputs, &proof, valid)
+		}
+
+		/// Verifies a Marlin proof against a registered key with the host accelerated
+		/// BLS12-381 curve.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::marlin_verify_optimized())]
+		pub fn marlin_verify_optimized(
+			origin: OriginFor<T>,
+			vk_id: u32,
+			public_inputs: Vec<u8>,
+			proof: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let vk = MarlinVerifyingKeys::<T>::get(vk_id).ok_or(Error::<T>::UnknownVerifyingKey)?;
+			let valid =
+				marlin::verify_serialized::<Bls12_381Optimized>(&vk, &public_inputs, &proof);
+			Self::deposit_marlin_result(who, vk_id, &public_inputs, &proof, valid)
+		}
+
+		/// Registers an uncompressed Groth16 verifying key over BLS12-381 and stores it
+		/// prepared for verification.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::groth16_register_vk())]
+		pub fn groth16_register_vk(origin: OriginFor<T>, vk: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let id = Self::register_groth16_verifying_key(vk, VerifyingKeyStatus::Pending)?;
+			Self::deposit_event(Event::Groth16VerifyingKeyRegistered { id, who });
+			Ok(())
+		}
+
+		/// Replaces the verifier setup of SnarkPack aggregates.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::groth16_set_aggregation_srs())]
+		pub fn groth16_set_aggregation_srs(origin: OriginFor<T>, srs: Vec<u8>) -> DispatchResult {
+			ensure_root(origin)?;
+			snarkpack::validate_srs::<Bls12_381Optimized>(&srs).map_err(Error::<T>::from)?;
+			let srs: BoundedVec<_, _> =
+				srs.try_into().map_err(|_| Error::<T>::InvalidAggregationSrs)?;
+			AggregationSrs::<T>::put(srs);
+			Ok(())
+		}
+
+		/// Verifies a SnarkPack aggregate of Groth16 proofs, one for each entry of
+		/// `inputs_list`, against a registered key with the host accelerated BLS12-381 curve.
+		/// The weight grows with the rounds of the recursion of the aggregate, and each statement
+		/// is passed to `Config::OnProofVerified`.
+		#[pallet::call_index(25)]
+		#[pallet::weight(
+			T::WeightInfo::groth16_verify_aggregate(snarkpack::rounds(inputs_list.len()))
+		)]
+		pub fn groth16_verify_aggregate(
+			origin: OriginFor<T>,
+			vk_id: u32,
+			inputs_list: Vec<Vec<u8>>,
+			aggregate_proof: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				inputs_list.len() <= snarkpack::MAX_AGGREGATED_PROOFS as usize,
+				Error::<T>::TooManyAggregatedProofs
+			);
+			let vk =
+				Groth16VerifyingKeys::<T>::get(vk_id).ok_or(Error::<T>::UnknownVerifyingKey)?;
+			Self::ensure_not_frozen(vk_id)?;
+			let srs = AggregationSrs::<T>::get().ok_or(Error::<T>::MissingAggregationSrs)?;
+			let valid = snarkpack::verify_serialized::<Bls12_381Optimized>(
+				&srs,
+				&vk,
+				&inputs_list,
+				&aggregate_proof,
+			);
+			ensure!(valid.map_err(Error::<T>::from)?, Error::<T>::AggregateVerificationFailed);
+			for public_inputs in &inputs_list {
+				T::OnProofVerified::on_proof_verified(&who, vk_id, public_inputs);
+			}
+			Self::deposit_event(Event::Groth16Verified {
+				who,
+				vk_hash: T::Hashing::hash(&vk),
+				inputs_hash: T::Hashing::hash_of(&inputs_list),
+				proof_hash: T::Hashing::hash(&aggregate_proof),
+				backend: Groth16Backend::Bls12_381Optimized,
+			});
+			Ok(())
+		}
+
+		/// Verifies a Groth16 proof against the prepared form of a registered key with the host
+		/// accelerated BLS12-381 curve.
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::groth16_verify_prepared())]
+		pub fn groth16_verify_prepared(
+			origin: OriginFor<T>,
+			vk_id: u32,
+			public_inputs: Vec<u8>,
+			proof: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::verify(vk_id, &public_inputs, &proof)?;
+			T::OnProofVerified::on_proof_verified(&who, vk_id, &public_inputs);
+			Self::deposit_groth16_result(
+				who,
+				vk_id,
+				&public_inputs,
+				&proof,
+				true,
+				Groth16Backend::Bls12_381Optimized,
+			);
+			Ok(())
+		}
+
+		/// Queues a Groth16 proof against a registered key for verification by the offchain
+		/// worker and reserves `VerificationRequestDeposit` until its result is submitted.
+		#[pallet::call_index(29)]
+		#[pallet::weight(T::WeightInfo::groth16_request_verification())]
+		pub fn groth16_request_verification(
+			origin: OriginFor<T>,
+			vk_id: u32,
+			public_inputs: Vec<u8>,
+			proof: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				Groth16PreparedVerifyingKeys::<T>::contains_key(vk_id),
+				Error::<T>::UnknownVerifyingKey
+			);
+			Self::ensure_not_frozen(vk_id)?;
+			let public_inputs: BoundedVec<_, _> =
+				public_inputs.try_into().map_err(|_| Error::<T>::InvalidPublicInputs)?;
+			let proof: BoundedVec<_, _> =
+				proof.try_into().map_err(|_| Error::<T>::InvalidGroth16Proof)?;
+
+			let request_id = NextVerificationRequestId::<T>::get();
+			let next_request_id = request_id.checked_add(1).ok_or(Error::<T>::IdsExhausted)?;
+			let deposit = T::VerificationRequestDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+			VerificationRequests::<T>::insert(
+				request_id,
+				(who.clone(), vk_id, public_inputs, proof),
+			);
+			VerificationRequestDeposits::<T>::insert(request_id, deposit);
+			NextVerificationRequestId::<T>::put(next_request_id);
+			Self::deposit_event(Event::VerificationRequested { request_id, vk_id, who });
+			Ok(())
+		}
+
+		/// Replaces the accounts trusted to submit offchain verification results.
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::set_result_submitters())]
+		pub fn set_result_submitters(
+			origin: OriginFor<T>,
+			submitters: Vec<T::AccountId>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let submitters: BoundedVec<_, _> =
+				submitters.try_into().map_err(|_| Error::<T>::TooManyResultSubmitters)?;
+			ResultSubmitters::<T>::put(submitters);
+			Ok(())
+		}
+
+		/// Settles a queued request with the result of the offchain worker.
+		#[pallet::call_index(31)]
+		#[pallet::weight(T::WeightInfo::submit_verification_result())]
+		pub fn submit_verification_result(
+			origin: OriginFor<T>,
+			request_id: u32,
+			valid: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_result_submitter(&who), Error::<T>::NotResultSubmitter);
+			Self::settle_verification_request(request_id, valid)
+		}
+
+		/// Settles a queued request with a result signed by the offchain worker.
+		#[pallet::call_index(32)]
+		#[pallet::weight(T::WeightInfo::submit_verification_result())]
+		pub fn submit_verification_result_unsigned(
+			origin: OriginFor<T>,
+			payload: VerificationResultPayload<T::Public>,
+			_signature: T::Signature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			Self::settle_verification_request(payload.request_id, payload.valid)
+		}
+
+		/// Verifies a Groth16 proof against a registered key like `groth16_verify_prepared`, but
+		/// records a proof that does not verify in a `VerificationFailed` event instead of
+		/// failing, for audit trails.
+		#[pallet::call_index(33)]
+		#[pallet::weight(T::WeightInfo::groth16_verify_audited())]
+		pub fn groth16_verify_audited(
+			origin: OriginFor<T>,
+			vk_id: u32,
+			public_inputs: Vec<u8>,
+			proof: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				Groth16VerifyingKeyHashes::<T>::contains_key(vk_id),
+				Error::<T>::UnknownVerifyingKey
+			);
+			Self::ensure_not_frozen(vk_id)?;
+			let valid = Self::verify(vk_id, &public_inputs, &proof).is_ok();
+			if valid {
+				T::OnProofVerified::on_proof_verified(&who, vk_id, &public_inputs);
+			}
+			Self::deposit_groth16_result(
+				who,
+				vk_id,
+				&public_inputs,
+				&proof,
+				valid,
+				Groth16Backend::Bls12_381Optimized,
+			);
+			Ok(())
+		}
+
+		/// Like `groth16_verification_optimized`, with the whole verification in the experimental
+		/// `groth16_verify_bls12_381` host call. `inputs` are uncompressed scalars one after the
+		/// other, so a single input is encoded as for the other backends. Arguments within the
+		/// bounds of registered keys but malformed fail with `VerificationFailed` too, as the host
+		/// call only returns whether the proof verified.
+		///
+		/// Only with the `experimental` feature, as a runtime calling it only runs on nodes with
+		/// the host function.
+		#[cfg(feature = "experimental")]
+		#[pallet::call_index(34)]
+		#[pallet::weight(T::WeightInfo::groth16_verification_host(
+			(inputs.len() as u32).saturating_div(bls12_381::GROTH16_SCALAR_LEN)
+		))]
+		pub fn groth16_verification_host(
+			origin: OriginFor<T>,
+			vk: Vec<u8>,
+			inputs: Vec<u8>,
+			proof: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				vk.len() <= bls12_381::GROTH16_MAX_VERIFYING_KEY_LEN as usize,
+				Error::<T>::InvalidGroth16VerifyingKey
+			);
+			ensure!(
+				inputs.len() <=
+					(bls12_381::GROTH16_MAX_PUBLIC_INPUTS * bls12_381::GROTH16_SCALAR_LEN)
+						as usize,
+				Error::<T>::InvalidPublicInputs
+			);
+			ensure!(
+				proof.len() == bls12_381::GROTH16_PROOF_LEN as usize,
+				Error::<T>::InvalidGroth16Proof
+			);
+			ensure!(
+				host::groth16::groth16_verify_bls12_381(&vk, &inputs, &proof),
+				Error::<T>::VerificationFailed
+			);
+			Self::deposit_event(Event::Groth16Verified {
+				who,
+				vk_hash: T::Hashing::hash(&vk),
+				inputs_hash: T::Hashing::hash(&inputs),
+				proof_hash: T::Hashing::hash(&proof),
+				backend: Groth16Backend::HostGroth16,
+			});
+			Ok(())
+		}
+
+		/// Approves the registered Groth16 key `vk_id`, also to unfreeze it.
+		#[pallet::call_index(35)]
+		#[pallet::weight(T::WeightInfo::groth16_approve_vk())]
+		pub fn groth16_approve_vk(origin: OriginFor<T>, vk_id: u32) -> DispatchResult {
+			let event = Event::Groth16VerifyingKeyApproved { id: vk_id };
+			Self::set_verifying_key_status(origin, vk_id, VerifyingKeyStatus::Approved, event)
+		}
+
+		/// Freezes the registered Groth16 key `vk_id`, refusing proofs against it.
+		#[pallet::call_index(36)]
+		#[pallet::weight(T::WeightInfo::groth16_freeze_vk())]
+		pub fn groth16_freeze_vk(origin: OriginFor<T>, vk_id: u32) -> DispatchResult {
+			let event = Event::Groth16VerifyingKeyFrozen { id: vk_id };
+			Self::set_verifying_key_status(origin, vk_id, VerifyingKeyStatus::Frozen, event)
+		}
+
+		/// Deprecates the registered Groth16 key `vk_id` in favour of another key.
+		#[pallet::call_index(37)]
+		#[pallet::weight(T::WeightInfo::groth16_deprecate_vk())]
+		pub fn groth16_deprecate_vk(origin: OriginFor<T>, vk_id: u32) -> DispatchResult {
+			let event = Event::Groth16VerifyingKeyDeprecated { id: vk_id };
+			Self::set_verifying_key_status(origin, vk_id, VerifyingKeyStatus::Deprecated, event)
+		}
+
+		/// Publishes the registered Groth16 key `vk_id` as the next version of the circuit
+		/// `name`. Proofs of the previous version are still accepted for `CircuitGracePeriod`
+		/// blocks.
+		#[pallet::call_index(38)]
+		#[pallet::weight(T::WeightInfo::groth16_publish_circuit_version())]
+		pub fn groth16_publish_circuit_version(
+			origin: OriginFor<T>,
+			name: Vec<u8>,
+			vk_id: u32,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let name: CircuitName = name.try_into().map_err(|_| Error::<T>::InvalidCircuitName)?;
+			ensure!(
+				Groth16VerifyingKeyHashes::<T>::contains_key(vk_id),
+				Error::<T>::UnknownVerifyingKey
+			);
+			Self::ensure_not_frozen(vk_id)?;
+			Self::publish_circuit_version(name, vk_id);
+			Ok(())
+		}
+
+		/// Verifies a Groth16 proof against `version` of the circuit `name` like
+		/// `groth16_verify_prepared`.
+		#[pallet::call_index(39)]
+		#[pallet::weight(T::WeightInfo::groth16_verify_circuit())]
+		pub fn groth16_verify_circuit(
+			origin: OriginFor<T>,
+			name: Vec<u8>,
+			version: u32,
+			public_inputs: Vec<u8>,
+			proof: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let vk_id = Self::verify_circuit(&name, version, &public_inputs, &proof)?;
+			T::OnProofVerified::on_proof_verified(&who, vk_id, &public_inputs);
+			Self::deposit_groth16_result(
+				who,
+				vk_id,
+				&public_inputs,
+				&proof,
+				true,
+				Groth16Backend::Bls12_381Optimized,
+			);
+			Ok(())
+		}
+
+		/// Sets the uncompressed `[x]G2` point of the universal setup PLONK verifying keys over
+		/// `curve` are registered for. Keys registered before stay bound to the previous setup.
+		#[pallet::call_index(40)]
+		#[pallet::weight(T::WeightInfo::plonk_set_srs())]
+		pub fn plonk_set_srs(
+			origin: OriginFor<T>,
+			curve: plonk::Curve,
+			x_2: Vec<u8>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			match curve {
+				plonk::Curve::Bls12_381 => plonk::validate_srs::<ark_bls12_381::Bls12_381>(&x_2),
+				plonk::Curve::Bn254 => plonk::validate_srs::<ark_bn254::Bn254>(&x_2),
+			}
+			.map_err(Error::<T>::from)?;
+			let x_2: BoundedVec<_, _> = x_2.try_into().map_err(|_| Error::<T>::InvalidPlonkSrs)?;
+			PlonkSrs::<T>::insert(curve, x_2);
+			Self::deposit_event(Event::PlonkSrsSet { curve });
+			Ok(())
+		}
+
+		/// Removes a PLONK verifying key registered by the caller and returns its deposit.
+		#[pallet::call_index(41)]
+		#[pallet::weight(T::WeightInfo::plonk_remove_vk())]
+		pub fn plonk_remove_vk(origin: OriginFor<T>, id: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (owner, deposit) =
+				PlonkVerifyingKeyDeposits::<T>::get(id).ok_or(Error::<T>::UnknownVerifyingKey)?;
+			ensure!(owner == who, Error::<T>::NotVerifyingKeyOwner);
+			PlonkVerifyingKeys::<T>::remove(id);
+			PlonkVerifyingKeyDeposits::<T>::remove(id);
+			T::Currency::unreserve(&owner, deposit);
+			Self::deposit_event(Event::PlonkVerifyingKeyRemoved { id });
+			Ok(())
+		}
+
+		/// Sets the uncompressed `[tau]G2` point of the universal parameters Marlin index
+		/// verifying keys are registered for. Keys registered before stay bound to the previous
+		/// parameters.
+		#[pallet::call_index(42)]
+		#[pallet::weight(T::WeightInfo::marlin_set_srs())]
+		pub fn marlin_set_srs(origin: OriginFor<T>, tau_h: Vec<u8>) -> DispatchResult {
+			ensure_root(origin)?;
+			marlin::validate_srs::<ark_bls12_381::Bls12_381>(&tau_h).map_err(Error::<T>::from)?;
+			let tau_h: BoundedVec<_, _> =
+				tau_h.try_into().map_err(|_| Error::<T>::InvalidMarlinSrs)?;
+			MarlinSrs::<T>::put(tau_h);
+			Self::deposit_event(Event::MarlinSrsSet);
+			Ok(())
+		}
+
+		/// Removes a Marlin index verifying key registered by the caller and returns its
+		/// deposit.
+		#[pallet::call_index(43)]
+		#[pallet::weight(T::WeightInfo::marlin_remove_vk())]
+		pub fn marlin_remove_vk(origin: OriginFor<T>, id: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (owner, deposit) =
+				MarlinVerifyingKeyDeposits::<T>::get(id).ok_or(Error::<T>::UnknownVerifyingKey)?;
+			ensure!(owner == who, Error::<T>::NotVerifyingKeyOwner);
+			MarlinVerifyingKeys::<T>::remove(id);
+			MarlinVerifyingKeyDeposits::<T>::remove(id);
+			T::Currency::unreserve(&owner, deposit);
+			Self::deposit_event(Event::MarlinVerifyingKeyRemoved { id });
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		/// Accepts results signed by a trusted submitter for requests that are still queued.
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let (payload, signature) = match call {
+				Call::submit_verification_result_unsigned { payload, signature } =>
+					(payload, signature),
+				_ => return InvalidTransaction::Call.into(),
+			};
+			if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
+				return InvalidTransaction::BadProof.into()
+			}
+			if !Self::is_result_submitter(&payload.public.clone().into_account()) {
+				return InvalidTransaction::BadSigner.into()
+			}
+			if !VerificationRequests::<T>::contains_key(payload.request_id) {
+				return InvalidTransaction::Stale.into()
+			}
+			ValidTransaction::with_tag_prefix("Groth16VerificationResult")
+				.priority(T::UnsignedPriority::get())
+				.and_provides(payload.request_id)
+				.longevity(5)
+				.propagate(true)
+				.build()
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Verifies a Groth16 proof against the registered key `vk_id` without dispatching, for
+		/// pallets that act on the proof themselves. Neither deposits an event nor calls
+		/// `Config::OnProofVerified`. Fails for frozen keys and returns the status of the key
+		/// otherwise, which callers that gate anything on the proof must check to be
+		/// `VerifyingKeyStatus::Approved`: anyone can register a key.
+		pub fn verify(
+			vk_id: u32,
+			public_inputs: &[u8],
+			proof: &[u8],
+		) -> Result<VerifyingKeyStatus, Error<T>> {
+			let pvk = Groth16PreparedVerifyingKeys::<T>::get(vk_id)
+				.ok_or(Error::<T>::UnknownVerifyingKey)?;
+			Self::ensure_not_frozen(vk_id)?;
+			let valid = bls12_381::verify_prepared_groth16(&pvk, public_inputs, proof)?;
+			ensure!(valid, Error::<T>::VerificationFailed);
+			Ok(Groth16VerifyingKeyStatuses::<T>::get(vk_id))
+		}
+
+		/// Like `verify`, against `version` of the circuit `name`, and returns the id of its key.
+		/// Fails once the grace period of a superseded version has passed.
+		pub fn verify_circuit(
+			name: &[u8],
+			version: u32,
+			public_inputs: &[u8],
+			proof: &[u8],
+		) -> Result<u32, Error<T>> {
+			let name: CircuitName =
+				name.to_vec().try_into().map_err(|_| Error::<T>::InvalidCircuitName)?;
+			let CircuitVersion { vk_id, expires_at } = CircuitVersions::<T>::get(&name, version)
+				.ok_or(Error::<T>::UnknownCircuitVersion)?;
+			if let Some(expires_at) = expires_at {
+				ensure!(
+					frame_system::Pallet::<T>::block_number() < expires_at,
+					Error::<T>::CircuitVersionExpired
+				);
+			}
+			Self::verify(vk_id, public_inputs, proof)?;
+			Ok(vk_id)
+		}
+
+		/// Validates and prepares the uncompressed Groth16 key `vk` over BLS12-381 and stores it
+		/// under the next id with `status`.
+		fn register_groth16_verifying_key(
+			vk: Vec<u8>,
+			status: VerifyingKeyStatus,
+		) -> Result<u32, Error<T>> {
+			let pvk = bls12_381::prepare_registered_verifying_key_groth16(&vk)
+				.map_err(Error::<T>::from)?;
+			Self::store_groth16_verifying_key(vk, pvk, status)
+		}
+
+		/// Stores a Groth16 verifying key with its prepared form `pvk` under the next id.
+		fn store_groth16_verifying_key(
+			vk: Vec<u8>,
+			pvk: Vec<u8>,
+			status: VerifyingKeyStatus,
+		) -> Result<u32, Error<T>> {
+			let pvk: BoundedVec<_, _> =
+				pvk.try_into().map_err(|_| Error::<T>::InvalidGroth16VerifyingKey)?;
+			let vk: BoundedVec<_, _> =
+				vk.try_into().map_err(|_| Error::<T>::InvalidGroth16VerifyingKey)?;
+
+			let id = NextGroth16VerifyingKeyId::<T>::get();
+			let next_id = id.checked_add(1).ok_or(Error::<T>::IdsExhausted)?;
+			let vk_hash = T::Hashing::hash(&vk);
+			Groth16VerifyingKeyHashes::<T>::insert(id, vk_hash);
+			Groth16VerifyingKeyIds::<T>::insert(vk_hash, id);
+			Groth16VerifyingKeys::<T>::insert(id, vk);
+			Groth16PreparedVerifyingKeys::<T>::insert(id, pvk);
+			Groth16VerifyingKeyStatuses::<T>::insert(id, status);
+			NextGroth16VerifyingKeyId::<T>::put(next_id);
+			Ok(id)
+		}
+
+		/// Publishes the registered key `vk_id` as the next version of the circuit `name`,
+		/// starting the grace period of the previous version.
+		fn publish_circuit_version(name: CircuitName, vk_id: u32) {
+			let previous = LatestCircuitVersions::<T>::get(&name);
+			if let Some(previous) = previous {
+				let expires_at =
+					frame_system::Pallet::<T>::block_number() + T::CircuitGracePeriod::get();
+				CircuitVersions::<T>::mutate(&name, previous, |version| {
+					if let Some(version) = version {
+						version.expires_at = Some(expires_at);
+					}
+				});
+			}
+			let version = previous.map_or(1, |previous| previous.saturating_add(1));
+			CircuitVersions::<T>::insert(
+				&name,
+				version,
+				CircuitVersion { vk_id, expires_at: None },
+			);
+			LatestCircuitVersions::<T>::insert(&name, version);
+			Self::deposit_event(Event::CircuitVersionPublished { name, version, vk_id });
+		}
+
+		/// Prepares `public_inputs` for the registered Groth16 key `vk_id` on `Curve` and stores
+		/// them under a new handle.
+		fn store_prepared_inputs<Curve: ark_ec::pairing::Pairing>(
+			who: T::AccountId,
+			vk_id: u32,
+			public_inputs: Vec<u8>,
+		) -> DispatchResult {
+			let pvk = Groth16PreparedVerifyingKeys::<T>::get(vk_id)
+				.ok_or(Error::<T>::UnknownVerifyingKey)?;
+			Self::ensure_not_frozen(vk_id)?;
+			let prepared_inputs =
+				bls12_381::prepare_registered_inputs_groth16::<Curve>(&pvk, &public_inputs)
+					.map_err(Error::<T>::from)?;
+			let public_inputs: BoundedVec<_, _> =
+				public_inputs.try_into().map_err(|_| Error::<T>::InvalidPublicInputs)?;
+
+			let handle = NextPreparedInputsHandle::<T>::get();
+			let next_handle = handle.checked_add(1).ok_or(Error::<T>::IdsExhausted)?;
+			let expires_at =
+				frame_system::Pallet::<T>::block_number() + T::PreparedInputsLifetime::get();
+			PreparedInputs::<T>::insert(
+				handle,
+				(vk_id, prepared_inputs, public_inputs, expires_at),
+			);
+			NextPreparedInputsHandle::<T>::put(next_handle);
+			Self::deposit_event(Event::Groth16InputsPrepared { handle, vk_id, expires_at, who });
+			Ok(())
+		}
+
+		/// Verifies `proof` on `Curve` against the public inputs stored under `handle` and
+		/// removes them on success.
+		fn verify_prepared_inputs<Curve: bls12_381::RegisteredKeyCurve>(
+			who: T::AccountId,
+			handle: u32,
+			proof: Vec<u8>,
+			backend: Groth16Backend,
+		) -> DispatchResult {
+			let (vk_id, prepared_inputs, public_inputs, expires_at) =
+				PreparedInputs::<T>::get(handle).ok_or(Error::<T>::UnknownPreparedInputs)?;
+			ensure!(
+				expires_at > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::PreparedInputsExpired
+			);
+			let pvk = Groth16PreparedVerifyingKeys::<T>::get(vk_id)
+				.ok_or(Error::<T>::UnknownVerifyingKey)?;
+			Self::ensure_not_frozen(vk_id)?;
+			let valid =
+				bls12_381::verify_prepared_inputs_groth16::<Curve>(&pvk, &prepared_inputs, &proof);
+			ensure!(valid.map_err(Error::<T>::from)?, Error::<T>::VerificationFailed);
+			PreparedInputs::<T>::remove(handle);
+			T::OnProofVerified::on_proof_verified(&who, vk_id, &public_inputs);
+			Self::deposit_groth16_result(who, vk_id, &public_inputs, &proof, true, backend);
+			Ok(())
+		}
+
+		fn ensure_not_frozen(vk_id: u32) -> Result<(), Error<T>> {
+			ensure!(
+				Groth16VerifyingKeyStatuses::<T>::get(vk_id) != VerifyingKeyStatus::Frozen,
+				Error::<T>::VerifyingKeyFrozen
+			);
+			Ok(())
+		}
+
+		fn ensure_approved(vk_id: u32) -> Result<(), Error<T>> {
+			match Groth16VerifyingKeyStatuses::<T>::get(vk_id) {
+				VerifyingKeyStatus::Approved => Ok(()),
+				VerifyingKeyStatus::Frozen => Err(Error::<T>::VerifyingKeyFrozen),
+				_ => Err(Error::<T>::VerifyingKeyNotApproved),
+			}
+		}
+
+		/// Sets the status of the registered Groth16 key `vk_id` on behalf of the admin and
+		/// deposits `event`.
+		fn set_verifying_key_status(
+			origin: OriginFor<T>,
+			vk_id: u32,
+			status: VerifyingKeyStatus,
+			event: Event<T>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				Groth16VerifyingKeyHashes::<T>::contains_key(vk_id),
+				Error::<T>::UnknownVerifyingKey
+			);
+			Groth16VerifyingKeyStatuses::<T>::insert(vk_id, status);
+			Self::deposit_event(event);
+			Ok(())
+		}
+
+		fn is_result_submitter(who: &T::AccountId) -> bool {
+			ResultSubmitters::<T>::get().contains(who)
+		}
+
+		/// Deposits `Groth16Verified` or `VerificationFailed` for a proof checked against the
+		/// registered key `vk_id` on `backend`.
+		fn deposit_groth16_result(
+			who: T::AccountId,
+			vk_id: u32,
+			public_inputs: &[u8],
+			proof: &[u8],
+			valid: bool,
+			backend: Groth16Backend,
+		) {
+			let vk_hash = Groth16VerifyingKeyHashes::<T>::get(vk_id).unwrap_or_default();
+			let inputs_hash = T::Hashing::hash(public_inputs);
+			let proof_hash = T::Hashing::hash(proof);
+			Self::deposit_event(if valid {
+				Event::Groth16Verified { who, vk_hash, inputs_hash, proof_hash, backend }
+			} else {
+				Event::VerificationFailed { who, vk_hash, inputs_hash, proof_hash, backend }
+			});
+		}
+
+		fn settle_verification_request(request_id: u32, valid: bool) -> DispatchResult {
+			let (who, vk_id, public_inputs, proof) = VerificationRequests::<T>::take(request_id)
+				.ok_or(Error::<T>::UnknownVerificationRequest)?;
+			// Requests queued before deposits were taken have none.
+			if let Some(deposit) = VerificationRequestDeposits::<T>::take(request_id) {
+				T::Currency::unreserve(&who, deposit);
+			}
+			// Keys frozen while the request was queued no longer verify proofs.
+			let valid = valid && Self::ensure_not_frozen(vk_id).is_ok();
+			if valid {
+				T::OnProofVerified::on_proof_verified(&who, vk_id, &public_inputs);
+			}
+			Self::deposit_groth16_result(
+				who.clone(),
+				vk_id,
+				&public_inputs,
+				&proof,
+				valid,
+				Groth16Backend::Bls12_381Optimized,
+			);
+			Self::deposit_event(Event::VerificationRequestProcessed { request_id, who, valid });
+			Ok(())
+		}
+
+		fn deposit_plonk_result(
+			who: T::AccountId,
+			vk_id: u32,
+			public_inputs: &[u8],
+			proof: &[u8],
+			valid: Result<bool, plonk::Error>,
+		) -> DispatchResult {
+			ensure!(valid.map_err(Error::<T>::from)?, Error::<T>::PlonkVerificationFailed);
+			Self::deposit_event(Event::PlonkVerified {
+				who,
+				vk_id,
+				inputs_hash: T::Hashing::hash(public_inputs),
+				proof_hash: T::Hashing::hash(proof),
+			});
+			Ok(())
+		}
+
+		fn deposit_marlin_result(
+			who: T::AccountId,
+			vk_id: u32,
+			public_inputs: &[u8],
+			proof: &[u8],
+			valid: Result<bool, marlin::Error>,
+		) -> DispatchResult {
+			ensure!(valid.map_err(Error::<T>::from)?, Error::<T>::MarlinVerificationFailed);
+			Self::deposit_event(Event::MarlinVerified {
+				who,
+				vk_id,
+				inputs_hash: T::Hashing::hash(public_inputs),
+				proof_hash: T::Hashing::hash(proof),
+			});
+			Ok(())
+		}
+
+		fn kzg_trusted_setup() -> Result<bls12_381::G2AffineOptimized, Error<T>> {
+			kzg::decode_setup(&KzgTrustedSetup::<T>::get()).map_err(Error::<T>::from)
+		}
+
+		fn deposit_signature_result(
+			who: T::AccountId,
+			valid: Result<bool, bls_signatures::Error>,
+			public_keys_hash: T::Hash,
+			messages_hash: T::Hash,
+			signature: &[u8],
+		) -> DispatchResult {
+			if valid.map_err(Error::<T>::from)? {
+				let signature_hash = T::Hashing::hash(signature);
+				Self::deposit_event(Event::BlsSignatureVerified {
+					who,
+					public_keys_hash,
+					messages_hash,
+					signature_hash,
+				});
+				Ok(())
+			} else {
+				Err(Error::<T>::SignatureVerificationFailed.into())
+			}
+		}
 	}
 }
@@ -0,0 +1,395 @@
+//! The Marlin preprocessing SNARK (<https://eprint.iacr.org/2019/1047>) for R1CS with a
+//! universal KZG setup, behind the `ark_snark::SNARK` and `UniversalSetupSNARK` traits.
+//!
+//! The holographic IOP follows section 5 of the paper without the zero knowledge masking: the
+//! public input lives on the subgroup `H_x` of the constraint domain `H`, `z_C` is replaced by
+//! `z_A z_B` in the first sumcheck, and the third sumcheck runs over the domain `K` of the
+//! non-zero matrix entries. The degree bounds of `g_1`, `g_2` and `g_3` are enforced with shifted
+//! commitments `[X^(D - d) g]` against the maximum degree `D` of the universal parameters. All
+//! openings at `beta_1`, `beta_2` and `beta_3` are checked with a single pairing product.
+//!
+//! With the host accelerated `Bls12_381Optimized` the MSM and the pairing check run natively.
+
+use crate::utils::Transcript;
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{FftField, Field, One, PrimeField, Zero};
+use ark_relations::r1cs::SynthesisError;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use ark_std::{fmt, marker::PhantomData, vec::Vec};
+
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+pub mod circuit;
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+mod prover;
+
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+pub use prover::ProvingKey;
+
+/// Upper bound for the uncompressed encoding of a `VerifyingKey`.
+pub const MAX_VERIFYING_KEY_LEN: u32 = 2048;
+
+/// Upper bound for the uncompressed encoding of the `[tau]G2` point of universal parameters.
+pub const MAX_SRS_LEN: u32 = 192;
+
+/// The most public inputs of a registered verifying key, without the constant one. Verification
+/// evaluates a Lagrange polynomial, with a field inversion, for each instance variable, which
+/// its fixed weight covers up to this many.
+pub const MAX_PUBLIC_INPUTS: u64 = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+	/// The verifying key does not decode or its domains are not supported.
+	InvalidVerifyingKey,
+	/// The proof does not decode.
+	InvalidProof,
+	/// The public inputs do not decode or do not match the verifying key.
+	PublicInputMismatch,
+	/// The circuit could not be synthesized.
+	Synthesis,
+	/// A challenge of the prover fell into one of the evaluation domains.
+	DegenerateChallenge,
+	/// The `[tau]G2` point of the universal parameters does not decode.
+	InvalidSrs,
+	/// The verifying key was not indexed from the given universal parameters.
+	SrsMismatch,
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Debug::fmt(self, f)
+	}
+}
+
+impl ark_std::error::Error for Error {}
+
+impl From<SynthesisError> for Error {
+	fn from(_: SynthesisError) -> Self {
+		Error::Synthesis
+	}
+}
+
+/// The universal KZG parameters, valid for every circuit up to `powers_of_g.len() - 1`.
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct UniversalParams<E: Pairing> {
+	/// `[tau^i]G1` for `i = 0..=D`.
+	pub powers_of_g: Vec<E::G1Affine>,
+	/// `[tau]G2`.
+	pub tau_h: E::G2Affine,
+}
+
+impl<E: Pairing> UniversalParams<E> {
+	pub fn max_degree(&self) -> usize {
+		self.powers_of_g.len() - 1
+	}
+}
+
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VerifyingKey<E: Pairing> {
+	/// Size `n` of the constraint domain `H`, a power of two.
+	pub domain_h_size: u64,
+	/// Size `m` of the domain `K` of the non-zero matrix entries, a power of two.
+	pub domain_k_size: u64,
+	/// Number of instance variables, including the constant one.
+	pub num_instance_variables: u64,
+	/// Maximum degree `D` of the universal parameters the key was indexed with.
+	pub max_degree: u64,
+	/// Commitments to `row`, `col` and `val` of `A`, `B` and `C`, in this order.
+	pub index_commitments: [E::G1Affine; 9],
+	/// `[tau]G2` of the universal parameters.
+	pub tau_h: E::G2Affine,
+}
+
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Proof<E: Pairing> {
+	pub w: E::G1Affine,
+	pub z_a: E::G1Affine,
+	pub z_b: E::G1Affine,
+	pub t: E::G1Affine,
+	pub g_1: E::G1Affine,
+	pub g_1_shifted: E::G1Affine,
+	pub h_1: E::G1Affine,
+	pub g_2: E::G1Affine,
+	pub g_2_shifted: E::G1Affine,
+	pub h_2: E::G1Affine,
+	pub sigma_3: E::ScalarField,
+	pub g_3: E::G1Affine,
+	pub g_3_shifted: E::G1Affine,
+	pub h_3: E::G1Affine,
+	/// `w, z_A, z_B, t, g_1, h_1` at `beta_1`.
+	pub beta_1_evals: [E::ScalarField; 6],
+	/// `g_2, h_2` at `beta_2`.
+	pub beta_2_evals: [E::ScalarField; 2],
+	/// `g_3, h_3` and the nine index polynomials at `beta_3`.
+	pub beta_3_evals: [E::ScalarField; 11],
+	/// Batched KZG opening proofs at `beta_1`, `beta_2` and `beta_3`.
+	pub openings: [E::G1Affine; 3],
+}
+
+/// The verifier challenges of the protocol.
+pub(crate) struct Challenges<F> {
+	pub alpha: F,
+	pub eta: [F; 3],
+	pub beta_1: F,
+	pub beta_2: F,
+	pub beta_3: F,
+	pub xi: F,
+	pub r: F,
+}
+
+/// Starts the Fiat-Shamir transcript of a proof for `public_inputs` under `vk`.
+pub(crate) fn transcript<E: Pairing>(
+	vk: &VerifyingKey<E>,
+	public_inputs: &[E::ScalarField],
+) -> Transcript {
+	let mut transcript = Transcript::new(b"marlin");
+	transcript.append(b"marlin-vk", vk);
+	transcript.append(b"public-inputs", public_inputs);
+	transcript
+}
+
+impl<F: PrimeField> Challenges<F> {
+	pub(crate) fn derive<E: Pairing<ScalarField = F>>(
+		vk: &VerifyingKey<E>,
+		public_inputs: &[F],
+		proof: &Proof<E>,
+	) -> Self {
+		let mut transcript = transcript(vk, public_inputs);
+		transcript.append(b"first", &[proof.w, proof.z_a, proof.z_b]);
+		let alpha = transcript.challenge(b"alpha");
+		let eta = [b"eta_a", b"eta_b", b"eta_c"].map(|label| transcript.challenge(label));
+		transcript.append(b"second", &[proof.t, proof.g_1, proof.g_1_shifted, proof.h_1]);
+		let beta_1 = transcript.challenge(b"beta_1");
+		transcript.append(b"third", &[proof.g_2, proof.g_2_shifted, proof.h_2]);
+		let beta_2 = transcript.challenge(b"beta_2");
+		transcript.append(b"sigma_3", &proof.sigma_3);
+		transcript.append(b"fourth", &[proof.g_3, proof.g_3_shifted, proof.h_3]);
+		let beta_3 = transcript.challenge(b"beta_3");
+		transcript.append(b"beta_1_evals", &proof.beta_1_evals);
+		transcript.append(b"beta_2_evals", &proof.beta_2_evals);
+		transcript.append(b"beta_3_evals", &proof.beta_3_evals);
+		let xi = transcript.challenge(b"xi");
+		transcript.append(b"openings", &proof.openings);
+		let r = transcript.challenge(b"r");
+		Self { alpha, eta, beta_1, beta_2, beta_3, xi, r }
+	}
+}
+
+/// Degree of the polynomials the universal parameters must support for a circuit with the
+/// domain sizes `n` and `m`, or `None` if it overflows. Computed in `u64` so that native and
+/// wasm builds agree.
+pub(crate) fn required_degree(n: u64, m: u64) -> Option<u64> {
+	Some(n.checked_mul(2)?.max(m.checked_mul(6)?))
+}
+
+/// `(a^n - b^n) / (a - b)`, the bivariate polynomial `u_H` of the paper, or `n a^(n-1)` if
+/// `a = b`.
+fn vanishing_quotient<F: Field>(a: F, b: F, n: u64) -> F {
+	match (a - b).inverse() {
+		Some(inverse) => (a.pow([n]) - b.pow([n])) * inverse,
+		None => F::from(n) * a.pow([n - 1]),
+	}
+}
+
+/// Verifies a Marlin `proof` for `public_inputs`, which exclude the constant one.
+pub fn verify<E: Pairing>(
+	vk: &VerifyingKey<E>,
+	public_inputs: &[E::ScalarField],
+	proof: &Proof<E>,
+) -> Result<bool, Error> {
+	type F<E> = <E as Pairing>::ScalarField;
+	if public_inputs.len() as u64 + 1 != vk.num_instance_variables {
+		return Err(Error::PublicInputMismatch)
+	}
+	let n = vk.domain_h_size;
+	let m = vk.domain_k_size;
+	let n_x = vk.num_instance_variables.next_power_of_two();
+	let max_degree = vk.max_degree;
+	if n < 2 ||
+		m < 2 || n_x > n ||
+		required_degree(n, m).filter(|degree| *degree <= max_degree).is_none()
+	{
+		return Err(Error::InvalidVerifyingKey)
+	}
+	let omega_x = F::<E>::get_root_of_unity(n_x).ok_or(Error::InvalidVerifyingKey)?;
+	let Challenges { alpha, eta, beta_1, beta_2, beta_3, xi, r } =
+		Challenges::derive(vk, public_inputs, proof);
+	let one = F::<E>::one();
+
+	// The public input polynomial on `H_x` at `beta_1`, from the Lagrange basis
+	// `L_i(X) = omega_x^i (X^n_x - 1) / (n_x (X - omega_x^i))`.
+	let v_h_x = beta_1.pow([n_x]) - one;
+	let n_x_inv = F::<E>::from(n_x).inverse().ok_or(Error::InvalidVerifyingKey)?;
+	let mut x_hat = F::<E>::zero();
+	let mut omega_i = one;
+	for x in core::iter::once(&one).chain(public_inputs) {
+		let denominator = match (beta_1 - omega_i).inverse() {
+			Some(denominator) => denominator,
+			None => return Ok(false),
+		};
+		x_hat += *x * omega_i * v_h_x * n_x_inv * denominator;
+		omega_i *= omega_x;
+	}
+
+	let [w, z_a, z_b, t, g_1, h_1] = proof.beta_1_evals;
+	let [g_2, h_2] = proof.beta_2_evals;
+	let [g_3, h_3, index_evals @ ..] = proof.beta_3_evals;
+	let [eta_a, eta_b, eta_c] = eta;
+	let z = w * v_h_x + x_hat;
+	let v_h = |beta: F<E>| beta.pow([n]) - one;
+
+	// First sumcheck over `H`:
+	// `r(alpha, X) sum_M eta_M z_M(X) - t(X) z(X) = h_1(X) v_H(X) + X g_1(X)`.
+	let r_alpha_beta_1 = vanishing_quotient(alpha, beta_1, n);
+	let first = r_alpha_beta_1 * (eta_a * z_a + eta_b * z_b + eta_c * z_a * z_b) - t * z ==
+		h_1 * v_h(beta_1) + beta_1 * g_1;
+
+	// Second sumcheck over `H`:
+	// `r(alpha, X) sigma_3 = h_2(X) v_H(X) + X g_2(X) + t(beta_1) / n` at `beta_2`.
+	let n_inv = F::<E>::from(n).inverse().ok_or(Error::InvalidVerifyingKey)?;
+	let second = vanishing_quotient(alpha, beta_2, n) * proof.sigma_3 ==
+		h_2 * v_h(beta_2) + beta_2 * g_2 + t * n_inv;
+
+	// Third sumcheck over `K`: `a(X) - b(X) (X g_3(X) + sigma_3 / m) = h_3(X) v_K(X)`.
+	let v_h_betas = v_h(beta_2) * v_h(beta_1);
+	let denominators: Vec<F<E>> = (0..3)
+		.map(|i| (beta_2 - index_evals[3 * i]) * (beta_1 - index_evals[3 * i + 1]))
+		.collect();
+	let b = denominators.iter().product::<F<E>>();
+	let a = (0..3)
+		.map(|i| {
+			let others: F<E> = denominators
+				.iter()
+				.enumerate()
+				.filter(|(j, _)| *j != i)
+				.map(|(_, d)| *d)
+				.product();
+			eta[i] * v_h_betas * index_evals[3 * i + 2] * others
+		})
+		.sum::<F<E>>();
+	let m_inv = F::<E>::from(m).inverse().ok_or(Error::InvalidVerifyingKey)?;
+	let third = a - b * (beta_3 * g_3 + proof.sigma_3 * m_inv) == h_3 * (beta_3.pow([m]) - one);
+
+	if !(first && second && third) {
+		return Ok(false)
+	}
+
+	// The openings, with the shifted commitments evaluating to `X^(D - d) g(X)`.
+	let shift = |beta: F<E>, bound: u64| beta.pow([max_degree - bound]);
+	let [row_a, col_a, val_a, row_b, col_b, val_b, row_c, col_c, val_c] = vk.index_commitments;
+	let queries = [
+		(
+			beta_1,
+			[
+				(proof.w, w),
+				(proof.z_a, z_a),
+				(proof.z_b, z_b),
+				(proof.t, t),
+				(proof.g_1, g_1),
+				(proof.g_1_shifted, shift(beta_1, n - 2) * g_1),
+				(proof.h_1, h_1),
+			]
+			.to_vec(),
+		),
+		(
+			beta_2,
+			[(proof.g_2, g_2), (proof.g_2_shifted, shift(beta_2, n - 2) * g_2), (proof.h_2, h_2)]
+				.to_vec(),
+		),
+		(
+			beta_3,
+			[proof.g_3, proof.g_3_shifted, proof.h_3, row_a, col_a, val_a, row_b, col_b, val_b]
+				.into_iter()
+				.chain([row_c, col_c, val_c])
+				.zip(
+					[g_3, shift(beta_3, m - 2) * g_3, h_3]
+						.into_iter()
+						.chain(index_evals.iter().copied()),
+				)
+				.collect(),
+		),
+	];
+
+	// `e(sum r^j W_j, [tau]_2) = e(sum r^j (C_j - [y_j]G1 + beta_j W_j), [1]_2)` with
+	// `C_j = sum xi^i C_ji` and `y_j = sum xi^i y_ji`.
+	let mut bases = Vec::with_capacity(26);
+	let mut scalars = Vec::with_capacity(26);
+	let mut y = F::<E>::zero();
+	let mut r_j = one;
+	for ((beta, query), opening) in queries.iter().zip(proof.openings) {
+		let mut xi_i = r_j;
+		for (commitment, value) in query {
+			bases.push(*commitment);
+			scalars.push(xi_i);
+			y += xi_i * value;
+			xi_i *= xi;
+		}
+		bases.push(opening);
+		scalars.push(r_j * beta);
+		r_j *= r;
+	}
+	bases.push(E::G1Affine::generator());
+	scalars.push(-y);
+	let rhs = E::G1::msm(&bases, &scalars).expect("equal lengths; qed");
+	let lhs =
+		proof.openings[0].into_group() + proof.openings[1] * r + proof.openings[2] * r.square();
+
+	Ok(E::multi_pairing(
+		[lhs.into_affine(), (-rhs).into_affine()],
+		[vk.tau_h, E::G2Affine::generator()],
+	)
+	.is_zero())
+}
+
+/// Decodes the uncompressed `vk`, `public_inputs` and `proof` and verifies the proof. The
+/// verifying key is expected to be validated already.
+pub fn verify_serialized<E: Pairing>(
+	vk: &[u8],
+	public_inputs: &[u8],
+	proof: &[u8],
+) -> Result<bool, Error> {
+	let vk = VerifyingKey::<E>::deserialize_with_mode(vk, Compress::No, Validate::No)
+		.map_err(|_| Error::InvalidVerifyingKey)?;
+	let public_inputs =
+		Vec::<E::ScalarField>::deserialize_with_mode(public_inputs, Compress::No, Validate::Yes)
+			.map_err(|_| Error::PublicInputMismatch)?;
+	let proof = Proof::<E>::deserialize_with_mode(proof, Compress::No, Validate::Yes)
+		.map_err(|_| Error::InvalidProof)?;
+	verify(&vk, &public_inputs, &proof)
+}
+
+/// Checks that `tau_h` is a valid uncompressed `[tau]G2` point of `E`.
+pub fn validate_srs<E: Pairing>(tau_h: &[u8]) -> Result<(), Error> {
+	E::G2Affine::deserialize_with_mode(tau_h, Compress::No, Validate::Yes)
+		.map(|_| ())
+		.map_err(|_| Error::InvalidSrs)
+}
+
+/// Checks that `vk` is a valid uncompressed index verifying key for `E` indexed from the
+/// universal parameters whose validated, uncompressed `[tau]G2` point is `tau_h`, with at most
+/// `MAX_PUBLIC_INPUTS` public inputs.
+pub fn validate_verifying_key<E: Pairing>(vk: &[u8], tau_h: &[u8]) -> Result<(), Error> {
+	let vk = VerifyingKey::<E>::deserialize_with_mode(vk, Compress::No, Validate::Yes)
+		.map_err(|_| Error::InvalidVerifyingKey)?;
+	let tau_h = E::G2Affine::deserialize_with_mode(tau_h, Compress::No, Validate::No)
+		.map_err(|_| Error::InvalidSrs)?;
+	if vk.tau_h != tau_h {
+		return Err(Error::SrsMismatch)
+	}
+	let (n, m) = (vk.domain_h_size, vk.domain_k_size);
+	if !n.is_power_of_two() ||
+		!m.is_power_of_two() ||
+		n < 2 || m < 2 ||
+		E::ScalarField::get_root_of_unity(n.max(m)).is_none() ||
+		vk.num_instance_variables == 0 ||
+		vk.num_instance_variables > n ||
+		vk.num_instance_variables > MAX_PUBLIC_INPUTS + 1 ||
+		required_degree(n, m).filter(|degree| *degree <= vk.max_degree).is_none()
+	{
+		return Err(Error::InvalidVerifyingKey)
+	}
+	Ok(())
+}
+
+/// Marlin over the pairing `E`. The `SNARK` and `UniversalSetupSNARK` implementations, which
+/// set up and prove, are only built for tests and benchmarks.
+pub struct Marlin<E: Pairing>(PhantomData<E>);
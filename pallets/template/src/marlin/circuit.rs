@@ -0,0 +1,98 @@
+//! R1CS circuits for tests and benchmarks.
+
+use ark_ff::PrimeField;
+use ark_relations::{
+	lc,
+	r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError, Variable},
+};
+use ark_std::vec::Vec;
+
+/// `x^3 + x + 5 = y` with public `y`, repeated `repetitions` times with the same `x`. Without
+/// `x` the circuit can only be indexed.
+#[derive(Clone, Copy)]
+pub struct CubeCircuit<F> {
+	pub x: Option<F>,
+	pub repetitions: usize,
+}
+
+impl<F: PrimeField> CubeCircuit<F> {
+	pub fn new(x: u64, repetitions: usize) -> Self {
+		Self { x: Some(F::from(x)), repetitions }
+	}
+
+	pub fn public_inputs(&self) -> Vec<F> {
+		self.x.map(|x| x * x * x + x + F::from(5u64)).into_iter().collect()
+	}
+}
+
+impl<F: PrimeField> ConstraintSynthesizer<F> for CubeCircuit<F> {
+	fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<(), SynthesisError> {
+		let y = cs.new_input_variable(|| {
+			self.public_inputs().pop().ok_or(SynthesisError::AssignmentMissing)
+		})?;
+		let x_value = self.x;
+		let x = cs.new_witness_variable(|| x_value.ok_or(SynthesisError::AssignmentMissing))?;
+		for _ in 0..self.repetitions.max(1) {
+			let x_2 = cs.new_witness_variable(|| {
+				x_value.map(|x| x * x).ok_or(SynthesisError::AssignmentMissing)
+			})?;
+			let x_3 = cs.new_witness_variable(|| {
+				x_value.map(|x| x * x * x).ok_or(SynthesisError::AssignmentMissing)
+			})?;
+			cs.enforce_constraint(lc!() + x, lc!() + x, lc!() + x_2)?;
+			cs.enforce_constraint(lc!() + x_2, lc!() + x, lc!() + x_3)?;
+			cs.enforce_constraint(
+				lc!() + x_3 + x + (F::from(5u64), Variable::One),
+				lc!() + Variable::One,
+				lc!() + y,
+			)?;
+		}
+		Ok(())
+	}
+}
+
+/// `CubeCircuit` with its public `y` repeated as `num_public_inputs` public inputs.
+#[derive(Clone, Copy)]
+pub struct RepeatedInputCircuit<F> {
+	pub x: Option<F>,
+	pub num_public_inputs: usize,
+}
+
+impl<F: PrimeField> RepeatedInputCircuit<F> {
+	pub fn new(x: u64, num_public_inputs: usize) -> Self {
+		Self { x: Some(F::from(x)), num_public_inputs }
+	}
+
+	pub fn public_inputs(&self) -> Vec<F> {
+		CubeCircuit { x: self.x, repetitions: 1 }
+			.public_inputs()
+			.repeat(self.num_public_inputs.max(1))
+	}
+}
+
+impl<F: PrimeField> ConstraintSynthesizer<F> for RepeatedInputCircuit<F> {
+	fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<(), SynthesisError> {
+		let y_value = self.public_inputs().pop();
+		let ys = (0..self.num_public_inputs.max(1))
+			.map(|_| cs.new_input_variable(|| y_value.ok_or(SynthesisError::AssignmentMissing)))
+			.collect::<Result<Vec<_>, _>>()?;
+		let x_value = self.x;
+		let x = cs.new_witness_variable(|| x_value.ok_or(SynthesisError::AssignmentMissing))?;
+		let x_2 = cs.new_witness_variable(|| {
+			x_value.map(|x| x * x).ok_or(SynthesisError::AssignmentMissing)
+		})?;
+		let x_3 = cs.new_witness_variable(|| {
+			x_value.map(|x| x * x * x).ok_or(SynthesisError::AssignmentMissing)
+		})?;
+		cs.enforce_constraint(lc!() + x, lc!() + x, lc!() + x_2)?;
+		cs.enforce_constraint(lc!() + x_2, lc!() + x, lc!() + x_3)?;
+		for y in ys {
+			cs.enforce_constraint(
+				lc!() + x_3 + x + (F::from(5u64), Variable::One),
+				lc!() + Variable::One,
+				lc!() + y,
+			)?;
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,489 @@
+//! Indexer and prover of Marlin.
+//!
+//! Variables are placed on the constraint domain `H` with the instance on the subgroup `H_x`
+//! and the witness on the remaining elements in increasing order. Proofs are not blinded and
+//! therefore not zero knowledge.
+
+use super::{
+	required_degree, transcript, verify, Error, Marlin, Proof, UniversalParams, VerifyingKey,
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, One, PrimeField, UniformRand, Zero};
+use ark_poly::{
+	univariate::DensePolynomial, DenseUVPolynomial, EvaluationDomain, Polynomial,
+	Radix2EvaluationDomain,
+};
+use ark_relations::r1cs::{
+	ConstraintSynthesizer, ConstraintSystem, Matrix, OptimizationGoal, SynthesisMode,
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_snark::{UniversalSetupIndexError, UniversalSetupSNARK, SNARK};
+use ark_std::{
+	rand::{CryptoRng, RngCore},
+	vec,
+	vec::Vec,
+};
+
+type Poly<F> = DensePolynomial<F>;
+
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProvingKey<E: Pairing> {
+	pub vk: VerifyingKey<E>,
+	pub powers_of_g: Vec<E::G1Affine>,
+	pub num_witness_variables: u64,
+	/// The constraint matrices `A`, `B` and `C`.
+	matrices: [Matrix<E::ScalarField>; 3],
+	/// `row`, `col` and `val` of `A`, `B` and `C`, in the order of the index commitments.
+	index_polynomials: [Poly<E::ScalarField>; 9],
+}
+
+/// The constraint matrices of a circuit and the domain sizes they need.
+pub(crate) struct Index<F: Field> {
+	matrices: [Matrix<F>; 3],
+	num_instance_variables: usize,
+	num_witness_variables: usize,
+	domain_h_size: usize,
+	domain_k_size: usize,
+}
+
+impl<F: PrimeField> Index<F> {
+	pub(crate) fn new<C: ConstraintSynthesizer<F>>(circuit: C) -> Result<Self, Error> {
+		let cs = ConstraintSystem::new_ref();
+		cs.set_optimization_goal(OptimizationGoal::Constraints);
+		cs.set_mode(SynthesisMode::Setup);
+		circuit.generate_constraints(cs.clone())?;
+		cs.finalize();
+		let matrices = cs.to_matrices().ok_or(Error::Synthesis)?;
+
+		let num_x = matrices.num_instance_variables.next_power_of_two();
+		let domain_h_size = matrices
+			.num_constraints
+			.max(num_x + matrices.num_witness_variables)
+			.max(2)
+			.next_power_of_two();
+		let domain_k_size = matrices
+			.a_num_non_zero
+			.max(matrices.b_num_non_zero)
+			.max(matrices.c_num_non_zero)
+			.max(2)
+			.next_power_of_two();
+		if F::get_root_of_unity(domain_h_size.max(domain_k_size) as u64).is_none() {
+			return Err(Error::Synthesis)
+		}
+		Ok(Self {
+			matrices: [matrices.a, matrices.b, matrices.c],
+			num_instance_variables: matrices.num_instance_variables,
+			num_witness_variables: matrices.num_witness_variables,
+			domain_h_size,
+			domain_k_size,
+		})
+	}
+
+	pub(crate) fn required_degree(&self) -> usize {
+		required_degree(self.domain_h_size as u64, self.domain_k_size as u64)
+			.expect("domains of indexed circuits are small; qed") as usize
+	}
+}
+
+/// Maps the variables to their positions in `H`.
+struct Positions {
+	num_instance_variables: usize,
+	stride: usize,
+}
+
+impl Positions {
+	fn new(num_instance_variables: usize, domain_h_size: usize) -> Self {
+		let stride = domain_h_size / num_instance_variables.next_power_of_two();
+		Self { num_instance_variables, stride }
+	}
+
+	fn get(&self, variable: usize) -> usize {
+		if variable < self.num_instance_variables {
+			variable * self.stride
+		} else {
+			let witness = variable - self.num_instance_variables;
+			witness / (self.stride - 1) * self.stride + witness % (self.stride - 1) + 1
+		}
+	}
+}
+
+fn domain<F: PrimeField>(size: u64) -> Radix2EvaluationDomain<F> {
+	Radix2EvaluationDomain::new(size as usize).expect("domain sizes are checked; qed")
+}
+
+fn interpolate<F: PrimeField>(domain: &Radix2EvaluationDomain<F>, mut evals: Vec<F>) -> Poly<F> {
+	evals.resize(domain.size(), F::zero());
+	Poly::from_coefficients_vec(domain.ifft(&evals))
+}
+
+fn constant<F: Field>(value: F) -> Poly<F> {
+	Poly::from_coefficients_vec(vec![value])
+}
+
+/// `(p(X) - p(0)) / X`.
+fn shift_down<F: Field>(p: &Poly<F>) -> Poly<F> {
+	Poly::from_coefficients_slice(p.coeffs.get(1..).unwrap_or_default())
+}
+
+/// `X^shift p(X)`.
+fn shift_up<F: Field>(p: &Poly<F>, shift: usize) -> Poly<F> {
+	let mut coeffs = vec![F::zero(); shift];
+	coeffs.extend_from_slice(&p.coeffs);
+	Poly::from_coefficients_vec(coeffs)
+}
+
+/// `(p(X) - p(point)) / (X - point)`.
+fn divide_by_linear<F: Field>(p: &Poly<F>, point: F) -> Poly<F> {
+	let mut quotient = vec![F::zero(); p.coeffs.len().saturating_sub(1)];
+	let mut carry = F::zero();
+	for i in (1..p.coeffs.len()).rev() {
+		carry = p.coeffs[i] + carry * point;
+		quotient[i - 1] = carry;
+	}
+	Poly::from_coefficients_vec(quotient)
+}
+
+fn divide_by_vanishing<F: PrimeField>(
+	p: &Poly<F>,
+	domain: Radix2EvaluationDomain<F>,
+) -> (Poly<F>, Poly<F>) {
+	p.divide_by_vanishing_poly(domain).expect("radix-2 domains; qed")
+}
+
+fn commit<E: Pairing>(powers: &[E::G1Affine], p: &Poly<E::ScalarField>) -> E::G1Affine {
+	E::G1::msm_unchecked(&powers[..p.coeffs.len()], &p.coeffs).into_affine()
+}
+
+/// Derives the index polynomials of the circuit and commits to them.
+pub(crate) fn index<E: Pairing>(
+	pp: &UniversalParams<E>,
+	index: Index<E::ScalarField>,
+) -> (ProvingKey<E>, VerifyingKey<E>) {
+	type F<E> = <E as Pairing>::ScalarField;
+	let n = index.domain_h_size;
+	let domain_h = domain::<F<E>>(n as u64);
+	let domain_k = domain::<F<E>>(index.domain_k_size as u64);
+	let positions = Positions::new(index.num_instance_variables, n);
+	let n_inv_square = F::<E>::from(n as u64).square().inverse().expect("n is non-zero; qed");
+
+	// `val(k) = M[r][c] / (u_H(omega^r, omega^r) u_H(omega^c', omega^c'))` with
+	// `u_H(a, a) = n a^(n - 1) = n / a` for the position `c'` of the variable `c`.
+	let mut index_polynomials = Vec::with_capacity(9);
+	for matrix in &index.matrices {
+		let mut row = Vec::with_capacity(domain_k.size());
+		let mut col = Vec::with_capacity(domain_k.size());
+		let mut val = Vec::with_capacity(domain_k.size());
+		for (r, entries) in matrix.iter().enumerate() {
+			let row_element = domain_h.element(r);
+			for (value, c) in entries {
+				let col_element = domain_h.element(positions.get(*c));
+				row.push(row_element);
+				col.push(col_element);
+				val.push(*value * row_element * col_element * n_inv_square);
+			}
+		}
+		row.resize(domain_k.size(), F::<E>::one());
+		col.resize(domain_k.size(), F::<E>::one());
+		index_polynomials.push(interpolate(&domain_k, row));
+		index_polynomials.push(interpolate(&domain_k, col));
+		index_polynomials.push(interpolate(&domain_k, val));
+	}
+	let index_polynomials: [Poly<F<E>>; 9] =
+		index_polynomials.try_into().expect("three polynomials per matrix; qed");
+
+	let vk = VerifyingKey {
+		domain_h_size: n as u64,
+		domain_k_size: index.domain_k_size as u64,
+		num_instance_variables: index.num_instance_variables as u64,
+		max_degree: pp.max_degree() as u64,
+		index_commitments: index_polynomials.clone().map(|p| commit::<E>(&pp.powers_of_g, &p)),
+		tau_h: pp.tau_h,
+	};
+	let pk = ProvingKey {
+		vk: vk.clone(),
+		powers_of_g: pp.powers_of_g.clone(),
+		num_witness_variables: index.num_witness_variables as u64,
+		matrices: index.matrices,
+		index_polynomials,
+	};
+	(pk, vk)
+}
+
+/// Proves that the assignment `circuit` synthesizes to satisfies the indexed circuit.
+pub(crate) fn prove<E: Pairing, C: ConstraintSynthesizer<E::ScalarField>>(
+	pk: &ProvingKey<E>,
+	circuit: C,
+) -> Result<Proof<E>, Error> {
+	type F<E> = <E as Pairing>::ScalarField;
+	let cs = ConstraintSystem::new_ref();
+	cs.set_optimization_goal(OptimizationGoal::Constraints);
+	cs.set_mode(SynthesisMode::Prove { construct_matrices: false });
+	circuit.generate_constraints(cs.clone())?;
+	cs.finalize();
+	let (instance, witness) = {
+		let cs = cs.borrow().ok_or(Error::Synthesis)?;
+		(cs.instance_assignment.clone(), cs.witness_assignment.clone())
+	};
+	let vk = &pk.vk;
+	if instance.len() as u64 != vk.num_instance_variables ||
+		witness.len() as u64 != pk.num_witness_variables
+	{
+		return Err(Error::PublicInputMismatch)
+	}
+
+	let one = F::<E>::one();
+	let zero = F::<E>::zero();
+	let n = vk.domain_h_size;
+	let m = vk.domain_k_size;
+	let n_x = vk.num_instance_variables.next_power_of_two();
+	let domain_h = domain::<F<E>>(n);
+	let domain_k = domain::<F<E>>(m);
+	let domain_x = domain::<F<E>>(n_x);
+	let positions = Positions::new(instance.len(), n as usize);
+	let powers = &pk.powers_of_g;
+	let max_degree = vk.max_degree as usize;
+	let z: Vec<F<E>> = instance.iter().chain(&witness).copied().collect();
+	let mut transcript = transcript(vk, &instance[1..]);
+
+	// Round 1: the witness and `z_A`, `z_B`.
+	let mut z_evals = vec![zero; n as usize];
+	for (variable, value) in z.iter().enumerate() {
+		z_evals[positions.get(variable)] = *value;
+	}
+	let z_poly = interpolate(&domain_h, z_evals);
+	let x_poly = interpolate(&domain_x, instance.clone());
+	let (w_poly, _) = divide_by_vanishing(&(&z_poly - &x_poly), domain_x);
+	let [z_a_poly, z_b_poly] = [0, 1].map(|i| {
+		let evals = pk.matrices[i]
+			.iter()
+			.map(|row| row.iter().map(|(value, c)| *value * z[*c]).sum())
+			.collect();
+		interpolate(&domain_h, evals)
+	});
+	let first = [&w_poly, &z_a_poly, &z_b_poly].map(|p| commit::<E>(powers, p));
+	transcript.append(b"first", &first);
+	let alpha: F<E> = transcript.challenge(b"alpha");
+	let eta: [F<E>; 3] = [b"eta_a", b"eta_b", b"eta_c"].map(|label| transcript.challenge(label));
+
+	// Round 2: the first sumcheck with `t(X) = sum_M eta_M r_M(alpha, X)`.
+	let v_h_alpha = domain_h.evaluate_vanishing_polynomial(alpha);
+	if v_h_alpha.is_zero() {
+		return Err(Error::DegenerateChallenge)
+	}
+	let mut t_evals = vec![zero; n as usize];
+	for (matrix, eta) in pk.matrices.iter().zip(eta) {
+		for (r, entries) in matrix.iter().enumerate() {
+			let u_alpha = v_h_alpha *
+				(alpha - domain_h.element(r)).inverse().expect("alpha is not in H; qed");
+			for (value, c) in entries {
+				t_evals[positions.get(*c)] += eta * u_alpha * value;
+			}
+		}
+	}
+	let t_poly = interpolate(&domain_h, t_evals);
+	// `r(alpha, X) = (alpha^n - X^n) / (alpha - X) = sum alpha^(n - 1 - i) X^i`.
+	let mut r_alpha = vec![zero; n as usize];
+	let mut power = one;
+	for coeff in r_alpha.iter_mut().rev() {
+		*coeff = power;
+		power *= alpha;
+	}
+	let r_alpha = Poly::from_coefficients_vec(r_alpha);
+	let z_c_poly = &z_a_poly * &z_b_poly;
+	let mut combined = &z_a_poly * eta[0];
+	combined += (eta[1], &z_b_poly);
+	combined += (eta[2], &z_c_poly);
+	let q_1 = &(&r_alpha * &combined) - &(&t_poly * &z_poly);
+	let (h_1_poly, remainder) = divide_by_vanishing(&q_1, domain_h);
+	let g_1_poly = shift_down(&remainder);
+	let g_1_shifted_poly = shift_up(&g_1_poly, max_degree - (n as usize - 2));
+	let second = [&t_poly, &g_1_poly, &g_1_shifted_poly, &h_1_poly].map(|p| commit::<E>(powers, p));
+	transcript.append(b"second", &second);
+	let beta_1: F<E> = transcript.challenge(b"beta_1");
+
+	// Round 3: the second sumcheck of `r(alpha, X) sum_M eta_M M(X, beta_1)`.
+	if domain_h.evaluate_vanishing_polynomial(beta_1).is_zero() {
+		return Err(Error::DegenerateChallenge)
+	}
+	let lagrange_beta_1 = domain_h.evaluate_all_lagrange_coefficients(beta_1);
+	let mut m_evals = vec![zero; n as usize];
+	for (matrix, eta) in pk.matrices.iter().zip(eta) {
+		for (r, entries) in matrix.iter().enumerate() {
+			for (value, c) in entries {
+				m_evals[r] += eta * value * lagrange_beta_1[positions.get(*c)];
+			}
+		}
+	}
+	let q_2 = &r_alpha * &interpolate(&domain_h, m_evals);
+	let (h_2_poly, remainder) = divide_by_vanishing(&q_2, domain_h);
+	let g_2_poly = shift_down(&remainder);
+	let g_2_shifted_poly = shift_up(&g_2_poly, max_degree - (n as usize - 2));
+	let third = [&g_2_poly, &g_2_shifted_poly, &h_2_poly].map(|p| commit::<E>(powers, p));
+	transcript.append(b"third", &third);
+	let beta_2: F<E> = transcript.challenge(b"beta_2");
+
+	// Round 4: the third sumcheck of `a(X) / b(X)` over `K`.
+	let v_h_betas = domain_h.evaluate_vanishing_polynomial(beta_2) *
+		domain_h.evaluate_vanishing_polynomial(beta_1);
+	let index = &pk.index_polynomials;
+	let denominators: Vec<Poly<F<E>>> = (0..3)
+		.map(|i| &(&constant(beta_2) - &index[3 * i]) * &(&constant(beta_1) - &index[3 * i + 1]))
+		.collect();
+	let b_poly = &(&denominators[0] * &denominators[1]) * &denominators[2];
+	let mut a_poly = Poly::zero();
+	for i in 0..3 {
+		let others = &denominators[(i + 1) % 3] * &denominators[(i + 2) % 3];
+		a_poly += (eta[i] * v_h_betas, &(&index[3 * i + 2] * &others));
+	}
+	// `f = a / b` on `K`, from the evaluations of the index polynomials of degree `< m`.
+	let index_evals: Vec<Vec<F<E>>> = index.iter().map(|p| domain_k.fft(&p.coeffs)).collect();
+	let f_evals = (0..m as usize)
+		.map(|k| {
+			(0..3).try_fold(zero, |f, i| {
+				let denominator =
+					(beta_2 - index_evals[3 * i][k]) * (beta_1 - index_evals[3 * i + 1][k]);
+				let inverse = denominator.inverse().ok_or(Error::DegenerateChallenge)?;
+				Ok(f + eta[i] * v_h_betas * index_evals[3 * i + 2][k] * inverse)
+			})
+		})
+		.collect::<Result<Vec<_>, Error>>()?;
+	let f_poly = interpolate(&domain_k, f_evals);
+	let sigma_3 = f_poly.coeffs.first().copied().unwrap_or_default() * F::<E>::from(m);
+	let g_3_poly = shift_down(&f_poly);
+	let g_3_shifted_poly = shift_up(&g_3_poly, max_degree - (m as usize - 2));
+	let (h_3_poly, remainder) = divide_by_vanishing(&(&a_poly - &(&b_poly * &f_poly)), domain_k);
+	debug_assert!(remainder.is_zero());
+	let fourth = [&g_3_poly, &g_3_shifted_poly, &h_3_poly].map(|p| commit::<E>(powers, p));
+	transcript.append(b"sigma_3", &sigma_3);
+	transcript.append(b"fourth", &fourth);
+	let beta_3: F<E> = transcript.challenge(b"beta_3");
+
+	// Round 5: evaluations and batched openings.
+	let beta_1_polys =
+		[&w_poly, &z_a_poly, &z_b_poly, &t_poly, &g_1_poly, &g_1_shifted_poly, &h_1_poly];
+	let beta_2_polys = [&g_2_poly, &g_2_shifted_poly, &h_2_poly];
+	let mut beta_3_polys = vec![&g_3_poly, &g_3_shifted_poly, &h_3_poly];
+	beta_3_polys.extend(index.iter());
+	let evaluate = |p: &Poly<F<E>>, point| p.evaluate(&point);
+	let beta_1_evals = [0, 1, 2, 3, 4, 6].map(|i| evaluate(beta_1_polys[i], beta_1));
+	let beta_2_evals = [0, 2].map(|i| evaluate(beta_2_polys[i], beta_2));
+	let beta_3_evals: [F<E>; 11] =
+		[0, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11].map(|i| evaluate(beta_3_polys[i], beta_3));
+	transcript.append(b"beta_1_evals", &beta_1_evals);
+	transcript.append(b"beta_2_evals", &beta_2_evals);
+	transcript.append(b"beta_3_evals", &beta_3_evals);
+	let xi: F<E> = transcript.challenge(b"xi");
+	let open = |polys: &[&Poly<F<E>>], point| {
+		let mut combined = Poly::zero();
+		let mut xi_i = one;
+		for p in polys {
+			combined += (xi_i, *p);
+			xi_i *= xi;
+		}
+		commit::<E>(powers, &divide_by_linear(&combined, point))
+	};
+	let openings =
+		[open(&beta_1_polys, beta_1), open(&beta_2_polys, beta_2), open(&beta_3_polys, beta_3)];
+
+	Ok(Proof {
+		w: first[0],
+		z_a: first[1],
+		z_b: first[2],
+		t: second[0],
+		g_1: second[1],
+		g_1_shifted: second[2],
+		h_1: second[3],
+		g_2: third[0],
+		g_2_shifted: third[1],
+		h_2: third[2],
+		sigma_3,
+		g_3: fourth[0],
+		g_3_shifted: fourth[1],
+		h_3: fourth[2],
+		beta_1_evals,
+		beta_2_evals,
+		beta_3_evals,
+		openings,
+	})
+}
+
+impl<E: Pairing> SNARK<E::ScalarField> for Marlin<E> {
+	type ProvingKey = ProvingKey<E>;
+	type VerifyingKey = VerifyingKey<E>;
+	type Proof = Proof<E>;
+	type ProcessedVerifyingKey = VerifyingKey<E>;
+	type Error = Error;
+
+	/// Samples universal parameters of exactly the size the circuit needs and indexes it.
+	fn circuit_specific_setup<C: ConstraintSynthesizer<E::ScalarField>, R: RngCore + CryptoRng>(
+		circuit: C,
+		rng: &mut R,
+	) -> Result<(Self::ProvingKey, Self::VerifyingKey), Self::Error> {
+		let index = Index::new(circuit)?;
+		let pp = Self::universal_setup(&index.required_degree(), rng)?;
+		Ok(self::index(&pp, index))
+	}
+
+	/// Proves that `circuit` is satisfied. The proof is not zero knowledge, `rng` is unused.
+	fn prove<C: ConstraintSynthesizer<E::ScalarField>, R: RngCore + CryptoRng>(
+		circuit_pk: &Self::ProvingKey,
+		circuit: C,
+		_rng: &mut R,
+	) -> Result<Self::Proof, Self::Error> {
+		self::prove(circuit_pk, circuit)
+	}
+
+	fn process_vk(
+		circuit_vk: &Self::VerifyingKey,
+	) -> Result<Self::ProcessedVerifyingKey, Self::Error> {
+		Ok(circuit_vk.clone())
+	}
+
+	fn verify_with_processed_vk(
+		circuit_pvk: &Self::ProcessedVerifyingKey,
+		public_input: &[E::ScalarField],
+		proof: &Self::Proof,
+	) -> Result<bool, Self::Error> {
+		verify(circuit_pvk, public_input, proof)
+	}
+}
+
+impl<E: Pairing> UniversalSetupSNARK<E::ScalarField> for Marlin<E> {
+	/// The maximum polynomial degree `D`.
+	type ComputationBound = usize;
+	type PublicParameters = UniversalParams<E>;
+
+	fn universal_setup<R: RngCore + CryptoRng>(
+		compute_bound: &Self::ComputationBound,
+		rng: &mut R,
+	) -> Result<Self::PublicParameters, Self::Error> {
+		let tau = E::ScalarField::rand(rng);
+		let g = E::G1Affine::generator();
+		let h = E::G2Affine::generator();
+		let mut powers_of_g = Vec::with_capacity(compute_bound + 1);
+		let mut power = E::ScalarField::one();
+		for _ in 0..=*compute_bound {
+			powers_of_g.push(g * power);
+			power *= tau;
+		}
+		Ok(UniversalParams {
+			powers_of_g: E::G1::normalize_batch(&powers_of_g),
+			tau_h: (h * tau).into_affine(),
+		})
+	}
+
+	fn index<C: ConstraintSynthesizer<E::ScalarField>, R: RngCore + CryptoRng>(
+		pp: &Self::PublicParameters,
+		circuit: C,
+		_rng: &mut R,
+	) -> Result<
+		(Self::ProvingKey, Self::VerifyingKey),
+		UniversalSetupIndexError<Self::ComputationBound, Self::Error>,
+	> {
+		let index = Index::new(circuit).map_err(UniversalSetupIndexError::Other)?;
+		if index.required_degree() > pp.max_degree() {
+			return Err(UniversalSetupIndexError::NeedLargerBound(index.required_degree()))
+		}
+		Ok(self::index(pp, index))
+	}
+}
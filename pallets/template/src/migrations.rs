@@ -0,0 +1,64 @@
+//! Migrations of the pallet's storage between its `StorageVersion`s.
+
+/// Version 1 records a review status for every registered Groth16 verifying key. Keys
+/// registered at version 0 may have none and read as `Pending`. Storing it explicitly lets
+/// clients list the keys with their status from `Groth16VerifyingKeyStatuses` alone.
+pub mod v1 {
+	use crate::{
+		Config, Groth16VerifyingKeyHashes, Groth16VerifyingKeyStatuses, Pallet, VerifyingKeyStatus,
+	};
+	use core::marker::PhantomData;
+	use frame_support::{
+		traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		weights::Weight,
+	};
+	#[cfg(feature = "try-runtime")]
+	use {
+		ark_std::vec::Vec,
+		codec::{Decode, Encode},
+		frame_support::ensure,
+	};
+
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 0 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut keys = 0;
+			for id in Groth16VerifyingKeyHashes::<T>::iter_keys() {
+				if !Groth16VerifyingKeyStatuses::<T>::contains_key(id) {
+					Groth16VerifyingKeyStatuses::<T>::insert(id, VerifyingKeyStatus::Pending);
+				}
+				keys += 1;
+			}
+			StorageVersion::new(1).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(2 * keys + 1, keys + 1)
+		}
+
+		/// Returns the number of registered keys.
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			let keys = Groth16VerifyingKeyHashes::<T>::iter_keys().count() as u32;
+			Ok(keys.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let keys = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+			ensure!(Pallet::<T>::on_chain_storage_version() == 1, "storage version not updated");
+			ensure!(
+				Groth16VerifyingKeyHashes::<T>::iter_keys().count() as u32 == keys,
+				"registered keys changed"
+			);
+			ensure!(
+				Groth16VerifyingKeyHashes::<T>::iter_keys()
+					.all(Groth16VerifyingKeyStatuses::<T>::contains_key),
+				"registered key without a status"
+			);
+			Ok(())
+		}
+	}
+}
@@ -1,12 +1,19 @@
 use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64},
+};
+use frame_system::offchain::{
+	AppCrypto, CreateSignedTransaction, SendTransactionTypes, SigningTypes,
+};
 use sp_core::H256;
 use sp_runtime::{
-	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	testing::{Header, TestSignature, TestXt, UintAuthorityId},
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup},
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
 // Configure a mock runtime to test the pallet.
@@ -17,6 +24,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system,
+		Balances: pallet_balances,
 		TemplateModule: pallet_template,
 	}
 );
@@ -39,7 +47,7 @@ impl frame_system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -48,11 +56,96 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type HoldIdentifier = ();
+	type MaxHolds = ();
+}
+
+impl SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl<LocalCall> SendTransactionTypes<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> CreateSignedTransaction<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		_public: UintAuthorityId,
+		_account: u64,
+		nonce: u64,
+	) -> Option<(RuntimeCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+parameter_types! {
+	pub static SignedResults: bool = false;
+	pub static VerifiedProofs: Vec<(u64, u32, Vec<u8>)> = vec![];
+}
+
+/// Records the proofs reported to `Config::OnProofVerified` in `VerifiedProofs`.
+pub struct RecordVerifiedProofs;
+
+impl pallet_template::OnProofVerified<u64> for RecordVerifiedProofs {
+	fn on_proof_verified(who: &u64, vk_id: u32, public_inputs: &[u8]) {
+		VerifiedProofs::mutate(|proofs| proofs.push((*who, vk_id, public_inputs.to_vec())));
+	}
+}
+
+/// Signs offchain verification results with the keys set by `UintAuthorityId::set_all_keys`.
+pub struct TestAuthId;
+
+impl AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+}
+
 impl pallet_template::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type PreparedInputsLifetime = ConstU64<10>;
+	type AuthorityId = TestAuthId;
+	type SignedResults = SignedResults;
+	type MaxOffchainVerifications = ConstU32<4>;
+	type UnsignedPriority = ConstU64<{ 1 << 20 }>;
+	type OnProofVerified = RecordVerifiedProofs;
+	type AdminOrigin = frame_system::EnsureRoot<u64>;
+	type CircuitGracePeriod = ConstU64<5>;
+	type Currency = Balances;
+	type VerifyingKeyDeposit = ConstU64<100>;
+	type VerificationRequestDeposit = ConstU64<10>;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 1_000), (2, 1_000)] }
+		.assimilate_storage(&mut storage)
+		.unwrap();
+	let mut ext: sp_io::TestExternalities = storage.into();
+	// Events are not deposited in the genesis block.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
 }
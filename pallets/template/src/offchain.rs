@@ -0,0 +1,123 @@
+//! Offchain verification of queued Groth16 proofs.
+//!
+//! Requests queued with `groth16_request_verification` are verified by the offchain worker with
+//! the host accelerated BLS12-381 curve, and the results come back in signed transactions or in
+//! unsigned transactions with a signed payload, depending on `Config::SignedResults`. Only the
+//! accounts in `ResultSubmitters` are trusted with results.
+
+use crate::{bls12_381, Call, Config, Groth16PreparedVerifyingKeys, Pallet, VerificationRequests};
+use codec::{Decode, Encode};
+use frame_system::offchain::{
+	SendSignedTransaction, SendUnsignedTransaction, SignedPayload, Signer,
+};
+use scale_info::TypeInfo;
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::{offchain::storage::StorageValueRef, RuntimeDebug};
+
+/// The key type of the keys offchain workers sign verification results with.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"zkpv");
+
+/// The most accounts trusted with offchain verification results.
+pub const MAX_RESULT_SUBMITTERS: u32 = 16;
+
+/// The number of blocks after which the worker submits the result of a still queued request
+/// again.
+const RESUBMIT_INTERVAL: u32 = 5;
+
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	/// Signs verification results with the sr25519 keys of `KEY_TYPE` for runtimes with
+	/// `MultiSignature` accounts.
+	pub struct OffchainAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for OffchainAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+/// The result of a queued request, signed by the worker that verified it.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct VerificationResultPayload<Public> {
+	pub request_id: u32,
+	pub valid: bool,
+	pub public: Public,
+}
+
+impl<T: frame_system::offchain::SigningTypes> SignedPayload<T>
+	for VerificationResultPayload<T::Public>
+{
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Verifies up to `MaxOffchainVerifications` queued requests and submits their results.
+	/// Requests whose result is still on its way do not count, and a failed submission only
+	/// leaves its request for the next block.
+	pub(crate) fn process_verification_requests(now: T::BlockNumber) -> Result<(), &'static str> {
+		let signer = Signer::<T, T::AuthorityId>::any_account();
+		if !signer.can_sign() {
+			return Err("no local key to sign verification results with")
+		}
+
+		let max = T::MaxOffchainVerifications::get();
+		let mut processed = 0;
+		let mut failed = false;
+		for (request_id, (_, vk_id, public_inputs, proof)) in VerificationRequests::<T>::iter() {
+			if processed >= max {
+				break
+			}
+			// Skip requests whose result is probably still on its way.
+			let sent = StorageValueRef::persistent(&(b"pallet-template/sent", request_id).encode());
+			if let Ok(Some(at)) = sent.get::<T::BlockNumber>() {
+				if now < at + RESUBMIT_INTERVAL.into() {
+					continue
+				}
+			}
+
+			processed += 1;
+			let valid = Groth16PreparedVerifyingKeys::<T>::get(vk_id).map_or(false, |pvk| {
+				bls12_381::verify_prepared_groth16(&pvk, &public_inputs, &proof) == Ok(true)
+			});
+			let submitted = if T::SignedResults::get() {
+				signer
+					.send_signed_transaction(|_| Call::submit_verification_result {
+						request_id,
+						valid,
+					})
+					.map(|(_, result)| result)
+			} else {
+				signer
+					.send_unsigned_transaction(
+						|account| VerificationResultPayload {
+							request_id,
+							valid,
+							public: account.public.clone(),
+						},
+						|payload, signature| Call::submit_verification_result_unsigned {
+							payload,
+							signature,
+						},
+					)
+					.map(|(_, result)| result)
+			};
+			match submitted {
+				Some(Ok(())) => sent.set(&now),
+				_ => failed = true,
+			}
+		}
+		if failed {
+			return Err("failed to submit a verification result")
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,320 @@
+//! Verification of PLONK proofs with KZG commitments, following the verifier of
+//! <https://eprint.iacr.org/2019/953> (round 5 with the linearization polynomial and the
+//! constant term `r_0`), over any pairing friendly curve.
+//!
+//! Circuits use the standard gate `q_M a b + q_L a + q_R b + q_O c + q_C + PI = 0` on a
+//! multiplicative subgroup `H = <omega>` of size `n`, with the first `l` gates constraining
+//! `a_i` to the public inputs. The quotient is split into three pieces of degree `< n + 2`.
+//! Fiat-Shamir challenges are derived from SHA-256 over the compressed encodings.
+//!
+//! With the host accelerated `Bls12_381Optimized` the MSM and the pairing check run natively.
+
+use crate::utils::Transcript;
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{FftField, Field, One, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use ark_std::vec::Vec;
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+pub mod prover;
+
+/// Upper bound for the uncompressed encoding of a `VerifyingKey`.
+pub const MAX_VERIFYING_KEY_LEN: u32 = 2048;
+
+/// Upper bound for the uncompressed encoding of the `[x]G2` point of a universal setup.
+pub const MAX_SRS_LEN: u32 = 192;
+
+/// The most public inputs of a registered verifying key. Verification evaluates a Lagrange
+/// polynomial, with a field inversion, for each of them, which its fixed weight covers up to
+/// this many.
+pub const MAX_PUBLIC_INPUTS: u64 = 32;
+
+/// The largest evaluation domain of a registered verifying key, the largest both curves
+/// support.
+pub const MAX_DOMAIN_SIZE: u64 = 1 << 28;
+
+/// The curves PLONK verifying keys can be registered for.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub enum Curve {
+	Bls12_381,
+	Bn254,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+	/// The verifying key does not decode or its domain size is not supported.
+	InvalidVerifyingKey,
+	/// The proof does not decode.
+	InvalidProof,
+	/// The public inputs do not decode or do not match the verifying key.
+	PublicInputMismatch,
+	/// The `[x]G2` point of the universal setup does not decode.
+	InvalidSrs,
+	/// The verifying key was not derived from the given universal setup.
+	SrsMismatch,
+}
+
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VerifyingKey<E: Pairing> {
+	/// Size `n` of the evaluation domain, a power of two.
+	pub domain_size: u64,
+	pub num_public_inputs: u64,
+	pub q_m: E::G1Affine,
+	pub q_l: E::G1Affine,
+	pub q_r: E::G1Affine,
+	pub q_o: E::G1Affine,
+	pub q_c: E::G1Affine,
+	pub s_sigma_1: E::G1Affine,
+	pub s_sigma_2: E::G1Affine,
+	pub s_sigma_3: E::G1Affine,
+	/// Coset shifts of the identity permutation for the `b` and `c` wires.
+	pub k1: E::ScalarField,
+	pub k2: E::ScalarField,
+	/// `[tau]G2` of the universal setup.
+	pub x_2: E::G2Affine,
+}
+
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Proof<E: Pairing> {
+	pub a: E::G1Affine,
+	pub b: E::G1Affine,
+	pub c: E::G1Affine,
+	pub z: E::G1Affine,
+	pub t_lo: E::G1Affine,
+	pub t_mid: E::G1Affine,
+	pub t_hi: E::G1Affine,
+	pub w_zeta: E::G1Affine,
+	pub w_zeta_omega: E::G1Affine,
+	pub a_eval: E::ScalarField,
+	pub b_eval: E::ScalarField,
+	pub c_eval: E::ScalarField,
+	pub s_sigma_1_eval: E::ScalarField,
+	pub s_sigma_2_eval: E::ScalarField,
+	pub z_omega_eval: E::ScalarField,
+}
+
+/// Starts the Fiat-Shamir transcript of a proof for `public_inputs` under `vk`.
+pub(crate) fn transcript<E: Pairing>(
+	vk: &VerifyingKey<E>,
+	public_inputs: &[E::ScalarField],
+) -> Transcript {
+	let mut transcript = Transcript::unseeded();
+	transcript.append(b"plonk-vk", vk);
+	transcript.append(b"public-inputs", public_inputs);
+	transcript
+}
+
+/// The challenges `beta, gamma, alpha, zeta, v, u` of the protocol.
+pub(crate) struct Challenges<F> {
+	pub beta: F,
+	pub gamma: F,
+	pub alpha: F,
+	pub zeta: F,
+	pub v: F,
+	pub u: F,
+}
+
+impl<F: PrimeField> Challenges<F> {
+	pub(crate) fn derive<E: Pairing<ScalarField = F>>(
+		vk: &VerifyingKey<E>,
+		public_inputs: &[F],
+		proof: &Proof<E>,
+	) -> Self {
+		let mut transcript = transcript(vk, public_inputs);
+		transcript.append(b"a", &proof.a);
+		transcript.append(b"b", &proof.b);
+		transcript.append(b"c", &proof.c);
+		let beta = transcript.challenge(b"beta");
+		let gamma = transcript.challenge(b"gamma");
+		transcript.append(b"z", &proof.z);
+		let alpha = transcript.challenge(b"alpha");
+		transcript.append(b"t_lo", &proof.t_lo);
+		transcript.append(b"t_mid", &proof.t_mid);
+		transcript.append(b"t_hi", &proof.t_hi);
+		let zeta = transcript.challenge(b"zeta");
+		transcript.append(
+			b"evaluations",
+			&[
+				proof.a_eval,
+				proof.b_eval,
+				proof.c_eval,
+				proof.s_sigma_1_eval,
+				proof.s_sigma_2_eval,
+				proof.z_omega_eval,
+			],
+		);
+		let v = transcript.challenge(b"v");
+		transcript.append(b"w_zeta", &proof.w_zeta);
+		transcript.append(b"w_zeta_omega", &proof.w_zeta_omega);
+		let u = transcript.challenge(b"u");
+		Self { beta, gamma, alpha, zeta, v, u }
+	}
+}
+
+/// Verifies a PLONK `proof` for `public_inputs`.
+pub fn verify<E: Pairing>(
+	vk: &VerifyingKey<E>,
+	public_inputs: &[E::ScalarField],
+	proof: &Proof<E>,
+) -> Result<bool, Error> {
+	if public_inputs.len() as u64 != vk.num_public_inputs || vk.num_public_inputs > vk.domain_size {
+		return Err(Error::PublicInputMismatch)
+	}
+	let n = vk.domain_size;
+	let omega = E::ScalarField::get_root_of_unity(n).ok_or(Error::InvalidVerifyingKey)?;
+	let Challenges { beta, gamma, alpha, zeta, v, u } =
+		Challenges::derive(vk, public_inputs, proof);
+
+	// Vanishing polynomial, first Lagrange polynomial and public input polynomial at `zeta`.
+	let zeta_n = zeta.pow([n]);
+	let z_h = zeta_n - E::ScalarField::one();
+	let n_inv = E::ScalarField::from(n).inverse().ok_or(Error::InvalidVerifyingKey)?;
+	let mut lagrange = Vec::with_capacity(public_inputs.len().max(1));
+	let mut omega_i = E::ScalarField::one();
+	for _ in 0..public_inputs.len().max(1) {
+		// `L_i(zeta) = omega^i (zeta^n - 1) / (n (zeta - omega^i))`, `zeta` is in `H` with
+		// negligible probability only.
+		let denominator = match (zeta - omega_i).inverse() {
+			Some(denominator) => denominator,
+			None => return Ok(false),
+		};
+		lagrange.push(omega_i * z_h * n_inv * denominator);
+		omega_i *= omega;
+	}
+	let l_0 = lagrange[0];
+	let pi: E::ScalarField =
+		-public_inputs.iter().zip(&lagrange).map(|(w, l)| *w * l).sum::<E::ScalarField>();
+
+	let (a, b, c) = (proof.a_eval, proof.b_eval, proof.c_eval);
+	let (s1, s2) = (proof.s_sigma_1_eval, proof.s_sigma_2_eval);
+	let z_omega = proof.z_omega_eval;
+	let alpha_2 = alpha.square();
+
+	let sigma_product = (a + beta * s1 + gamma) * (b + beta * s2 + gamma) * alpha * z_omega;
+	let r_0 = pi - l_0 * alpha_2 - sigma_product * (c + gamma);
+
+	// `[F] = [D] + v [a] + v^2 [b] + v^3 [c] + v^4 [s_sigma_1] + v^5 [s_sigma_2]`, then
+	// `[F] - [E] + zeta [W_zeta] + u zeta omega [W_zeta_omega]` in one MSM.
+	let z_coefficient = (a + beta * zeta + gamma) *
+		(b + beta * vk.k1 * zeta + gamma) *
+		(c + beta * vk.k2 * zeta + gamma) *
+		alpha + l_0 * alpha_2 +
+		u;
+	let zeta_n_2 = zeta_n * zeta.square();
+	let v_powers: Vec<E::ScalarField> = (1..=5)
+		.scan(E::ScalarField::one(), |power, _| {
+			*power *= v;
+			Some(*power)
+		})
+		.collect();
+	let e = -r_0 +
+		v_powers[0] * a +
+		v_powers[1] * b +
+		v_powers[2] * c +
+		v_powers[3] * s1 +
+		v_powers[4] * s2 +
+		u * z_omega;
+
+	let bases = [
+		vk.q_m,
+		vk.q_l,
+		vk.q_r,
+		vk.q_o,
+		vk.q_c,
+		proof.z,
+		vk.s_sigma_3,
+		proof.t_lo,
+		proof.t_mid,
+		proof.t_hi,
+		proof.a,
+		proof.b,
+		proof.c,
+		vk.s_sigma_1,
+		vk.s_sigma_2,
+		E::G1Affine::generator(),
+		proof.w_zeta,
+		proof.w_zeta_omega,
+	];
+	let scalars = [
+		a * b,
+		a,
+		b,
+		c,
+		E::ScalarField::one(),
+		z_coefficient,
+		-sigma_product * beta,
+		-z_h,
+		-z_h * zeta_n_2,
+		-z_h * zeta_n_2.square(),
+		v_powers[0],
+		v_powers[1],
+		v_powers[2],
+		v_powers[3],
+		v_powers[4],
+		-e,
+		zeta,
+		u * zeta * omega,
+	];
+	let rhs = E::G1::msm(&bases, &scalars).expect("equal lengths; qed");
+	let lhs = proof.w_zeta.into_group() + proof.w_zeta_omega * u;
+
+	// `e([W_zeta] + u [W_zeta_omega], [x]_2) = e(rhs, [1]_2)`
+	Ok(E::multi_pairing(
+		[lhs.into_affine(), (-rhs).into_affine()],
+		[vk.x_2, E::G2Affine::generator()],
+	)
+	.is_zero())
+}
+
+/// Decodes the uncompressed `vk`, `public_inputs` and `proof` and verifies the proof. The
+/// verifying key is expected to be validated already.
+pub fn verify_serialized<E: Pairing>(
+	vk: &[u8],
+	public_inputs: &[u8],
+	proof: &[u8],
+) -> Result<bool, Error> {
+	let vk = VerifyingKey::<E>::deserialize_with_mode(vk, Compress::No, Validate::No)
+		.map_err(|_| Error::InvalidVerifyingKey)?;
+	let public_inputs =
+		Vec::<E::ScalarField>::deserialize_with_mode(public_inputs, Compress::No, Validate::Yes)
+			.map_err(|_| Error::PublicInputMismatch)?;
+	let proof = Proof::<E>::deserialize_with_mode(proof, Compress::No, Validate::Yes)
+		.map_err(|_| Error::InvalidProof)?;
+	verify(&vk, &public_inputs, &proof)
+}
+
+/// Checks that `x_2` is a valid uncompressed `[x]G2` point of `E`.
+pub fn validate_srs<E: Pairing>(x_2: &[u8]) -> Result<(), Error> {
+	E::G2Affine::deserialize_with_mode(x_2, Compress::No, Validate::Yes)
+		.map(|_| ())
+		.map_err(|_| Error::InvalidSrs)
+}
+
+/// Checks that `vk` is a valid uncompressed verifying key for `E` set up with the universal
+/// setup whose validated, uncompressed `[x]G2` point is `x_2`, within `MAX_PUBLIC_INPUTS` and
+/// `MAX_DOMAIN_SIZE`.
+pub fn validate_verifying_key<E: Pairing>(vk: &[u8], x_2: &[u8]) -> Result<(), Error> {
+	let vk = VerifyingKey::<E>::deserialize_with_mode(vk, Compress::No, Validate::Yes)
+		.map_err(|_| Error::InvalidVerifyingKey)?;
+	let x_2 = E::G2Affine::deserialize_with_mode(x_2, Compress::No, Validate::No)
+		.map_err(|_| Error::InvalidSrs)?;
+	if vk.x_2 != x_2 {
+		return Err(Error::SrsMismatch)
+	}
+	let valid_shifts = !vk.k1.is_zero() &&
+		!vk.k2.is_zero() &&
+		vk.k1.pow([vk.domain_size]) != E::ScalarField::one() &&
+		vk.k2.pow([vk.domain_size]) != E::ScalarField::one() &&
+		(vk.k1 / vk.k2).pow([vk.domain_size]) != E::ScalarField::one();
+	if E::ScalarField::get_root_of_unity(vk.domain_size).is_none() ||
+		!vk.domain_size.is_power_of_two() ||
+		vk.domain_size > MAX_DOMAIN_SIZE ||
+		vk.num_public_inputs > vk.domain_size.min(MAX_PUBLIC_INPUTS) ||
+		!valid_shifts
+	{
+		return Err(Error::InvalidVerifyingKey)
+	}
+	Ok(())
+}
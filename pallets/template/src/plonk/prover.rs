@@ -0,0 +1,380 @@
+//! A minimal PLONK prover for tests and benchmarks.
+//!
+//! The setup samples `tau` from the caller and commits by evaluating at `tau` directly, so it is
+//! insecure by construction. Proofs are not blinded and therefore not zero knowledge.
+
+use super::{transcript, Proof, VerifyingKey};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{FftField, Field, One, Zero};
+use ark_poly::{
+	univariate::DensePolynomial, DenseUVPolynomial, EvaluationDomain, Polynomial,
+	Radix2EvaluationDomain,
+};
+use ark_std::{vec, vec::Vec};
+
+/// Index of a circuit variable.
+pub type Variable = usize;
+
+/// A circuit together with the values of its variables.
+pub struct Circuit<F> {
+	num_public_inputs: usize,
+	/// Selectors `[q_M, q_L, q_R, q_O, q_C]` of every gate.
+	selectors: Vec<[F; 5]>,
+	/// Variables of the `a`, `b` and `c` wires of every gate.
+	wires: Vec<[Variable; 3]>,
+	values: Vec<F>,
+}
+
+impl<F: Field> Default for Circuit<F> {
+	fn default() -> Self {
+		// Variable 0 is a constant zero connected to all unused wires.
+		Self {
+			num_public_inputs: 0,
+			selectors: Vec::new(),
+			wires: Vec::new(),
+			values: vec![F::zero()],
+		}
+	}
+}
+
+impl<F: Field> Circuit<F> {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Allocates a public input. Must be called before any other gate is added.
+	pub fn public_input(&mut self, value: F) -> Variable {
+		assert_eq!(self.num_public_inputs, self.selectors.len(), "public inputs come first");
+		let input = self.witness(value);
+		self.num_public_inputs += 1;
+		self.gate([F::zero(), F::one(), F::zero(), F::zero(), F::zero()], [input, 0, 0]);
+		input
+	}
+
+	pub fn witness(&mut self, value: F) -> Variable {
+		self.values.push(value);
+		self.values.len() - 1
+	}
+
+	pub fn value(&self, variable: Variable) -> F {
+		self.values[variable]
+	}
+
+	/// Adds the gate `q_M a b + q_L a + q_R b + q_O c + q_C = 0`.
+	pub fn gate(&mut self, selectors: [F; 5], wires: [Variable; 3]) {
+		self.selectors.push(selectors);
+		self.wires.push(wires);
+	}
+
+	pub fn mul(&mut self, a: Variable, b: Variable) -> Variable {
+		let c = self.witness(self.value(a) * self.value(b));
+		self.gate([F::one(), F::zero(), F::zero(), -F::one(), F::zero()], [a, b, c]);
+		c
+	}
+
+	pub fn add(&mut self, a: Variable, b: Variable) -> Variable {
+		let c = self.witness(self.value(a) + self.value(b));
+		self.gate([F::zero(), F::one(), F::one(), -F::one(), F::zero()], [a, b, c]);
+		c
+	}
+
+	pub fn add_constant(&mut self, a: Variable, constant: F) -> Variable {
+		let c = self.witness(self.value(a) + constant);
+		self.gate([F::zero(), F::one(), F::zero(), -F::one(), constant], [a, 0, c]);
+		c
+	}
+
+	pub fn assert_equal(&mut self, a: Variable, b: Variable) {
+		self.gate([F::zero(), F::one(), -F::one(), F::zero(), F::zero()], [a, b, 0]);
+	}
+
+	/// The public inputs in the order they were allocated.
+	pub fn public_inputs(&self) -> Vec<F> {
+		self.wires[..self.num_public_inputs]
+			.iter()
+			.map(|wires| self.values[wires[0]])
+			.collect()
+	}
+}
+
+/// `x^3 + x + 5 = y` with public `y`, repeated `repetitions` times with the same `x`.
+pub fn cube_circuit<F: Field>(x: u64, repetitions: usize) -> Circuit<F> {
+	let x = F::from(x);
+	let y = x * x * x + x + F::from(5u64);
+	let mut circuit = Circuit::new();
+	let y = circuit.public_input(y);
+	let x = circuit.witness(x);
+	for _ in 0..repetitions.max(1) {
+		let x_2 = circuit.mul(x, x);
+		let x_3 = circuit.mul(x_2, x);
+		let sum = circuit.add(x_3, x);
+		let out = circuit.add_constant(sum, F::from(5u64));
+		circuit.assert_equal(out, y);
+	}
+	circuit
+}
+
+/// `cube_circuit` with its public `y` repeated as `num_public_inputs` public inputs.
+pub fn cube_circuit_with_inputs<F: Field>(x: u64, num_public_inputs: usize) -> Circuit<F> {
+	let x = F::from(x);
+	let y = x * x * x + x + F::from(5u64);
+	let mut circuit = Circuit::new();
+	let inputs: Vec<_> = (0..num_public_inputs.max(1)).map(|_| circuit.public_input(y)).collect();
+	let x = circuit.witness(x);
+	let x_2 = circuit.mul(x, x);
+	let x_3 = circuit.mul(x_2, x);
+	let sum = circuit.add(x_3, x);
+	let out = circuit.add_constant(sum, F::from(5u64));
+	for input in inputs {
+		circuit.assert_equal(out, input);
+	}
+	circuit
+}
+
+pub struct ProvingKey<E: Pairing> {
+	pub vk: VerifyingKey<E>,
+	tau: E::ScalarField,
+	domain: Radix2EvaluationDomain<E::ScalarField>,
+	q_m: DensePolynomial<E::ScalarField>,
+	q_l: DensePolynomial<E::ScalarField>,
+	q_r: DensePolynomial<E::ScalarField>,
+	q_o: DensePolynomial<E::ScalarField>,
+	q_c: DensePolynomial<E::ScalarField>,
+	sigma: [DensePolynomial<E::ScalarField>; 3],
+	/// `sigma_j(omega^i)` for every wire position.
+	sigma_evals: [Vec<E::ScalarField>; 3],
+}
+
+fn commit<E: Pairing>(tau: E::ScalarField, p: &DensePolynomial<E::ScalarField>) -> E::G1Affine {
+	(E::G1Affine::generator() * p.evaluate(&tau)).into_affine()
+}
+
+/// Divides `p` by `X - point`, discarding the remainder.
+fn divide_by_linear<F: Field>(p: &DensePolynomial<F>, point: F) -> DensePolynomial<F> {
+	let mut quotient = vec![F::zero(); p.coeffs.len().saturating_sub(1)];
+	let mut carry = F::zero();
+	for (i, coefficient) in p.coeffs.iter().enumerate().rev().take(quotient.len()) {
+		carry = *coefficient + carry * point;
+		quotient[i - 1] = carry;
+	}
+	DensePolynomial::from_coefficients_vec(quotient)
+}
+
+fn constant<F: Field>(value: F) -> DensePolynomial<F> {
+	DensePolynomial::from_coefficients_vec(vec![value])
+}
+
+/// Runs the (insecure) setup for the shape of `circuit` with the toxic waste `tau`.
+pub fn setup<E: Pairing>(circuit: &Circuit<E::ScalarField>, tau: E::ScalarField) -> ProvingKey<E> {
+	let domain = Radix2EvaluationDomain::<E::ScalarField>::new(circuit.selectors.len())
+		.expect("circuit fits into the two-adic subgroup; qed");
+	let n = domain.size();
+	let selector = |index: usize| {
+		let mut evals: Vec<_> = circuit.selectors.iter().map(|gate| gate[index]).collect();
+		evals.resize(n, E::ScalarField::zero());
+		DensePolynomial::from_coefficients_vec(domain.ifft(&evals))
+	};
+
+	// Wire positions `(j, i)` of the same variable form one cycle of the permutation.
+	let k1 = E::ScalarField::GENERATOR;
+	let k2 = k1.square();
+	let shifts = [E::ScalarField::one(), k1, k2];
+	let mut positions: Vec<Vec<(usize, usize)>> = vec![Vec::new(); circuit.values.len()];
+	for i in 0..n {
+		for j in 0..3 {
+			let variable = circuit.wires.get(i).map_or(0, |wires| wires[j]);
+			positions[variable].push((j, i));
+		}
+	}
+	let elements: Vec<E::ScalarField> = domain.elements().collect();
+	let mut sigma_evals = [
+		vec![E::ScalarField::zero(); n],
+		vec![E::ScalarField::zero(); n],
+		vec![E::ScalarField::zero(); n],
+	];
+	for cycle in positions {
+		for (k, &(j, i)) in cycle.iter().enumerate() {
+			let (next_j, next_i) = cycle[(k + 1) % cycle.len()];
+			sigma_evals[j][i] = shifts[next_j] * elements[next_i];
+		}
+	}
+	let sigma = sigma_evals
+		.clone()
+		.map(|evals| DensePolynomial::from_coefficients_vec(domain.ifft(&evals)));
+
+	let (q_m, q_l, q_r, q_o, q_c) =
+		(selector(0), selector(1), selector(2), selector(3), selector(4));
+	let vk = VerifyingKey {
+		domain_size: n as u64,
+		num_public_inputs: circuit.num_public_inputs as u64,
+		q_m: commit::<E>(tau, &q_m),
+		q_l: commit::<E>(tau, &q_l),
+		q_r: commit::<E>(tau, &q_r),
+		q_o: commit::<E>(tau, &q_o),
+		q_c: commit::<E>(tau, &q_c),
+		s_sigma_1: commit::<E>(tau, &sigma[0]),
+		s_sigma_2: commit::<E>(tau, &sigma[1]),
+		s_sigma_3: commit::<E>(tau, &sigma[2]),
+		k1,
+		k2,
+		x_2: (E::G2Affine::generator() * tau).into_affine(),
+	};
+	ProvingKey { vk, tau, domain, q_m, q_l, q_r, q_o, q_c, sigma, sigma_evals }
+}
+
+/// Proves that the values of `circuit` satisfy the circuit `pk` was created for.
+pub fn prove<E: Pairing>(pk: &ProvingKey<E>, circuit: &Circuit<E::ScalarField>) -> Proof<E> {
+	type Poly<E> = DensePolynomial<<E as Pairing>::ScalarField>;
+	let domain = pk.domain;
+	let n = domain.size();
+	let one = E::ScalarField::one();
+	let omega = domain.group_gen();
+	let x = Poly::<E>::from_coefficients_vec(vec![E::ScalarField::zero(), one]);
+	let interpolate = |mut evals: Vec<E::ScalarField>| {
+		evals.resize(n, E::ScalarField::zero());
+		Poly::<E>::from_coefficients_vec(domain.ifft(&evals))
+	};
+	let wire_evals: Vec<Vec<E::ScalarField>> = (0..3)
+		.map(|j| {
+			(0..n)
+				.map(|i| circuit.values[circuit.wires.get(i).map_or(0, |wires| wires[j])])
+				.collect()
+		})
+		.collect();
+	let public_inputs = circuit.public_inputs();
+
+	// Round 1: wire polynomials.
+	let [a, b, c] = [0, 1, 2].map(|j| interpolate(wire_evals[j].clone()));
+	let mut transcript = transcript(&pk.vk, &public_inputs);
+	let [a_commitment, b_commitment, c_commitment] = [&a, &b, &c].map(|p| commit::<E>(pk.tau, p));
+	transcript.append(b"a", &a_commitment);
+	transcript.append(b"b", &b_commitment);
+	transcript.append(b"c", &c_commitment);
+	let beta: E::ScalarField = transcript.challenge(b"beta");
+	let gamma: E::ScalarField = transcript.challenge(b"gamma");
+
+	// Round 2: permutation accumulator.
+	let shifts = [one, pk.vk.k1, pk.vk.k2];
+	let mut z_evals = vec![one];
+	for i in 0..n - 1 {
+		let omega_i = domain.element(i);
+		let mut numerator = one;
+		let mut denominator = one;
+		for j in 0..3 {
+			numerator *= wire_evals[j][i] + beta * shifts[j] * omega_i + gamma;
+			denominator *= wire_evals[j][i] + beta * pk.sigma_evals[j][i] + gamma;
+		}
+		z_evals.push(z_evals[i] * numerator * denominator.inverse().expect("gamma is random; qed"));
+	}
+	let z = interpolate(z_evals);
+	let z_commitment = commit::<E>(pk.tau, &z);
+	transcript.append(b"z", &z_commitment);
+	let alpha: E::ScalarField = transcript.challenge(b"alpha");
+
+	// Round 3: quotient polynomial.
+	let mut pi_evals: Vec<_> = public_inputs.iter().map(|w| -*w).collect();
+	pi_evals.resize(n, E::ScalarField::zero());
+	let pi = interpolate(pi_evals);
+	let mut l_0_evals = vec![E::ScalarField::zero(); n];
+	l_0_evals[0] = one;
+	let l_0 = interpolate(l_0_evals);
+	let z_omega = Poly::<E>::from_coefficients_vec(
+		z.coeffs.iter().zip(domain.elements()).map(|(c, w)| *c * w).collect(),
+	);
+	let beta_x = &x * beta;
+	let gamma_ = constant(gamma);
+	let permutation_id = &(&(&(&(&a + &beta_x) + &gamma_) *
+		&(&(&b + &(&beta_x * pk.vk.k1)) + &gamma_)) *
+		&(&(&c + &(&beta_x * pk.vk.k2)) + &gamma_)) *
+		&z;
+	let permutation_sigma = &(&(&(&(&a + &(&pk.sigma[0] * beta)) + &gamma_) *
+		&(&(&b + &(&pk.sigma[1] * beta)) + &gamma_)) *
+		&(&(&c + &(&pk.sigma[2] * beta)) + &gamma_)) *
+		&z_omega;
+	let gate = &(&(&(&(&(&(&a * &b) * &pk.q_m) + &(&a * &pk.q_l)) + &(&b * &pk.q_r)) +
+		&(&c * &pk.q_o)) +
+		&pi) + &pk.q_c;
+	let numerator = &(&gate + &(&(&permutation_id - &permutation_sigma) * alpha)) +
+		&(&(&(&z - &constant(one)) * &l_0) * alpha.square());
+	let (t, remainder) =
+		numerator.divide_by_vanishing_poly(domain).expect("n is a power of two; qed");
+	debug_assert!(remainder.is_zero(), "the circuit is not satisfied");
+	let piece = |k: usize| {
+		Poly::<E>::from_coefficients_vec(
+			t.coeffs.iter().skip(k * (n + 2)).take(n + 2).cloned().collect(),
+		)
+	};
+	let [t_lo, t_mid, t_hi] = [piece(0), piece(1), piece(2)];
+	let [t_lo_commitment, t_mid_commitment, t_hi_commitment] =
+		[&t_lo, &t_mid, &t_hi].map(|p| commit::<E>(pk.tau, p));
+	transcript.append(b"t_lo", &t_lo_commitment);
+	transcript.append(b"t_mid", &t_mid_commitment);
+	transcript.append(b"t_hi", &t_hi_commitment);
+	let zeta: E::ScalarField = transcript.challenge(b"zeta");
+
+	// Round 4: evaluations.
+	let [a_eval, b_eval, c_eval] = [&a, &b, &c].map(|p| p.evaluate(&zeta));
+	let s_sigma_1_eval = pk.sigma[0].evaluate(&zeta);
+	let s_sigma_2_eval = pk.sigma[1].evaluate(&zeta);
+	let z_omega_eval = z.evaluate(&(zeta * omega));
+	transcript.append(
+		b"evaluations",
+		&[a_eval, b_eval, c_eval, s_sigma_1_eval, s_sigma_2_eval, z_omega_eval],
+	);
+	let v: E::ScalarField = transcript.challenge(b"v");
+
+	// Round 5: linearization and opening proofs.
+	let zeta_n = zeta.pow([n as u64]);
+	let z_h = zeta_n - one;
+	let zeta_n_2 = zeta_n * zeta.square();
+	let l_0_eval = l_0.evaluate(&zeta);
+	let r =
+		&(&(&(&(&(&(&(&pk.q_m * (a_eval * b_eval)) + &(&pk.q_l * a_eval)) +
+			&(&pk.q_r * b_eval)) +
+			&(&pk.q_o * c_eval)) +
+			&constant(pi.evaluate(&zeta))) +
+			&pk.q_c) + &(&(&(&z *
+			((a_eval + beta * zeta + gamma) *
+				(b_eval + beta * pk.vk.k1 * zeta + gamma) *
+				(c_eval + beta * pk.vk.k2 * zeta + gamma))) -
+			&(&(&(&pk.sigma[2] * beta) + &constant(c_eval + gamma)) *
+				((a_eval + beta * s_sigma_1_eval + gamma) *
+					(b_eval + beta * s_sigma_2_eval + gamma) *
+					z_omega_eval))) *
+			alpha)) + &(&(&(&z - &constant(one)) * (l_0_eval * alpha.square())) -
+			&(&(&(&t_lo + &(&t_mid * zeta_n_2)) + &(&t_hi * zeta_n_2.square())) * z_h));
+	debug_assert!(r.evaluate(&zeta).is_zero());
+
+	let mut opening = r;
+	let mut v_power = one;
+	for (p, eval) in [
+		(&a, a_eval),
+		(&b, b_eval),
+		(&c, c_eval),
+		(&pk.sigma[0], s_sigma_1_eval),
+		(&pk.sigma[1], s_sigma_2_eval),
+	] {
+		v_power *= v;
+		opening = &opening + &(&(p - &constant(eval)) * v_power);
+	}
+	let w_zeta = divide_by_linear(&opening, zeta);
+	let w_zeta_omega = divide_by_linear(&(&z - &constant(z_omega_eval)), zeta * omega);
+
+	Proof {
+		a: a_commitment,
+		b: b_commitment,
+		c: c_commitment,
+		z: z_commitment,
+		t_lo: t_lo_commitment,
+		t_mid: t_mid_commitment,
+		t_hi: t_hi_commitment,
+		w_zeta: commit::<E>(pk.tau, &w_zeta),
+		w_zeta_omega: commit::<E>(pk.tau, &w_zeta_omega),
+		a_eval,
+		b_eval,
+		c_eval,
+		s_sigma_1_eval,
+		s_sigma_2_eval,
+		z_omega_eval,
+	}
+}
@@ -0,0 +1,302 @@
+//! Verification of SnarkPack (<https://eprint.iacr.org/2021/529>) aggregates of Groth16 proofs.
+//!
+//! The aggregate commits to the proofs `(A_i, B_i, C_i)` with the pairing based keys
+//! `v_i = (h^(a^i), h^(b^i))` and `w_i = (g^(a^(n + i)), g^(b^(n + i)))` of two powers of tau and
+//! proves `Z_AB = prod e(A_i, B_i)^(r^i)` (TIPP) and `Z_C = sum r^i C_i` (MIPP) in one GIPA
+//! recursion of `log n` rounds. The folded keys are opened with KZG at a random point, so the
+//! verifier only needs `g^a, g^b, h^a, h^b` of the setup. The batched Groth16 equation
+//! `Z_AB = e(alpha, beta)^(sum r^i) e(sum r^i PI_i, gamma) e(Z_C, delta)` concludes.
+//!
+//! With the host accelerated `Bls12_381Optimized` the pairings and MSMs run natively, the
+//! arithmetic in the target group stays in the runtime.
+
+use crate::utils::Transcript;
+use ark_ec::{
+	pairing::{Pairing, PairingOutput},
+	AffineRepr, CurveGroup, VariableBaseMSM,
+};
+use ark_ff::{Field, One, Zero};
+use ark_groth16::VerifyingKey;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use ark_std::{vec, vec::Vec};
+
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+pub mod prover;
+
+/// Size of the uncompressed encoding of `VerifierSrs` over BLS12-381.
+pub const VERIFIER_SRS_LEN: u32 = 2 * 96 + 2 * 192;
+
+/// Upper bound for the number of proofs of an aggregate, a power of two.
+pub const MAX_AGGREGATED_PROOFS: u32 = 64;
+
+/// The rounds of the recursion of an aggregate of `n` proofs, capped at the rounds of
+/// `MAX_AGGREGATED_PROOFS` proofs.
+pub fn rounds(n: usize) -> u32 {
+	n.min(MAX_AGGREGATED_PROOFS as usize).next_power_of_two().trailing_zeros()
+}
+
+/// The uncompressed Groth16 verifying key of the cube circuit, the aggregation setup, the public
+/// inputs and the SnarkPack aggregate of 4 proofs in `fixtures/snarkpack/aggregate.scale`, as
+/// `groth16_register_vk`, `groth16_set_aggregation_srs` and `groth16_verify_aggregate` take them.
+pub fn aggregate_fixture() -> (Vec<u8>, Vec<u8>, Vec<Vec<u8>>, Vec<u8>) {
+	let mut fixture = &include_bytes!("../fixtures/snarkpack/aggregate.scale")[..];
+	codec::Decode::decode(&mut fixture).expect("the fixture is a valid encoding; qed")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+	/// The Groth16 verifying key does not decode.
+	InvalidVerifyingKey,
+	/// The aggregate proof does not decode.
+	InvalidProof,
+	/// The public inputs do not decode, do not match the verifying key, or their number is not
+	/// the power of two the proof aggregates.
+	PublicInputMismatch,
+	/// The setup does not decode.
+	InvalidSrs,
+}
+
+/// The part of the two powers of tau `(g^(a^i), h^(a^i))` and `(g^(b^i), h^(b^i))` the
+/// verifier needs.
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VerifierSrs<E: Pairing> {
+	pub g_alpha: E::G1Affine,
+	pub g_beta: E::G1Affine,
+	pub h_alpha: E::G2Affine,
+	pub h_beta: E::G2Affine,
+}
+
+/// A commitment `(T, U)` under the first and the second powers of tau.
+pub type Commitment<E> = (PairingOutput<E>, PairingOutput<E>);
+
+/// The cross terms of one GIPA round, for the halves `L` and `R` of the vectors.
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GipaRound<E: Pairing> {
+	/// Commitments to `(A_R, B_L)` and `(A_L, B_R)`.
+	pub com_ab: [Commitment<E>; 2],
+	/// Commitments to `C_R` and `C_L`.
+	pub com_c: [Commitment<E>; 2],
+	/// `<A_R, B_L>` and `<A_L, B_R>`.
+	pub z_ab: [PairingOutput<E>; 2],
+	/// `<C_R, s_L>` and `<C_L, s_R>`.
+	pub z_c: [E::G1Affine; 2],
+}
+
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AggregateProof<E: Pairing> {
+	pub com_ab: Commitment<E>,
+	pub com_c: Commitment<E>,
+	/// `prod e(A_i, B_i)^(r^i)`.
+	pub ip_ab: PairingOutput<E>,
+	/// `sum r^i C_i`.
+	pub agg_c: E::G1Affine,
+	pub rounds: Vec<GipaRound<E>>,
+	pub final_a: E::G1Affine,
+	pub final_b: E::G2Affine,
+	pub final_c: E::G1Affine,
+	/// The folded keys `v` and `w`.
+	pub final_v: (E::G2Affine, E::G2Affine),
+	pub final_w: (E::G1Affine, E::G1Affine),
+	/// KZG openings of the folded `v` and `w` at `z`, for `a` and `b`.
+	pub v_openings: (E::G2Affine, E::G2Affine),
+	pub w_openings: (E::G1Affine, E::G1Affine),
+}
+
+/// Starts the Fiat-Shamir transcript of an aggregate for `public_inputs` under `vk`.
+pub(crate) fn transcript<E: Pairing>(
+	vk: &VerifyingKey<E>,
+	public_inputs: &[Vec<E::ScalarField>],
+) -> Transcript {
+	let mut transcript = Transcript::new(b"snarkpack");
+	transcript.append(b"groth16-vk", vk);
+	transcript.append(b"public-inputs", public_inputs);
+	transcript
+}
+
+/// `p(z) = prod_j (1 + c_j z^(n / 2^(j + 1)))` for the round coefficients `c_j`, the polynomial
+/// the GIPA folds a key with.
+pub(crate) fn folding_polynomial<F: Field>(coefficients: &[F], z: F) -> F {
+	coefficients
+		.iter()
+		.zip(squares(z, coefficients.len()).iter().rev())
+		.map(|(c, power)| F::one() + *c * power)
+		.product()
+}
+
+/// `[x, x^2, x^4, ..., x^(2^(k - 1))]`.
+pub(crate) fn squares<F: Field>(x: F, k: usize) -> Vec<F> {
+	let mut squares = Vec::with_capacity(k);
+	let mut power = x;
+	for _ in 0..k {
+		squares.push(power);
+		power.square_in_place();
+	}
+	squares
+}
+
+/// Verifies that `proof` aggregates valid Groth16 proofs under `vk`, one for each entry of
+/// `public_inputs`.
+pub fn verify<E: Pairing>(
+	srs: &VerifierSrs<E>,
+	vk: &VerifyingKey<E>,
+	public_inputs: &[Vec<E::ScalarField>],
+	proof: &AggregateProof<E>,
+) -> Result<bool, Error> {
+	let n = public_inputs.len();
+	if n < 2 ||
+		!n.is_power_of_two() ||
+		proof.rounds.len() != n.trailing_zeros() as usize ||
+		public_inputs.iter().any(|inputs| inputs.len() + 1 != vk.gamma_abc_g1.len())
+	{
+		return Err(Error::PublicInputMismatch)
+	}
+
+	let mut transcript = transcript(vk, public_inputs);
+	transcript.append(b"com_ab", &proof.com_ab);
+	transcript.append(b"com_c", &proof.com_c);
+	let r: E::ScalarField = transcript.challenge(b"r");
+	let r_inv = match r.inverse() {
+		Some(r_inv) => r_inv,
+		None => return Ok(false),
+	};
+	transcript.append(b"ip_ab", &proof.ip_ab);
+	transcript.append(b"agg_c", &proof.agg_c);
+
+	// Fold the commitments and inner products with the challenges of every round.
+	let (mut com_ab, mut com_c) = (proof.com_ab, proof.com_c);
+	let mut z_ab = proof.ip_ab;
+	let mut z_c = proof.agg_c.into_group();
+	let mut challenges = Vec::with_capacity(proof.rounds.len());
+	let mut challenges_inv = Vec::with_capacity(proof.rounds.len());
+	for round in &proof.rounds {
+		transcript.append(b"round", round);
+		let x: E::ScalarField = transcript.challenge(b"x");
+		let x_inv = match x.inverse() {
+			Some(x_inv) => x_inv,
+			None => return Ok(false),
+		};
+		let fold = |com: &mut Commitment<E>, cross: &[Commitment<E>; 2]| {
+			com.0 = com.0 + cross[0].0 * x + cross[1].0 * x_inv;
+			com.1 = com.1 + cross[0].1 * x + cross[1].1 * x_inv;
+		};
+		fold(&mut com_ab, &round.com_ab);
+		fold(&mut com_c, &round.com_c);
+		z_ab = z_ab + round.z_ab[0] * x + round.z_ab[1] * x_inv;
+		z_c += round.z_c[0] * x + round.z_c[1] * x_inv;
+		challenges.push(x);
+		challenges_inv.push(x_inv);
+	}
+	transcript.append(
+		b"final",
+		&((proof.final_a, proof.final_b, proof.final_c), proof.final_v, proof.final_w),
+	);
+	let z: E::ScalarField = transcript.challenge(b"z");
+
+	// The final elements open the folded commitments and inner products. The scalars of the
+	// MIPP start as ones and fold to `prod_j (1 + x_j^-1)`.
+	let (a, b, c) = (proof.final_a, proof.final_b, proof.final_c);
+	let (v_1, v_2) = proof.final_v;
+	let (w_1, w_2) = proof.final_w;
+	let s: E::ScalarField =
+		challenges_inv.iter().map(|x_inv| E::ScalarField::one() + x_inv).product();
+	if E::multi_pairing([a, w_1], [v_1, b]) != com_ab.0 ||
+		E::multi_pairing([a, w_2], [v_2, b]) != com_ab.1 ||
+		E::pairing(c, v_1) != com_c.0 ||
+		E::pairing(c, v_2) != com_c.1 ||
+		E::pairing(a, b) != z_ab ||
+		c * s != z_c
+	{
+		return Ok(false)
+	}
+
+	// `v` folds `h^((a / r)^i)` with `x_j^-1` and `w` folds `g^(a^(n + i))` with `x_j`.
+	let g = E::G1Affine::generator();
+	let h = E::G2Affine::generator();
+	let neg_g = (-g.into_group()).into_affine();
+	let coefficients_v: Vec<_> = challenges_inv
+		.iter()
+		.zip(squares(r_inv, challenges.len()).iter().rev())
+		.map(|(x_inv, r_inv_power)| *x_inv * r_inv_power)
+		.collect();
+	let v_z = folding_polynomial(&coefficients_v, z);
+	let w_z = z.pow([n as u64]) * folding_polynomial(&challenges, z);
+	let g_z = g * z;
+	let h_z = h * z;
+	let kzg_v = |tau: E::G1Affine, opening: E::G2Affine, key: E::G2Affine| {
+		E::multi_pairing(
+			[(tau.into_group() - g_z).into_affine(), neg_g],
+			[opening, (key.into_group() - h * v_z).into_affine()],
+		)
+		.is_zero()
+	};
+	let kzg_w = |tau: E::G2Affine, opening: E::G1Affine, key: E::G1Affine| {
+		E::multi_pairing(
+			[opening, (g * w_z - key).into_affine()],
+			[(tau.into_group() - h_z).into_affine(), h],
+		)
+		.is_zero()
+	};
+	if !kzg_v(srs.g_alpha, proof.v_openings.0, v_1) ||
+		!kzg_v(srs.g_beta, proof.v_openings.1, v_2) ||
+		!kzg_w(srs.h_alpha, proof.w_openings.0, w_1) ||
+		!kzg_w(srs.h_beta, proof.w_openings.1, w_2)
+	{
+		return Ok(false)
+	}
+
+	// `Z_AB = e(alpha, beta)^(sum r^i) e(sum r^i PI_i, gamma) e(Z_C, delta)`.
+	let mut scalars = vec![E::ScalarField::zero(); vk.gamma_abc_g1.len()];
+	let mut r_i = E::ScalarField::one();
+	for inputs in public_inputs {
+		scalars[0] += r_i;
+		for (scalar, input) in scalars[1..].iter_mut().zip(inputs) {
+			*scalar += r_i * input;
+		}
+		r_i *= r;
+	}
+	let inputs = E::G1::msm(&vk.gamma_abc_g1, &scalars).expect("equal lengths; qed");
+	Ok(E::multi_pairing(
+		[(vk.alpha_g1 * scalars[0]).into_affine(), inputs.into_affine(), proof.agg_c],
+		[vk.beta_g2, vk.gamma_g2, vk.delta_g2],
+	) == proof.ip_ab)
+}
+
+/// Decodes the uncompressed setup, verifying key, lists of public inputs and aggregate proof and
+/// verifies the aggregate. The setup and the verifying key are expected to be validated
+/// already.
+pub fn verify_serialized<E: Pairing>(
+	srs: &[u8],
+	vk: &[u8],
+	public_inputs: &[Vec<u8>],
+	proof: &[u8],
+) -> Result<bool, Error> {
+	let srs = VerifierSrs::<E>::deserialize_with_mode(srs, Compress::No, Validate::No)
+		.map_err(|_| Error::InvalidSrs)?;
+	let vk = VerifyingKey::<E>::deserialize_with_mode(vk, Compress::No, Validate::No)
+		.map_err(|_| Error::InvalidVerifyingKey)?;
+	let public_inputs = public_inputs
+		.iter()
+		.map(|inputs| {
+			Vec::<E::ScalarField>::deserialize_with_mode(&inputs[..], Compress::No, Validate::Yes)
+		})
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(|_| Error::PublicInputMismatch)?;
+	let proof = AggregateProof::<E>::deserialize_with_mode(proof, Compress::No, Validate::Yes)
+		.map_err(|_| Error::InvalidProof)?;
+	verify(&srs, &vk, &public_inputs, &proof)
+}
+
+/// Checks that `srs` is a valid uncompressed verifier setup for `E`.
+pub fn validate_srs<E: Pairing>(srs: &[u8]) -> Result<(), Error> {
+	let srs = VerifierSrs::<E>::deserialize_with_mode(srs, Compress::No, Validate::Yes)
+		.map_err(|_| Error::InvalidSrs)?;
+	// `e(g^a, h) = e(g, h^a)` and the same for `b` bind the G1 and G2 halves together.
+	let neg_g = (-E::G1Affine::generator().into_group()).into_affine();
+	let h = E::G2Affine::generator();
+	if !E::multi_pairing([srs.g_alpha, neg_g], [h, srs.h_alpha]).is_zero() ||
+		!E::multi_pairing([srs.g_beta, neg_g], [h, srs.h_beta]).is_zero()
+	{
+		return Err(Error::InvalidSrs)
+	}
+	Ok(())
+}
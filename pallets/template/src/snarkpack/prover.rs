@@ -0,0 +1,247 @@
+//! Aggregation of Groth16 proofs with SnarkPack.
+//!
+//! The setup samples the two powers of tau from a local rng and is only good for tests and
+//! benchmarks; production aggregates use keys of a ceremony.
+
+use super::{transcript, AggregateProof, Commitment, GipaRound, VerifierSrs};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, One, UniformRand};
+use ark_groth16::{Proof, VerifyingKey};
+use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{rand::Rng, vec, vec::Vec};
+
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProverSrs<E: Pairing> {
+	pub vk: VerifierSrs<E>,
+	/// `g^(a^i)` and `g^(b^i)` for `i < 2n`.
+	g_alpha_powers: Vec<E::G1Affine>,
+	g_beta_powers: Vec<E::G1Affine>,
+	/// `h^(a^i)` and `h^(b^i)` for `i < n`.
+	h_alpha_powers: Vec<E::G2Affine>,
+	h_beta_powers: Vec<E::G2Affine>,
+}
+
+impl<E: Pairing> ProverSrs<E> {
+	/// Samples a setup for aggregating up to `n` proofs.
+	pub fn setup<R: Rng>(n: usize, rng: &mut R) -> Self {
+		let alpha = E::ScalarField::rand(rng);
+		let beta = E::ScalarField::rand(rng);
+		let g_alpha_powers = powers::<E::G1Affine>(alpha, 2 * n);
+		let g_beta_powers = powers::<E::G1Affine>(beta, 2 * n);
+		let h_alpha_powers = powers::<E::G2Affine>(alpha, n);
+		let h_beta_powers = powers::<E::G2Affine>(beta, n);
+		let vk = VerifierSrs {
+			g_alpha: g_alpha_powers[1],
+			g_beta: g_beta_powers[1],
+			h_alpha: h_alpha_powers[1],
+			h_beta: h_beta_powers[1],
+		};
+		Self { vk, g_alpha_powers, g_beta_powers, h_alpha_powers, h_beta_powers }
+	}
+
+	/// The number of proofs the setup aggregates at most.
+	pub fn max_proofs(&self) -> usize {
+		self.h_alpha_powers.len()
+	}
+}
+
+fn powers<G: AffineRepr>(tau: G::ScalarField, n: usize) -> Vec<G> {
+	let mut power = G::ScalarField::one();
+	let powers: Vec<_> = (0..n)
+		.map(|_| {
+			let p = G::generator() * power;
+			power *= tau;
+			p
+		})
+		.collect();
+	G::Group::normalize_batch(&powers)
+}
+
+fn scale<G: AffineRepr>(points: &[G], scalars: &[G::ScalarField]) -> Vec<G> {
+	let scaled: Vec<_> = points.iter().zip(scalars).map(|(p, s)| *p * s).collect();
+	G::Group::normalize_batch(&scaled)
+}
+
+/// `left + x * right`, element wise.
+fn fold<G: AffineRepr>(left: &[G], right: &[G], x: G::ScalarField) -> Vec<G> {
+	let folded: Vec<_> = left.iter().zip(right).map(|(l, r)| *r * x + l).collect();
+	G::Group::normalize_batch(&folded)
+}
+
+/// The commitment `(sum e(C_i, v_1i), sum e(C_i, v_2i))`.
+fn commit<E: Pairing>(g1: &[E::G1Affine], keys: (&[E::G2Affine], &[E::G2Affine])) -> Commitment<E> {
+	(E::multi_pairing(g1, keys.0), E::multi_pairing(g1, keys.1))
+}
+
+/// The commitment `(sum e(A_i, v_1i) + e(w_1i, B_i), sum e(A_i, v_2i) + e(w_2i, B_i))`.
+fn commit_ab<E: Pairing>(
+	a: &[E::G1Affine],
+	b: &[E::G2Affine],
+	v: (&[E::G2Affine], &[E::G2Affine]),
+	w: (&[E::G1Affine], &[E::G1Affine]),
+) -> Commitment<E> {
+	let pair = |v: &[E::G2Affine], w: &[E::G1Affine]| {
+		E::multi_pairing(a.iter().chain(w), v.iter().chain(b))
+	};
+	(pair(v.0, w.0), pair(v.1, w.1))
+}
+
+/// Commits to the quotient `(f(X) - f(z)) / (X - z)` for the coefficients of `f`.
+fn open<G: AffineRepr>(powers: &[G], coefficients: Vec<G::ScalarField>, z: G::ScalarField) -> G {
+	let f = DensePolynomial::from_coefficients_vec(coefficients);
+	let divisor = DensePolynomial::from_coefficients_vec(vec![-z, G::ScalarField::one()]);
+	let quotient = &f / &divisor;
+	G::Group::msm_unchecked(powers, &quotient.coeffs).into_affine()
+}
+
+/// The coefficients of `prod_j (1 + c_j X^(n / 2^(j + 1)))`.
+fn folding_coefficients<F: Field>(challenges: &[F], n: usize) -> Vec<F> {
+	let mut coefficients = vec![F::one(); n];
+	for (j, c) in challenges.iter().enumerate() {
+		let bit = n >> (j + 1);
+		coefficients
+			.iter_mut()
+			.enumerate()
+			.filter(|(i, _)| i & bit != 0)
+			.for_each(|(_, x)| {
+				*x *= c;
+			});
+	}
+	coefficients
+}
+
+/// Aggregates `proofs` of `public_inputs` under `vk`. The number of proofs must be a power of
+/// two of at least two and at most `srs.max_proofs()`.
+pub fn aggregate<E: Pairing>(
+	srs: &ProverSrs<E>,
+	vk: &VerifyingKey<E>,
+	public_inputs: &[Vec<E::ScalarField>],
+	proofs: &[Proof<E>],
+) -> AggregateProof<E> {
+	let n = proofs.len();
+	assert!(n >= 2 && n.is_power_of_two() && n <= srs.max_proofs());
+	assert_eq!(n, public_inputs.len());
+
+	let a: Vec<_> = proofs.iter().map(|proof| proof.a).collect();
+	let b: Vec<_> = proofs.iter().map(|proof| proof.b).collect();
+	let c: Vec<_> = proofs.iter().map(|proof| proof.c).collect();
+	let v_1 = &srs.h_alpha_powers[..n];
+	let v_2 = &srs.h_beta_powers[..n];
+	let w_1 = &srs.g_alpha_powers[n..2 * n];
+	let w_2 = &srs.g_beta_powers[n..2 * n];
+
+	let com_ab = commit_ab::<E>(&a, &b, (v_1, v_2), (w_1, w_2));
+	let com_c = commit::<E>(&c, (v_1, v_2));
+	let mut transcript = transcript(vk, public_inputs);
+	transcript.append(b"com_ab", &com_ab);
+	transcript.append(b"com_c", &com_c);
+	let r: E::ScalarField = transcript.challenge(b"r");
+	let r_inv = r.inverse().expect("the challenge is not zero; qed");
+
+	// Scaling `A` and `C` with `r^i` and `v` with `r^-i` keeps the commitments.
+	let mut r_powers = Vec::with_capacity(n);
+	let mut r_inv_powers = Vec::with_capacity(n);
+	let (mut r_i, mut r_inv_i) = (E::ScalarField::one(), E::ScalarField::one());
+	for _ in 0..n {
+		r_powers.push(r_i);
+		r_inv_powers.push(r_inv_i);
+		r_i *= r;
+		r_inv_i *= r_inv;
+	}
+	let mut a = scale(&a, &r_powers);
+	let mut b = b;
+	let mut c = scale(&c, &r_powers);
+	let mut v_1 = scale(v_1, &r_inv_powers);
+	let mut v_2 = scale(v_2, &r_inv_powers);
+	let mut w_1 = w_1.to_vec();
+	let mut w_2 = w_2.to_vec();
+	let mut s = vec![E::ScalarField::one(); n];
+
+	let ip_ab = E::multi_pairing(&a, &b);
+	let agg_c = c.iter().map(|c| c.into_group()).sum::<E::G1>().into_affine();
+	transcript.append(b"ip_ab", &ip_ab);
+	transcript.append(b"agg_c", &agg_c);
+
+	let mut rounds = Vec::with_capacity(n.trailing_zeros() as usize);
+	let mut challenges = Vec::with_capacity(rounds.capacity());
+	let mut challenges_inv = Vec::with_capacity(rounds.capacity());
+	while a.len() > 1 {
+		let m = a.len() / 2;
+		let (a_l, a_r) = a.split_at(m);
+		let (b_l, b_r) = b.split_at(m);
+		let (c_l, c_r) = c.split_at(m);
+		let (v_1l, v_1r) = v_1.split_at(m);
+		let (v_2l, v_2r) = v_2.split_at(m);
+		let (w_1l, w_1r) = w_1.split_at(m);
+		let (w_2l, w_2r) = w_2.split_at(m);
+		let (s_l, s_r) = s.split_at(m);
+
+		let round = GipaRound {
+			com_ab: [
+				commit_ab::<E>(a_r, b_l, (v_1l, v_2l), (w_1r, w_2r)),
+				commit_ab::<E>(a_l, b_r, (v_1r, v_2r), (w_1l, w_2l)),
+			],
+			com_c: [commit::<E>(c_r, (v_1l, v_2l)), commit::<E>(c_l, (v_1r, v_2r))],
+			z_ab: [E::multi_pairing(a_r, b_l), E::multi_pairing(a_l, b_r)],
+			z_c: [
+				E::G1::msm_unchecked(c_r, s_l).into_affine(),
+				E::G1::msm_unchecked(c_l, s_r).into_affine(),
+			],
+		};
+		transcript.append(b"round", &round);
+		let x: E::ScalarField = transcript.challenge(b"x");
+		let x_inv = x.inverse().expect("the challenge is not zero; qed");
+
+		a = fold(a_l, a_r, x);
+		b = fold(b_l, b_r, x_inv);
+		c = fold(c_l, c_r, x);
+		let folded_v_1 = fold(v_1l, v_1r, x_inv);
+		let folded_v_2 = fold(v_2l, v_2r, x_inv);
+		let folded_w_1 = fold(w_1l, w_1r, x);
+		let folded_w_2 = fold(w_2l, w_2r, x);
+		s = s_l.iter().zip(s_r).map(|(l, r)| *l + x_inv * r).collect();
+		(v_1, v_2, w_1, w_2) = (folded_v_1, folded_v_2, folded_w_1, folded_w_2);
+		rounds.push(round);
+		challenges.push(x);
+		challenges_inv.push(x_inv);
+	}
+
+	let (final_a, final_b, final_c) = (a[0], b[0], c[0]);
+	let final_v = (v_1[0], v_2[0]);
+	let final_w = (w_1[0], w_2[0]);
+	transcript.append(b"final", &((final_a, final_b, final_c), final_v, final_w));
+	let z: E::ScalarField = transcript.challenge(b"z");
+
+	// `v` is `h^(f_v(a))` for the coefficients `x^-1 r^-i`, `w` is `g^(a^n f_w(a))`.
+	let mut coefficients_v = folding_coefficients(&challenges_inv, n);
+	coefficients_v
+		.iter_mut()
+		.zip(&r_inv_powers)
+		.for_each(|(c, r_inv_i)| *c *= r_inv_i);
+	let mut coefficients_w = vec![E::ScalarField::from(0u64); n];
+	coefficients_w.extend(folding_coefficients(&challenges, n));
+	let v_openings = (
+		open(&srs.h_alpha_powers, coefficients_v.clone(), z),
+		open(&srs.h_beta_powers, coefficients_v, z),
+	);
+	let w_openings = (
+		open(&srs.g_alpha_powers, coefficients_w.clone(), z),
+		open(&srs.g_beta_powers, coefficients_w, z),
+	);
+
+	AggregateProof {
+		com_ab,
+		com_c,
+		ip_ab,
+		agg_c,
+		rounds,
+		final_a,
+		final_b,
+		final_c,
+		final_v,
+		final_w,
+		v_openings,
+		w_openings,
+	}
+}
@@ -1,15 +1,2499 @@
-use crate::{bls12_381, mock::*};
-use frame_support::assert_ok;
+use crate::{
+	bls12_381::{
+		self, Bls12_381Optimized, BlsFrOptimized, G1AffineOptimized, G1ProjectiveOptimized,
+		G2AffineOptimized, G2ProjectiveOptimized, Groth16Backend,
+	},
+	bls_signatures::{self, MinPk, MinSig, Variant},
+	hash_to_curve, kzg,
+	marlin::{
+		self,
+		circuit::{CubeCircuit, RepeatedInputCircuit},
+		Marlin,
+	},
+	migrations,
+	mock::*,
+	offchain::VerificationResultPayload,
+	plonk::{self, prover},
+	snarkpack, utils, CircuitName, CircuitVersion, CircuitVersions, Error, Event, Groth16Curve,
+	Groth16PreparedVerifyingKeys, Groth16VerifyingKeyIds, Groth16VerifyingKeyStatuses,
+	KzgTrustedSetup, MarlinSrs, NextPlonkVerifyingKeyId, VerifyingKeyStatus, WeightInfo,
+	MAX_CIRCUIT_NAME_LEN,
+};
+use ark_bls12_381::{Bls12_381, Fq12, Fr as BlsFr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{
+	pairing::{MillerLoopOutput, Pairing},
+	AffineRepr, CurveGroup, VariableBaseMSM,
+};
+use ark_ff::{PrimeField, UniformRand};
+use ark_groth16::Groth16;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_snark::{UniversalSetupSNARK, SNARK};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::DispatchResult,
+	pallet_prelude::{InvalidTransaction, TransactionSource},
+	traits::{GenesisBuild, GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion},
+	unsigned::ValidateUnsigned,
+	weights::Weight,
+	BoundedVec,
+};
+use parking_lot::RwLock;
+use proptest::prelude::{any, prop_oneof, proptest, Just, ProptestConfig, Strategy};
+use sp_core::offchain::{
+	testing::{PoolState, TestOffchainExt, TestTransactionPoolExt},
+	OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+};
+use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId},
+	traits::{BlakeTwo256, Hash},
+	DispatchError, RuntimeAppPublic,
+};
+use std::sync::Arc;
+
+/// The uncompressed Groth16 fixture key, its single public input and proof.
+fn groth16_single_input_fixture() -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+	let (vk, c, proof) = bls12_381::groth16_fixture();
+	(vk, utils::serialize_argument(c), proof)
+}
+
+/// A freshly generated uncompressed Groth16 key for the cube circuit, the public input for `x`
+/// and a proof of it. Keys of the same `seed` are the same.
+fn groth16_cube_fixture(x: u64, seed: u64) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+	type E = ark_bls12_381::Bls12_381;
+	let mut rng = StdRng::seed_from_u64(seed);
+	let (pk, vk) = Groth16::<E>::circuit_specific_setup(
+		CubeCircuit::<BlsFr> { x: None, repetitions: 1 },
+		&mut rng,
+	)
+	.unwrap();
+	let circuit = CubeCircuit::<BlsFr>::new(x, 1);
+	let proof = Groth16::<E>::prove(&pk, circuit, &mut rng).unwrap();
+	(
+		utils::serialize_argument(vk),
+		utils::serialize_argument(circuit.public_inputs()[0]),
+		utils::serialize_argument(proof),
+	)
+}
+
+type Groth16Verification = fn(RuntimeOrigin, Vec<u8>, Vec<u8>, Vec<u8>) -> DispatchResult;
+
+/// The calls verifying a proof of a single public input against an unregistered key, with the
+/// backend they run on.
+fn groth16_verifications() -> [(Groth16Verification, Groth16Backend); 2] {
+	[
+		(TemplateModule::groth16_verification, Groth16Backend::Bls12_381),
+		(
+			|origin, vk, c, proof| {
+				TemplateModule::groth16_verification_optimized(origin, vk, c, proof)
+					.map(|_| ())
+					.map_err(|error| error.error)
+			},
+			Groth16Backend::Bls12_381Optimized,
+		),
+	]
+}
+
+#[test]
+fn groth16_verification_optimized_reads_registered_keys() {
+	new_test_ext().execute_with(|| {
+		let (vk, c, proof) = groth16_single_input_fixture();
+		let post_info = TemplateModule::groth16_verification_optimized(
+			RuntimeOrigin::signed(1),
+			vk.clone(),
+			c.clone(),
+			proof.clone(),
+		)
+		.unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<() as WeightInfo>::groth16_verification_optimized())
+		);
+
+		assert_ok!(TemplateModule::groth16_prepare_verifying_key(
+			RuntimeOrigin::signed(1),
+			vk.clone()
+		));
+		System::assert_last_event(Event::Groth16VerifyingKeyRegistered { id: 0, who: 1 }.into());
+		let pvk = bls12_381::prepare_registered_verifying_key_groth16(&vk).unwrap();
+		assert_eq!(Groth16PreparedVerifyingKeys::<Test>::get(0).unwrap().into_inner(), pvk);
+		assert_eq!(Groth16VerifyingKeyIds::<Test>::get(BlakeTwo256::hash(&vk)), Some(0));
+
+		// Registered keys are only used once approved.
+		assert_noop!(
+			TemplateModule::groth16_verification_optimized(
+				RuntimeOrigin::signed(1),
+				vk.clone(),
+				c.clone(),
+				proof.clone()
+			),
+			Error::<Test>::VerifyingKeyNotApproved
+		);
+		assert_ok!(TemplateModule::groth16_approve_vk(RuntimeOrigin::root(), 0));
+		let post_info = TemplateModule::groth16_verification_optimized(
+			RuntimeOrigin::signed(1),
+			vk.clone(),
+			c.clone(),
+			proof.clone(),
+		)
+		.unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<() as WeightInfo>::groth16_verification_optimized_prepared())
+		);
+		// The proof is checked against the stored prepared key, not against `vk`.
+		let (other_vk, _, _) = groth16_cube_fixture(3, 1);
+		let other_pvk = bls12_381::prepare_registered_verifying_key_groth16(&other_vk).unwrap();
+		Groth16PreparedVerifyingKeys::<Test>::insert(0, BoundedVec::try_from(other_pvk).unwrap());
+		assert_noop!(
+			TemplateModule::groth16_verification_optimized(
+				RuntimeOrigin::signed(1),
+				vk.clone(),
+				c.clone(),
+				proof.clone()
+			),
+			Error::<Test>::VerificationFailed
+		);
+		Groth16PreparedVerifyingKeys::<Test>::insert(0, BoundedVec::try_from(pvk).unwrap());
+		assert_noop!(
+			TemplateModule::groth16_verification_optimized(
+				RuntimeOrigin::signed(1),
+				vk.clone(),
+				utils::serialize_argument(BlsFr::from(1u64)),
+				proof.clone()
+			),
+			Error::<Test>::VerificationFailed
+		);
+		assert_noop!(
+			TemplateModule::groth16_verification_optimized(
+				RuntimeOrigin::signed(1),
+				vk.clone(),
+				Vec::new(),
+				proof.clone()
+			),
+			Error::<Test>::InvalidPublicInputs
+		);
+
+		assert_ok!(TemplateModule::groth16_freeze_vk(RuntimeOrigin::root(), 0));
+		assert_noop!(
+			TemplateModule::groth16_verification_optimized(
+				RuntimeOrigin::signed(1),
+				vk.clone(),
+				c.clone(),
+				proof.clone()
+			),
+			Error::<Test>::VerifyingKeyFrozen
+		);
+		assert_ok!(TemplateModule::groth16_deprecate_vk(RuntimeOrigin::root(), 0));
+		assert_noop!(
+			TemplateModule::groth16_verification_optimized(RuntimeOrigin::signed(1), vk, c, proof),
+			Error::<Test>::VerifyingKeyNotApproved
+		);
+	});
+}
+
+#[test]
+fn groth16_verification_accepts_valid_proofs() {
+	let statements =
+		[groth16_single_input_fixture(), groth16_cube_fixture(3, 1), groth16_cube_fixture(42, 2)];
+	for (verify, backend) in groth16_verifications() {
+		new_test_ext().execute_with(|| {
+			for (vk, c, proof) in statements.clone() {
+				assert_ok!(verify(RuntimeOrigin::signed(1), vk.clone(), c.clone(), proof.clone()));
+				System::assert_last_event(
+					Event::Groth16Verified {
+						who: 1,
+						vk_hash: BlakeTwo256::hash(&vk),
+						inputs_hash: BlakeTwo256::hash(&c),
+						proof_hash: BlakeTwo256::hash(&proof),
+						backend,
+					}
+					.into(),
+				);
+			}
+		});
+	}
+}
+
+#[test]
+fn groth16_verification_rejects_invalid_proofs() {
+	let (vk, c, proof) = groth16_single_input_fixture();
+	let (other_vk, other_c, other_proof) = groth16_cube_fixture(3, 1);
+	let (_, _, proof_of_other_x) = groth16_cube_fixture(4, 1);
+	let wrong_c = utils::serialize_argument(
+		BlsFr::deserialize_uncompressed(&c[..]).unwrap() + BlsFr::from(1u64),
+	);
+	let mut mixed_proof = other_proof.clone();
+	mixed_proof[..96].copy_from_slice(&proof_of_other_x[..96]);
+
+	for (verify, _) in groth16_verifications() {
+		new_test_ext().execute_with(|| {
+			let rejects = |vk: &[u8], c: &[u8], proof: &[u8], error: Error<Test>| {
+				assert_noop!(
+					verify(RuntimeOrigin::signed(1), vk.to_vec(), c.to_vec(), proof.to_vec()),
+					error
+				);
+			};
+			// Flipped bits leave the points of the proof off the curve.
+			for i in [0, 47, 100, 200, 287] {
+				let mut tampered = proof.clone();
+				tampered[i] ^= 1;
+				rejects(&vk, &c, &tampered, Error::<Test>::InvalidGroth16Proof);
+			}
+			// Valid points that do not form a proof of the statement.
+			rejects(&other_vk, &other_c, &mixed_proof, Error::<Test>::VerificationFailed);
+			rejects(&other_vk, &other_c, &proof_of_other_x, Error::<Test>::VerificationFailed);
+			rejects(&vk, &wrong_c, &proof, Error::<Test>::VerificationFailed);
+			rejects(&other_vk, &c, &proof, Error::<Test>::VerificationFailed);
+
+			rejects(&vk[..vk.len() - 1], &c, &proof, Error::<Test>::InvalidGroth16VerifyingKey);
+			rejects(&vk, &c[..31], &proof, Error::<Test>::InvalidPublicInputs);
+			rejects(&vk, &c, &proof[..proof.len() - 1], Error::<Test>::InvalidGroth16Proof);
+			rejects(&vk, &c, &[], Error::<Test>::InvalidGroth16Proof);
+
+			assert_noop!(
+				verify(RuntimeOrigin::none(), vk.clone(), c.clone(), proof.clone()),
+				DispatchError::BadOrigin
+			);
+		});
+	}
+}
+
+#[test]
+fn groth16_backends_agree() {
+	let (vk, c, proof) = groth16_single_input_fixture();
+	let (other_vk, other_c, other_proof) = groth16_cube_fixture(7, 3);
+	let mut tampered = proof.clone();
+	tampered[100] ^= 1;
+	let cases = [
+		(vk.clone(), c.clone(), proof.clone()),
+		(other_vk.clone(), other_c.clone(), other_proof.clone()),
+		(other_vk.clone(), c.clone(), proof.clone()),
+		(vk.clone(), other_c.clone(), proof.clone()),
+		(vk.clone(), c.clone(), other_proof),
+		(vk.clone(), c.clone(), tampered),
+		(vk[1..].to_vec(), c.clone(), proof.clone()),
+	];
+	new_test_ext().execute_with(|| {
+		for (vk, c, proof) in cases {
+			assert_eq!(
+				bls12_381::do_verify_groth16(vk.clone(), c.clone(), proof.clone()),
+				bls12_381::do_verify_groth16_optimized(vk.clone(), c.clone(), proof.clone())
+			);
+		}
+
+		// The prepared keys differ in encoding, the prepared inputs do not.
+		let pvk = bls12_381::prepare_verifying_key_groth16::<ark_bls12_381::Bls12_381>(vk.clone())
+			.unwrap();
+		let pvk_optimized =
+			bls12_381::prepare_verifying_key_groth16::<bls12_381::Bls12_381Optimized>(vk).unwrap();
+		for c in [c, other_c] {
+			let inputs = bls12_381::prepare_inputs_groth16::<ark_bls12_381::Bls12_381>(
+				pvk.clone(),
+				c.clone(),
+			)
+			.unwrap();
+			let inputs_optimized =
+				bls12_381::prepare_inputs_groth16::<bls12_381::Bls12_381Optimized>(
+					pvk_optimized.clone(),
+					c,
+				)
+				.unwrap();
+			assert_eq!(inputs, inputs_optimized);
+			assert_eq!(
+				bls12_381::verify_with_prepared_inputs_groth16::<ark_bls12_381::Bls12_381>(
+					inputs.clone(),
+					pvk.clone(),
+					proof.clone()
+				),
+				bls12_381::verify_with_prepared_inputs_groth16::<bls12_381::Bls12_381Optimized>(
+					inputs,
+					pvk_optimized.clone(),
+					proof.clone()
+				)
+			);
+		}
+	});
+}
+
+#[cfg(feature = "experimental")]
+#[test]
+fn groth16_verification_host_agrees_with_the_runtime_backends() {
+	let (vk, c, proof) = groth16_single_input_fixture();
+	let (other_vk, other_c, other_proof) = groth16_cube_fixture(7, 3);
+	let mut tampered = proof.clone();
+	tampered[100] ^= 1;
+	let cases = [
+		(vk.clone(), c.clone(), proof.clone()),
+		(other_vk.clone(), other_c.clone(), other_proof.clone()),
+		(other_vk, c.clone(), proof.clone()),
+		(vk.clone(), other_c, proof.clone()),
+		(vk.clone(), c.clone(), other_proof),
+		(vk.clone(), c.clone(), tampered),
+		(vk[1..].to_vec(), c.clone(), proof.clone()),
+		(vk.clone(), c[..31].to_vec(), proof.clone()),
+	];
+	new_test_ext().execute_with(|| {
+		for (vk, inputs, proof) in cases {
+			let valid = bls12_381::do_verify_groth16(vk.clone(), inputs.clone(), proof.clone())
+				.unwrap_or(false);
+			let result = TemplateModule::groth16_verification_host(
+				RuntimeOrigin::signed(1),
+				vk.clone(),
+				inputs.clone(),
+				proof.clone(),
+			);
+			if valid {
+				assert_ok!(result);
+				System::assert_last_event(
+					Event::Groth16Verified {
+						who: 1,
+						vk_hash: BlakeTwo256::hash(&vk),
+						inputs_hash: BlakeTwo256::hash(&inputs),
+						proof_hash: BlakeTwo256::hash(&proof),
+						backend: Groth16Backend::HostGroth16,
+					}
+					.into(),
+				);
+			} else {
+				assert_eq!(result, Err(Error::<Test>::VerificationFailed.into()));
+			}
+		}
+
+		// The other backends ignore the bytes after the single input, this one reads them as a
+		// second input the key does not have.
+		assert_noop!(
+			TemplateModule::groth16_verification_host(
+				RuntimeOrigin::signed(1),
+				vk,
+				[&c[..], &c[..]].concat(),
+				proof
+			),
+			Error::<Test>::VerificationFailed
+		);
+	});
+}
+
+#[cfg(feature = "experimental")]
+#[test]
+fn groth16_verification_host_bounds_its_arguments() {
+	let (vk, c, proof) = groth16_single_input_fixture();
+	let verify = |vk: &[u8], inputs: &[u8], proof: &[u8]| {
+		TemplateModule::groth16_verification_host(
+			RuntimeOrigin::signed(1),
+			vk.to_vec(),
+			inputs.to_vec(),
+			proof.to_vec(),
+		)
+	};
+	new_test_ext().execute_with(|| {
+		let long_vk = [&vk[..], &[0; bls12_381::GROTH16_MAX_VERIFYING_KEY_LEN as usize]].concat();
+		assert_noop!(verify(&long_vk, &c, &proof), Error::<Test>::InvalidGroth16VerifyingKey);
+		let many_inputs = c.repeat(bls12_381::GROTH16_MAX_PUBLIC_INPUTS as usize + 1);
+		assert_noop!(verify(&vk, &many_inputs, &proof), Error::<Test>::InvalidPublicInputs);
+		assert_noop!(verify(&vk, &c, &proof[1..]), Error::<Test>::InvalidGroth16Proof);
+		let long_proof = [&proof[..], &[0]].concat();
+		assert_noop!(verify(&vk, &c, &long_proof), Error::<Test>::InvalidGroth16Proof);
+		// The most inputs a key may have are within the bounds.
+		let inputs = c.repeat(bls12_381::GROTH16_MAX_PUBLIC_INPUTS as usize);
+		assert_noop!(verify(&vk, &inputs, &proof), Error::<Test>::VerificationFailed);
+	});
+}
+
+#[test]
+fn groth16_prepared_calls_check_their_arguments() {
+	new_test_ext().execute_with(|| {
+		let (vk, inputs, proof) = groth16_fixture();
+		let pvk =
+			bls12_381::prepare_verifying_key_groth16::<bls12_381::Bls12_381Optimized>(vk.clone())
+				.unwrap();
+
+		assert_ok!(TemplateModule::groth16_optimized_prepare_verifying_key(
+			RuntimeOrigin::signed(1),
+			vk.clone()
+		));
+		assert_eq!(Groth16PreparedVerifyingKeys::<Test>::get(0).unwrap().into_inner(), pvk);
+		assert_noop!(
+			TemplateModule::groth16_prepare_verifying_key(
+				RuntimeOrigin::signed(1),
+				vk[..vk.len() - 1].to_vec()
+			),
+			Error::<Test>::InvalidGroth16VerifyingKey
+		);
+		assert_noop!(
+			TemplateModule::groth16_optimized_prepare_inputs(
+				RuntimeOrigin::signed(1),
+				0,
+				inputs[..inputs.len() - 1].to_vec()
+			),
+			Error::<Test>::InvalidPublicInputs
+		);
+		assert_noop!(
+			TemplateModule::groth16_prepare_inputs(RuntimeOrigin::signed(1), 1, inputs.clone()),
+			Error::<Test>::UnknownVerifyingKey
+		);
+		assert_noop!(
+			TemplateModule::groth16_optimized_prepare_inputs(RuntimeOrigin::none(), 0, inputs),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TemplateModule::groth16_verify_with_prepared_inputs(RuntimeOrigin::signed(1), 0, proof),
+			Error::<Test>::UnknownPreparedInputs
+		);
+	});
+}
+
+#[test]
+fn groth16_prepared_inputs_agree_across_curves() {
+	new_test_ext().execute_with(|| {
+		let (vk, inputs, proof) = groth16_fixture();
+		assert_ok!(TemplateModule::groth16_register_vk(RuntimeOrigin::signed(1), vk.clone()));
+		assert_ok!(TemplateModule::groth16_prepare_inputs(
+			RuntimeOrigin::signed(1),
+			0,
+			inputs.clone()
+		));
+		assert_ok!(TemplateModule::groth16_optimized_prepare_inputs(
+			RuntimeOrigin::signed(1),
+			0,
+			inputs.clone()
+		));
+		assert_eq!(
+			crate::PreparedInputs::<Test>::get(0).unwrap().1,
+			crate::PreparedInputs::<Test>::get(1).unwrap().1
+		);
+
+		// Either curve verifies inputs prepared by the other.
+		assert_ok!(TemplateModule::groth16_verify_with_prepared_inputs(
+			RuntimeOrigin::signed(1),
+			1,
+			proof.clone()
+		));
+		System::assert_last_event(
+			Event::Groth16Verified {
+				who: 1,
+				vk_hash: BlakeTwo256::hash(&vk),
+				inputs_hash: BlakeTwo256::hash(&inputs),
+				proof_hash: BlakeTwo256::hash(&proof),
+				backend: Groth16Backend::Bls12_381,
+			}
+			.into(),
+		);
+		assert_ok!(TemplateModule::groth16_optimized_verify_with_prepared_inputs(
+			RuntimeOrigin::signed(1),
+			0,
+			proof.clone()
+		));
+		System::assert_last_event(groth16_event(1, true, &vk, &inputs, &proof));
+		assert_eq!(VerifiedProofs::get(), vec![(1, 0, inputs.clone()), (1, 0, inputs)]);
+	});
+}
+
+#[test]
+fn bls_verify_min_pk() {
+	new_test_ext().execute_with(|| {
+		let secret_key = BlsFrOptimized::from(42u64);
+		let public_key = bls_signatures::public_key::<MinPk>(secret_key);
+		let signature = bls_signatures::sign::<MinPk>(secret_key, b"hello", MinPk::DST).unwrap();
+
+		assert_ok!(TemplateModule::bls_verify(
+			RuntimeOrigin::signed(1),
+			public_key.clone(),
+			b"hello".to_vec(),
+			signature.clone(),
+		));
+		System::assert_last_event(
+			Event::BlsSignatureVerified {
+				who: 1,
+				public_keys_hash: BlakeTwo256::hash(&public_key),
+				messages_hash: BlakeTwo256::hash(b"hello"),
+				signature_hash: BlakeTwo256::hash(&signature),
+			}
+			.into(),
+		);
+		assert_noop!(
+			TemplateModule::bls_verify(
+				RuntimeOrigin::signed(1),
+				public_key,
+				b"world".to_vec(),
+				signature
+			),
+			Error::<Test>::SignatureVerificationFailed
+		);
+	});
+}
+
+#[test]
+fn bls_verify_min_sig() {
+	new_test_ext().execute_with(|| {
+		let secret_key = BlsFrOptimized::from(42u64);
+		let public_key = bls_signatures::public_key::<MinSig>(secret_key);
+		let signature = bls_signatures::sign::<MinSig>(secret_key, b"hello", MinSig::DST).unwrap();
+
+		assert_ok!(TemplateModule::bls_verify_min_sig(
+			RuntimeOrigin::signed(1),
+			public_key.clone(),
+			b"hello".to_vec(),
+			signature.clone(),
+		));
+		assert_noop!(
+			TemplateModule::bls_verify_min_sig(
+				RuntimeOrigin::signed(1),
+				public_key,
+				b"world".to_vec(),
+				signature
+			),
+			Error::<Test>::SignatureVerificationFailed
+		);
+	});
+}
+
+#[test]
+fn bls_aggregate_verify() {
+	new_test_ext().execute_with(|| {
+		let secret_keys: Vec<_> = (1..=4u64).map(BlsFrOptimized::from).collect();
+		let messages: Vec<Vec<u8>> = (0..4u8).map(|i| vec![i; 32]).collect();
+		let public_keys: Vec<_> =
+			secret_keys.iter().map(|sk| bls_signatures::public_key::<MinPk>(*sk)).collect();
+		let signatures: Vec<_> = secret_keys
+			.iter()
+			.zip(&messages)
+			.map(|(sk, m)| bls_signatures::sign::<MinPk>(*sk, m, MinPk::DST).unwrap())
+			.collect();
+		let signature = bls_signatures::aggregate_signatures::<MinPk>(&signatures).unwrap();
+
+		assert_ok!(TemplateModule::bls_aggregate_verify(
+			RuntimeOrigin::signed(1),
+			public_keys.clone(),
+			messages.clone(),
+			signature.clone(),
+		));
+		assert_noop!(
+			TemplateModule::bls_aggregate_verify(
+				RuntimeOrigin::signed(1),
+				public_keys[1..].to_vec(),
+				messages.clone(),
+				signature.clone()
+			),
+			Error::<Test>::LengthMismatch
+		);
+		assert_noop!(
+			TemplateModule::bls_aggregate_verify(
+				RuntimeOrigin::signed(1),
+				vec![public_keys[0].clone(); bls_signatures::MAX_AGGREGATE_SIGNERS as usize + 1],
+				vec![messages[0].clone(); bls_signatures::MAX_AGGREGATE_SIGNERS as usize + 1],
+				signature
+			),
+			Error::<Test>::TooManyPublicKeys
+		);
+	});
+}
+
+#[test]
+fn bls_fast_aggregate_verify() {
+	new_test_ext().execute_with(|| {
+		let secret_keys: Vec<_> = (1..=4u64).map(BlsFrOptimized::from).collect();
+		let public_keys: Vec<_> =
+			secret_keys.iter().map(|sk| bls_signatures::public_key::<MinSig>(*sk)).collect();
+		let signatures: Vec<_> = secret_keys
+			.iter()
+			.map(|sk| bls_signatures::sign::<MinSig>(*sk, b"attestation", MinSig::DST).unwrap())
+			.collect();
+		let signature = bls_signatures::aggregate_signatures::<MinSig>(&signatures).unwrap();
+
+		assert_ok!(TemplateModule::bls_fast_aggregate_verify_min_sig(
+			RuntimeOrigin::signed(1),
+			public_keys.clone(),
+			b"attestation".to_vec(),
+			signature.clone(),
+		));
+		assert_noop!(
+			TemplateModule::bls_fast_aggregate_verify_min_sig(
+				RuntimeOrigin::signed(1),
+				public_keys[1..].to_vec(),
+				b"attestation".to_vec(),
+				signature.clone()
+			),
+			Error::<Test>::SignatureVerificationFailed
+		);
+		assert_noop!(
+			TemplateModule::bls_fast_aggregate_verify_min_sig(
+				RuntimeOrigin::signed(1),
+				vec![vec![0u8; 96]],
+				b"attestation".to_vec(),
+				signatures[0].clone()
+			),
+			Error::<Test>::InvalidPublicKey
+		);
+		assert_noop!(
+			TemplateModule::bls_fast_aggregate_verify_min_sig(
+				RuntimeOrigin::signed(1),
+				vec![
+					public_keys[0].clone();
+					bls_signatures::MAX_FAST_AGGREGATE_SIGNERS as usize + 1
+				],
+				b"attestation".to_vec(),
+				signature
+			),
+			Error::<Test>::TooManyPublicKeys
+		);
+	});
+}
+
+fn fq(hex: &str) -> ark_bls12_381::Fq {
+	ark_bls12_381::Fq::from_be_bytes_mod_order(&hex::decode(&hex[2..]).unwrap())
+}
+
+fn fq2(hex: &str) -> ark_bls12_381::Fq2 {
+	let (c0, c1) = hex.split_once(',').unwrap();
+	ark_bls12_381::Fq2::new(fq(c0), fq(c1))
+}
+
+#[test]
+fn hash_to_g1_rfc9380_vectors() {
+	let suite: serde_json::Value = serde_json::from_str(include_str!(
+		"../fixtures/hash_to_curve/BLS12381G1_XMD-SHA-256_SSWU_RO_.json"
+	))
+	.unwrap();
+	let dst = suite["dst"].as_str().unwrap().as_bytes();
+
+	for vector in suite["vectors"].as_array().unwrap() {
+		let msg = vector["msg"].as_str().unwrap().as_bytes();
+		let u: Vec<_> = vector["u"]
+			.as_array()
+			.unwrap()
+			.iter()
+			.map(|u| fq(u.as_str().unwrap()))
+			.collect();
+		assert_eq!(hash_to_curve::hash_to_fq(msg, dst, 2).unwrap(), u);
+
+		let expected = ark_bls12_381::G1Affine::new_unchecked(
+			fq(vector["P"]["x"].as_str().unwrap()),
+			fq(vector["P"]["y"].as_str().unwrap()),
+		);
+		let p = hash_to_curve::hash_to_g1(msg, dst).unwrap();
+		assert_eq!(utils::serialize_argument(p), utils::serialize_argument(expected));
+	}
+}
+
+#[test]
+fn hash_to_g2_rfc9380_vectors() {
+	let suite: serde_json::Value = serde_json::from_str(include_str!(
+		"../fixtures/hash_to_curve/BLS12381G2_XMD-SHA-256_SSWU_RO_.json"
+	))
+	.unwrap();
+	let dst = suite["dst"].as_str().unwrap().as_bytes();
+
+	for vector in suite["vectors"].as_array().unwrap() {
+		let msg = vector["msg"].as_str().unwrap().as_bytes();
+		let u: Vec<_> = vector["u"]
+			.as_array()
+			.unwrap()
+			.iter()
+			.map(|u| fq2(u.as_str().unwrap()))
+			.collect();
+		assert_eq!(hash_to_curve::hash_to_fq2(msg, dst, 2).unwrap(), u);
+
+		let expected = ark_bls12_381::G2Affine::new_unchecked(
+			fq2(vector["P"]["x"].as_str().unwrap()),
+			fq2(vector["P"]["y"].as_str().unwrap()),
+		);
+		let p = hash_to_curve::hash_to_g2(msg, dst).unwrap();
+		assert_eq!(utils::serialize_argument(p), utils::serialize_argument(expected));
+	}
+}
 
 #[test]
-fn groth16_verification() {
+fn expand_message_xmd_limits() {
+	assert_eq!(hash_to_curve::expand_message_xmd(b"", b"DST", 32).unwrap().len(), 32);
+	assert_eq!(hash_to_curve::expand_message_xmd(b"", &[0x42; 300], 0x80).unwrap().len(), 0x80);
+	assert_eq!(
+		hash_to_curve::expand_message_xmd(b"", b"DST", 255 * 32 + 1),
+		Err(hash_to_curve::Error::OutputTooLong)
+	);
+}
+
+#[derive(serde::Deserialize)]
+struct KzgVector {
+	input: KzgInput,
+	output: Option<bool>,
+}
+
+#[derive(serde::Deserialize)]
+struct KzgInput {
+	commitment: String,
+	z: String,
+	y: String,
+	proof: String,
+}
+
+impl KzgInput {
+	fn decode(&self) -> [Vec<u8>; 4] {
+		[&self.commitment, &self.z, &self.y, &self.proof]
+			.map(|field| hex::decode(field.trim_start_matches("0x")).unwrap())
+	}
+}
+
+fn kzg_vectors() -> Vec<(String, KzgVector)> {
+	let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/kzg/verify_kzg_proof");
+	let mut vectors: Vec<_> = std::fs::read_dir(dir)
+		.unwrap()
+		.map(|case| {
+			let path = case.unwrap().path();
+			let data = std::fs::read_to_string(path.join("data.yaml")).unwrap();
+			(
+				path.file_name().unwrap().to_string_lossy().into_owned(),
+				serde_yaml::from_str(&data).unwrap(),
+			)
+		})
+		.collect();
+	vectors.sort_by(|a, b| a.0.cmp(&b.0));
+	vectors
+}
+
+#[test]
+fn kzg_verify_proof_vectors() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(TemplateModule::groth16_verification(RuntimeOrigin::signed(1)));
+		for (name, vector) in kzg_vectors() {
+			let [commitment, z, y, proof] = vector.input.decode();
+			let result =
+				TemplateModule::verify_kzg_proof(RuntimeOrigin::signed(1), commitment, z, y, proof);
+			match vector.output {
+				Some(true) => assert!(result.is_ok(), "{}: {:?}", name, result),
+				Some(false) =>
+					assert_eq!(result, Err(Error::<Test>::KzgVerificationFailed.into()), "{}", name),
+				// The case names of the official vectors also start with the invalid input.
+				None if name.contains("_invalid_z_") || name.contains("_invalid_y_") =>
+					assert_eq!(result, Err(Error::<Test>::InvalidKzgScalar.into()), "{}", name),
+				None => assert_eq!(result, Err(Error::<Test>::InvalidKzgPoint.into()), "{}", name),
+			}
+		}
 	});
 }
+
 #[test]
-fn groth16_verificaton_optimized() {
+fn kzg_verify_proof_batch() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(TemplateModule::groth16_verification_optimized(RuntimeOrigin::signed(1)));
+		let vectors = kzg_vectors();
+		let valid: Vec<_> = vectors
+			.iter()
+			.filter(|(_, vector)| vector.output == Some(true))
+			.map(|(_, vector)| vector.input.decode())
+			.collect();
+		let invalid: Vec<_> = vectors
+			.iter()
+			.filter(|(_, vector)| vector.output == Some(false))
+			.map(|(_, vector)| vector.input.decode())
+			.collect();
+		let columns = |openings: &[[Vec<u8>; 4]]| {
+			let column = |i: usize| openings.iter().map(|o| o[i].clone()).collect::<Vec<_>>();
+			(column(0), column(1), column(2), column(3))
+		};
+
+		let (commitments, zs, ys, proofs) = columns(&valid);
+		assert_ok!(TemplateModule::verify_kzg_proof_batch(
+			RuntimeOrigin::signed(1),
+			commitments.clone(),
+			zs.clone(),
+			ys.clone(),
+			proofs.clone()
+		));
+		System::assert_last_event(
+			Event::KzgVerified {
+				who: 1,
+				commitments_hash: BlakeTwo256::hash_of(&commitments),
+				inputs_hash: BlakeTwo256::hash_of(&(zs.clone(), ys.clone())),
+				proof_hash: BlakeTwo256::hash_of(&proofs),
+			}
+			.into(),
+		);
+		assert_noop!(
+			TemplateModule::verify_kzg_proof_batch(
+				RuntimeOrigin::signed(1),
+				commitments.clone(),
+				zs[1..].to_vec(),
+				ys.clone(),
+				proofs.clone()
+			),
+			Error::<Test>::LengthMismatch
+		);
+		assert_noop!(
+			TemplateModule::verify_kzg_proof_batch(
+				RuntimeOrigin::signed(1),
+				Vec::new(),
+				Vec::new(),
+				Vec::new(),
+				Vec::new()
+			),
+			Error::<Test>::EmptyKzgBatch
+		);
+		let too_large = kzg::MAX_BATCH_SIZE as usize + 1;
+		assert_noop!(
+			TemplateModule::verify_kzg_proof_batch(
+				RuntimeOrigin::signed(1),
+				vec![commitments[0].clone(); too_large],
+				vec![zs[0].clone(); too_large],
+				vec![ys[0].clone(); too_large],
+				vec![proofs[0].clone(); too_large]
+			),
+			Error::<Test>::KzgBatchTooLarge
+		);
+
+		for opening in &invalid {
+			let mut batch = valid.clone();
+			batch.insert(1, opening.clone());
+			let (commitments, zs, ys, proofs) = columns(&batch);
+			assert_noop!(
+				TemplateModule::verify_kzg_proof_batch(
+					RuntimeOrigin::signed(1),
+					commitments,
+					zs,
+					ys,
+					proofs
+				),
+				Error::<Test>::KzgVerificationFailed
+			);
+		}
+	});
+}
+
+#[test]
+fn kzg_set_trusted_setup() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(KzgTrustedSetup::<Test>::get(), kzg::ETHEREUM_SETUP_G2);
+		let generator = hex::decode(
+			"93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8",
+		)
+		.unwrap();
+
+		assert_noop!(
+			TemplateModule::kzg_set_trusted_setup(RuntimeOrigin::signed(1), generator.clone()),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TemplateModule::kzg_set_trusted_setup(RuntimeOrigin::root(), generator[..95].to_vec()),
+			Error::<Test>::InvalidTrustedSetup
+		);
+		assert_ok!(TemplateModule::kzg_set_trusted_setup(RuntimeOrigin::root(), generator));
+
+		// Openings of constant polynomials do not depend on the setup, all others fail now.
+		for (name, vector) in kzg_vectors() {
+			let [commitment, z, y, proof] = vector.input.decode();
+			let result =
+				TemplateModule::verify_kzg_proof(RuntimeOrigin::signed(1), commitment, z, y, proof);
+			if name.contains("point_at_infinity") {
+				assert!(result.is_ok(), "{}: {:?}", name, result);
+			} else {
+				assert!(result.is_err(), "{}", name);
+			}
+		}
 	});
 }
+
+/// Returns the uncompressed verifying key, public inputs, proof and wrong public inputs of a
+/// PLONK proof for the cube circuit, and sets the universal setup of the key for `curve`.
+fn plonk_fixture<E: Pairing>(curve: plonk::Curve) -> (Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>) {
+	let circuit = prover::cube_circuit::<E::ScalarField>(3, 2);
+	let pk = prover::setup::<E>(&circuit, E::ScalarField::from(0x5eedu64));
+	let proof = prover::prove(&pk, &circuit);
+	let public_inputs = circuit.public_inputs();
+	let wrong_inputs: Vec<_> = public_inputs.iter().map(|input| *input + input).collect();
+	assert_ok!(TemplateModule::plonk_set_srs(
+		RuntimeOrigin::root(),
+		curve,
+		utils::serialize_argument(pk.vk.x_2)
+	));
+	(
+		utils::serialize_argument(pk.vk),
+		utils::serialize_argument(public_inputs),
+		utils::serialize_argument(proof),
+		utils::serialize_argument(wrong_inputs),
+	)
+}
+
+#[test]
+fn plonk_verify_bls12_381() {
+	new_test_ext().execute_with(|| {
+		let (vk, inputs, proof, wrong_inputs) =
+			plonk_fixture::<ark_bls12_381::Bls12_381>(plonk::Curve::Bls12_381);
+		assert_ok!(TemplateModule::plonk_register_vk(
+			RuntimeOrigin::signed(1),
+			plonk::Curve::Bls12_381,
+			vk
+		));
+		System::assert_last_event(Event::PlonkVerifyingKeyRegistered { id: 0, who: 1 }.into());
+		assert_eq!(Balances::reserved_balance(1), 100);
+
+		assert_ok!(TemplateModule::plonk_verify(
+			RuntimeOrigin::signed(1),
+			0,
+			inputs.clone(),
+			proof.clone()
+		));
+		System::assert_last_event(
+			Event::PlonkVerified {
+				who: 1,
+				vk_id: 0,
+				inputs_hash: BlakeTwo256::hash(&inputs),
+				proof_hash: BlakeTwo256::hash(&proof),
+			}
+			.into(),
+		);
+		assert_ok!(TemplateModule::plonk_verify_optimized(
+			RuntimeOrigin::signed(1),
+			0,
+			inputs.clone(),
+			proof.clone()
+		));
+		for verify in [TemplateModule::plonk_verify, TemplateModule::plonk_verify_optimized] {
+			assert_noop!(
+				verify(RuntimeOrigin::signed(1), 0, wrong_inputs.clone(), proof.clone()),
+				Error::<Test>::PlonkVerificationFailed
+			);
+			assert_noop!(
+				verify(RuntimeOrigin::signed(1), 0, inputs.clone(), proof[1..].to_vec()),
+				Error::<Test>::InvalidPlonkProof
+			);
+			assert_noop!(
+				verify(RuntimeOrigin::signed(1), 1, inputs.clone(), proof.clone()),
+				Error::<Test>::UnknownVerifyingKey
+			);
+		}
+	});
+}
+
+#[test]
+fn plonk_verify_bn254() {
+	new_test_ext().execute_with(|| {
+		let (vk, inputs, proof, wrong_inputs) =
+			plonk_fixture::<ark_bn254::Bn254>(plonk::Curve::Bn254);
+		assert_ok!(TemplateModule::plonk_register_vk(
+			RuntimeOrigin::signed(1),
+			plonk::Curve::Bn254,
+			vk
+		));
+
+		assert_ok!(TemplateModule::plonk_verify(
+			RuntimeOrigin::signed(1),
+			0,
+			inputs.clone(),
+			proof.clone()
+		));
+		assert_noop!(
+			TemplateModule::plonk_verify(RuntimeOrigin::signed(1), 0, wrong_inputs, proof.clone()),
+			Error::<Test>::PlonkVerificationFailed
+		);
+		assert_noop!(
+			TemplateModule::plonk_verify_optimized(RuntimeOrigin::signed(1), 0, inputs, proof),
+			Error::<Test>::UnsupportedCurve
+		);
+	});
+}
+
+#[test]
+fn plonk_register_vk_bounds_the_verification_work() {
+	type E = ark_bls12_381::Bls12_381;
+	new_test_ext().execute_with(|| {
+		// Sets the universal setup all keys below are set up with.
+		let (vk, ..) = plonk_fixture::<E>(plonk::Curve::Bls12_381);
+		let register = |num_public_inputs: usize| {
+			let circuit = prover::cube_circuit_with_inputs::<BlsFr>(3, num_public_inputs);
+			let pk = prover::setup::<E>(&circuit, BlsFr::from(0x5eedu64));
+			TemplateModule::plonk_register_vk(
+				RuntimeOrigin::signed(1),
+				plonk::Curve::Bls12_381,
+				utils::serialize_argument(pk.vk),
+			)
+		};
+		let max = plonk::MAX_PUBLIC_INPUTS as usize;
+		assert_ok!(register(max));
+		assert_noop!(register(max + 1), Error::<Test>::InvalidPlonkVerifyingKey);
+
+		let mut vk = plonk::VerifyingKey::<E>::deserialize_uncompressed(&vk[..]).unwrap();
+		vk.domain_size = 2 * plonk::MAX_DOMAIN_SIZE;
+		assert_noop!(
+			TemplateModule::plonk_register_vk(
+				RuntimeOrigin::signed(1),
+				plonk::Curve::Bls12_381,
+				utils::serialize_argument(vk)
+			),
+			Error::<Test>::InvalidPlonkVerifyingKey
+		);
+	});
+}
+
+#[test]
+fn plonk_register_vk_rejects_invalid_keys() {
+	new_test_ext().execute_with(|| {
+		let (vk, ..) = plonk_fixture::<ark_bls12_381::Bls12_381>(plonk::Curve::Bls12_381);
+		assert_noop!(
+			TemplateModule::plonk_register_vk(
+				RuntimeOrigin::signed(1),
+				plonk::Curve::Bn254,
+				vk.clone()
+			),
+			Error::<Test>::PlonkSrsNotSet
+		);
+		// A BLS12-381 key does not decode as a BN254 key.
+		plonk_fixture::<ark_bn254::Bn254>(plonk::Curve::Bn254);
+		assert_noop!(
+			TemplateModule::plonk_register_vk(
+				RuntimeOrigin::signed(1),
+				plonk::Curve::Bn254,
+				vk.clone()
+			),
+			Error::<Test>::InvalidPlonkVerifyingKey
+		);
+		assert_noop!(
+			TemplateModule::plonk_register_vk(
+				RuntimeOrigin::signed(1),
+				plonk::Curve::Bls12_381,
+				vk[..vk.len() - 1].to_vec()
+			),
+			Error::<Test>::InvalidPlonkVerifyingKey
+		);
+		// Without the deposit the key is not registered.
+		assert!(TemplateModule::plonk_register_vk(
+			RuntimeOrigin::signed(3),
+			plonk::Curve::Bls12_381,
+			vk
+		)
+		.is_err());
+		assert_eq!(NextPlonkVerifyingKeyId::<Test>::get(), 0);
+	});
+}
+
+#[test]
+fn plonk_register_vk_checks_the_srs() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::plonk_set_srs(
+				RuntimeOrigin::signed(1),
+				plonk::Curve::Bls12_381,
+				vec![]
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TemplateModule::plonk_set_srs(
+				RuntimeOrigin::root(),
+				plonk::Curve::Bls12_381,
+				vec![1; 192]
+			),
+			Error::<Test>::InvalidPlonkSrs
+		);
+
+		let (vk, ..) = plonk_fixture::<ark_bls12_381::Bls12_381>(plonk::Curve::Bls12_381);
+		System::assert_last_event(Event::PlonkSrsSet { curve: plonk::Curve::Bls12_381 }.into());
+		// A key of another setup is rejected.
+		let other = prover::setup::<ark_bls12_381::Bls12_381>(
+			&prover::cube_circuit(3, 2),
+			ark_bls12_381::Fr::from(7u64),
+		);
+		assert_noop!(
+			TemplateModule::plonk_register_vk(
+				RuntimeOrigin::signed(1),
+				plonk::Curve::Bls12_381,
+				utils::serialize_argument(other.vk)
+			),
+			Error::<Test>::PlonkSrsMismatch
+		);
+		assert_ok!(TemplateModule::plonk_register_vk(
+			RuntimeOrigin::signed(1),
+			plonk::Curve::Bls12_381,
+			vk
+		));
+	});
+}
+
+#[test]
+fn plonk_remove_vk_returns_the_deposit() {
+	new_test_ext().execute_with(|| {
+		let (vk, inputs, proof, _) =
+			plonk_fixture::<ark_bls12_381::Bls12_381>(plonk::Curve::Bls12_381);
+		assert_ok!(TemplateModule::plonk_register_vk(
+			RuntimeOrigin::signed(1),
+			plonk::Curve::Bls12_381,
+			vk
+		));
+		assert_eq!(Balances::reserved_balance(1), 100);
+
+		assert_noop!(
+			TemplateModule::plonk_remove_vk(RuntimeOrigin::signed(2), 0),
+			Error::<Test>::NotVerifyingKeyOwner
+		);
+		assert_noop!(
+			TemplateModule::plonk_remove_vk(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::UnknownVerifyingKey
+		);
+		assert_ok!(TemplateModule::plonk_remove_vk(RuntimeOrigin::signed(1), 0));
+		System::assert_last_event(Event::PlonkVerifyingKeyRemoved { id: 0 }.into());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000);
+		assert_noop!(
+			TemplateModule::plonk_verify(RuntimeOrigin::signed(1), 0, inputs, proof),
+			Error::<Test>::UnknownVerifyingKey
+		);
+	});
+}
+
+type MarlinBls12_381 = Marlin<ark_bls12_381::Bls12_381>;
+
+/// Returns the uncompressed index verifying key, public inputs, proof and wrong public inputs
+/// of a Marlin proof for the cube circuit, indexed from the universal parameters `pp`, and sets
+/// `pp` as the universal parameters of Marlin keys.
+fn marlin_fixture(
+	pp: &marlin::UniversalParams<ark_bls12_381::Bls12_381>,
+	repetitions: usize,
+) -> (Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>) {
+	let mut rng = StdRng::seed_from_u64(0x5eed);
+	let circuit = CubeCircuit::new(3, repetitions);
+	let (pk, vk) = MarlinBls12_381::index(pp, CubeCircuit { x: None, repetitions }, &mut rng)
+		.unwrap_or_else(|_| panic!("universal parameters are large enough"));
+	let proof = MarlinBls12_381::prove(&pk, circuit, &mut rng).unwrap();
+	let public_inputs = circuit.public_inputs();
+	let wrong_inputs: Vec<BlsFr> = public_inputs.iter().map(|input| *input + input).collect();
+	assert_ok!(TemplateModule::marlin_set_srs(
+		RuntimeOrigin::root(),
+		utils::serialize_argument(pp.tau_h)
+	));
+	(
+		utils::serialize_argument(vk),
+		utils::serialize_argument(public_inputs),
+		utils::serialize_argument(proof),
+		utils::serialize_argument(wrong_inputs),
+	)
+}
+
+fn marlin_universal_params() -> marlin::UniversalParams<ark_bls12_381::Bls12_381> {
+	MarlinBls12_381::universal_setup(&384, &mut StdRng::seed_from_u64(0x5eed)).unwrap()
+}
+
+#[test]
+fn marlin_verify() {
+	new_test_ext().execute_with(|| {
+		let (vk, inputs, proof, wrong_inputs) = marlin_fixture(&marlin_universal_params(), 2);
+		assert_ok!(TemplateModule::marlin_register_vk(RuntimeOrigin::signed(1), vk));
+		System::assert_last_event(Event::MarlinVerifyingKeyRegistered { id: 0, who: 1 }.into());
+		assert_eq!(Balances::reserved_balance(1), 100);
+
+		for verify in [TemplateModule::marlin_verify, TemplateModule::marlin_verify_optimized] {
+			assert_ok!(verify(RuntimeOrigin::signed(1), 0, inputs.clone(), proof.clone()));
+			System::assert_last_event(
+				Event::MarlinVerified {
+					who: 1,
+					vk_id: 0,
+					inputs_hash: BlakeTwo256::hash(&inputs),
+					proof_hash: BlakeTwo256::hash(&proof),
+				}
+				.into(),
+			);
+			assert_noop!(
+				verify(RuntimeOrigin::signed(1), 0, wrong_inputs.clone(), proof.clone()),
+				Error::<Test>::MarlinVerificationFailed
+			);
+			assert_noop!(
+				verify(RuntimeOrigin::signed(1), 0, inputs.clone(), proof[1..].to_vec()),
+				Error::<Test>::InvalidMarlinProof
+			);
+			assert_noop!(
+				verify(RuntimeOrigin::signed(1), 0, Vec::new(), proof.clone()),
+				Error::<Test>::InvalidPublicInputs
+			);
+			assert_noop!(
+				verify(RuntimeOrigin::signed(1), 1, inputs.clone(), proof.clone()),
+				Error::<Test>::UnknownVerifyingKey
+			);
+		}
+	});
+}
+
+#[test]
+fn marlin_universal_setup_indexes_several_circuits() {
+	new_test_ext().execute_with(|| {
+		let pp = marlin_universal_params();
+		let (small_vk, small_inputs, small_proof, _) = marlin_fixture(&pp, 1);
+		let (large_vk, large_inputs, large_proof, _) = marlin_fixture(&pp, 8);
+		assert_ok!(TemplateModule::marlin_register_vk(RuntimeOrigin::signed(1), small_vk));
+		assert_ok!(TemplateModule::marlin_register_vk(RuntimeOrigin::signed(1), large_vk));
+
+		assert_ok!(TemplateModule::marlin_verify_optimized(
+			RuntimeOrigin::signed(1),
+			0,
+			small_inputs.clone(),
+			small_proof.clone()
+		));
+		assert_ok!(TemplateModule::marlin_verify_optimized(
+			RuntimeOrigin::signed(1),
+			1,
+			large_inputs,
+			large_proof
+		));
+		// A proof only verifies against the index it was created for.
+		assert_noop!(
+			TemplateModule::marlin_verify_optimized(
+				RuntimeOrigin::signed(1),
+				1,
+				small_inputs,
+				small_proof
+			),
+			Error::<Test>::MarlinVerificationFailed
+		);
+
+		// The universal parameters are too small for a circuit with 32 repetitions.
+		assert!(MarlinBls12_381::index(
+			&pp,
+			CubeCircuit::<BlsFr> { x: None, repetitions: 32 },
+			&mut StdRng::seed_from_u64(0)
+		)
+		.is_err());
+	});
+}
+
+#[test]
+fn marlin_register_vk_rejects_invalid_keys() {
+	new_test_ext().execute_with(|| {
+		let (vk, ..) = marlin_fixture(&marlin_universal_params(), 1);
+		assert_noop!(
+			TemplateModule::marlin_register_vk(
+				RuntimeOrigin::signed(1),
+				vk[..vk.len() - 1].to_vec()
+			),
+			Error::<Test>::InvalidMarlinVerifyingKey
+		);
+		// The degree bounds need universal parameters of degree at least `max(2n, 6m)`.
+		let mut key =
+			marlin::VerifyingKey::<ark_bls12_381::Bls12_381>::deserialize_uncompressed(&vk[..])
+				.unwrap();
+		key.max_degree = 8;
+		assert_noop!(
+			TemplateModule::marlin_register_vk(
+				RuntimeOrigin::signed(1),
+				utils::serialize_argument(key.clone())
+			),
+			Error::<Test>::InvalidMarlinVerifyingKey
+		);
+		// `6m` exceeds 32 bits, the degree bound is not truncated.
+		key.domain_k_size = 1 << 31;
+		key.max_degree = (6 << 31) - 1;
+		assert_noop!(
+			TemplateModule::marlin_register_vk(
+				RuntimeOrigin::signed(1),
+				utils::serialize_argument(key)
+			),
+			Error::<Test>::InvalidMarlinVerifyingKey
+		);
+	});
+}
+
+#[test]
+fn marlin_register_vk_bounds_the_public_inputs() {
+	new_test_ext().execute_with(|| {
+		let pp =
+			MarlinBls12_381::universal_setup(&1536, &mut StdRng::seed_from_u64(0x5eed)).unwrap();
+		assert_ok!(TemplateModule::marlin_set_srs(
+			RuntimeOrigin::root(),
+			utils::serialize_argument(pp.tau_h)
+		));
+		let register = |num_public_inputs: usize| {
+			let circuit = RepeatedInputCircuit::<BlsFr> { x: None, num_public_inputs };
+			let (_, vk) = MarlinBls12_381::index(&pp, circuit, &mut StdRng::seed_from_u64(0))
+				.unwrap_or_else(|_| panic!("universal parameters are large enough"));
+			TemplateModule::marlin_register_vk(
+				RuntimeOrigin::signed(1),
+				utils::serialize_argument(vk),
+			)
+		};
+		let max = marlin::MAX_PUBLIC_INPUTS as usize;
+		assert_ok!(register(max));
+		assert_noop!(register(max + 1), Error::<Test>::InvalidMarlinVerifyingKey);
+	});
+}
+
+#[test]
+fn marlin_register_vk_checks_the_srs() {
+	new_test_ext().execute_with(|| {
+		let pp = marlin_universal_params();
+		let (vk, ..) = marlin_fixture(&pp, 1);
+		MarlinSrs::<Test>::kill();
+		assert_noop!(
+			TemplateModule::marlin_register_vk(RuntimeOrigin::signed(1), vk.clone()),
+			Error::<Test>::MarlinSrsNotSet
+		);
+		assert_noop!(
+			TemplateModule::marlin_set_srs(
+				RuntimeOrigin::signed(1),
+				utils::serialize_argument(pp.tau_h)
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TemplateModule::marlin_set_srs(RuntimeOrigin::root(), vec![1; 192]),
+			Error::<Test>::InvalidMarlinSrs
+		);
+
+		// A key indexed from other universal parameters is rejected.
+		let other = MarlinBls12_381::universal_setup(&384, &mut StdRng::seed_from_u64(7)).unwrap();
+		assert_ok!(TemplateModule::marlin_set_srs(
+			RuntimeOrigin::root(),
+			utils::serialize_argument(other.tau_h)
+		));
+		System::assert_last_event(Event::MarlinSrsSet.into());
+		assert_noop!(
+			TemplateModule::marlin_register_vk(RuntimeOrigin::signed(1), vk.clone()),
+			Error::<Test>::MarlinSrsMismatch
+		);
+
+		assert_ok!(TemplateModule::marlin_set_srs(
+			RuntimeOrigin::root(),
+			utils::serialize_argument(pp.tau_h)
+		));
+		assert_ok!(TemplateModule::marlin_register_vk(RuntimeOrigin::signed(1), vk));
+	});
+}
+
+#[test]
+fn marlin_remove_vk_returns_the_deposit() {
+	new_test_ext().execute_with(|| {
+		let (vk, inputs, proof, _) = marlin_fixture(&marlin_universal_params(), 1);
+		assert_ok!(TemplateModule::marlin_register_vk(RuntimeOrigin::signed(1), vk));
+		assert_eq!(Balances::reserved_balance(1), 100);
+
+		assert_noop!(
+			TemplateModule::marlin_remove_vk(RuntimeOrigin::signed(2), 0),
+			Error::<Test>::NotVerifyingKeyOwner
+		);
+		assert_noop!(
+			TemplateModule::marlin_remove_vk(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::UnknownVerifyingKey
+		);
+		assert_ok!(TemplateModule::marlin_remove_vk(RuntimeOrigin::signed(1), 0));
+		System::assert_last_event(Event::MarlinVerifyingKeyRemoved { id: 0 }.into());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000);
+		assert_noop!(
+			TemplateModule::marlin_verify(RuntimeOrigin::signed(1), 0, inputs, proof),
+			Error::<Test>::UnknownVerifyingKey
+		);
+	});
+}
+
+/// Returns the uncompressed Groth16 verifying key, aggregation setup, lists of public inputs and
+/// SnarkPack aggregate of `n` proofs for the cube circuit.
+fn snarkpack_fixture(n: usize) -> (Vec<u8>, Vec<u8>, Vec<Vec<u8>>, Vec<u8>) {
+	type E = ark_bls12_381::Bls12_381;
+	let mut rng = StdRng::seed_from_u64(0x5eed);
+	let (pk, vk) = Groth16::<E>::circuit_specific_setup(
+		CubeCircuit::<BlsFr> { x: None, repetitions: 1 },
+		&mut rng,
+	)
+	.unwrap();
+	let circuits: Vec<_> = (0..n).map(|i| CubeCircuit::<BlsFr>::new(i as u64, 1)).collect();
+	let proofs: Vec<_> = circuits
+		.iter()
+		.map(|circuit| Groth16::<E>::prove(&pk, *circuit, &mut rng).unwrap())
+		.collect();
+	let public_inputs: Vec<_> = circuits.iter().map(|circuit| circuit.public_inputs()).collect();
+	let srs = snarkpack::prover::ProverSrs::<E>::setup(n, &mut rng);
+	let proof = snarkpack::prover::aggregate(&srs, &vk, &public_inputs, &proofs);
+	(
+		utils::serialize_argument(vk),
+		utils::serialize_argument(srs.vk),
+		public_inputs.into_iter().map(utils::serialize_argument).collect(),
+		utils::serialize_argument(proof),
+	)
+}
+
+#[test]
+fn snarkpack_aggregate_fixture_is_reproducible() {
+	// Regenerate `fixtures/snarkpack/aggregate.scale` with `snarkpack_fixture(4).encode()`.
+	assert_eq!(snarkpack::aggregate_fixture(), snarkpack_fixture(4));
+}
+
+#[test]
+fn groth16_verify_aggregate() {
+	new_test_ext().execute_with(|| {
+		let (vk, srs, inputs, proof) = snarkpack::aggregate_fixture();
+		assert_ok!(TemplateModule::groth16_register_vk(RuntimeOrigin::signed(1), vk));
+		System::assert_last_event(Event::Groth16VerifyingKeyRegistered { id: 0, who: 1 }.into());
+		assert_noop!(
+			TemplateModule::groth16_verify_aggregate(
+				RuntimeOrigin::signed(1),
+				0,
+				inputs.clone(),
+				proof.clone()
+			),
+			Error::<Test>::MissingAggregationSrs
+		);
+		assert_ok!(TemplateModule::groth16_set_aggregation_srs(RuntimeOrigin::root(), srs));
+
+		assert_ok!(TemplateModule::groth16_verify_aggregate(
+			RuntimeOrigin::signed(1),
+			0,
+			inputs.clone(),
+			proof.clone()
+		));
+		// Each aggregated statement reaches the hook.
+		assert_eq!(
+			VerifiedProofs::get(),
+			inputs.iter().map(|inputs| (1, 0, inputs.clone())).collect::<Vec<_>>()
+		);
+		let mut swapped_inputs = inputs.clone();
+		swapped_inputs.swap(0, 1);
+		assert_noop!(
+			TemplateModule::groth16_verify_aggregate(
+				RuntimeOrigin::signed(1),
+				0,
+				swapped_inputs,
+				proof.clone()
+			),
+			Error::<Test>::AggregateVerificationFailed
+		);
+		assert_noop!(
+			TemplateModule::groth16_verify_aggregate(
+				RuntimeOrigin::signed(1),
+				0,
+				inputs.clone(),
+				proof[1..].to_vec()
+			),
+			Error::<Test>::InvalidAggregateProof
+		);
+		// The number of proofs must match the rounds of the aggregate.
+		assert_noop!(
+			TemplateModule::groth16_verify_aggregate(
+				RuntimeOrigin::signed(1),
+				0,
+				inputs[..2].to_vec(),
+				proof.clone()
+			),
+			Error::<Test>::InvalidPublicInputs
+		);
+		assert_noop!(
+			TemplateModule::groth16_verify_aggregate(
+				RuntimeOrigin::signed(1),
+				0,
+				vec![inputs[0].clone(); snarkpack::MAX_AGGREGATED_PROOFS as usize + 1],
+				proof.clone()
+			),
+			Error::<Test>::TooManyAggregatedProofs
+		);
+		assert_noop!(
+			TemplateModule::groth16_verify_aggregate(RuntimeOrigin::signed(1), 1, inputs, proof),
+			Error::<Test>::UnknownVerifyingKey
+		);
+	});
+}
+
+#[test]
+fn groth16_set_aggregation_srs_rejects_invalid_setups() {
+	new_test_ext().execute_with(|| {
+		let (vk, srs, ..) = snarkpack_fixture(2);
+		assert_noop!(
+			TemplateModule::groth16_set_aggregation_srs(RuntimeOrigin::signed(1), srs.clone()),
+			DispatchError::BadOrigin
+		);
+		// `h^a` and `h^b` must match `g^a` and `g^b`.
+		let mut setup =
+			snarkpack::VerifierSrs::<ark_bls12_381::Bls12_381>::deserialize_uncompressed(&srs[..])
+				.unwrap();
+		setup.h_beta = setup.h_alpha;
+		assert_noop!(
+			TemplateModule::groth16_set_aggregation_srs(
+				RuntimeOrigin::root(),
+				utils::serialize_argument(setup)
+			),
+			Error::<Test>::InvalidAggregationSrs
+		);
+		assert_noop!(
+			TemplateModule::groth16_register_vk(RuntimeOrigin::signed(1), vk[1..].to_vec()),
+			Error::<Test>::InvalidGroth16VerifyingKey
+		);
+	});
+}
+
+/// Returns the uncompressed verifying key, public inputs and proof of the Groth16 fixtures.
+fn groth16_fixture() -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+	let (vk, c, proof) = bls12_381::groth16_fixture();
+	(vk, utils::serialize_argument(vec![c]), proof)
+}
+
+#[test]
+fn groth16_verify_prepared() {
+	new_test_ext().execute_with(|| {
+		let (vk, inputs, proof) = groth16_fixture();
+		assert_ok!(TemplateModule::groth16_register_vk(RuntimeOrigin::signed(1), vk.clone()));
+		assert!(crate::Groth16PreparedVerifyingKeys::<Test>::contains_key(0));
+
+		assert_ok!(TemplateModule::groth16_verify_prepared(
+			RuntimeOrigin::signed(1),
+			0,
+			inputs.clone(),
+			proof.clone()
+		));
+		System::assert_last_event(groth16_event(1, true, &vk, &inputs, &proof));
+		assert_eq!(VerifiedProofs::get(), vec![(1, 0, inputs.clone())]);
+		assert_noop!(
+			TemplateModule::groth16_verify_prepared(
+				RuntimeOrigin::signed(1),
+				0,
+				utils::serialize_argument(vec![BlsFr::from(1u64)]),
+				proof.clone()
+			),
+			Error::<Test>::VerificationFailed
+		);
+		assert_noop!(
+			TemplateModule::groth16_verify_prepared(
+				RuntimeOrigin::signed(1),
+				0,
+				inputs.clone(),
+				proof[1..].to_vec()
+			),
+			Error::<Test>::InvalidGroth16Proof
+		);
+		assert_noop!(
+			TemplateModule::groth16_verify_prepared(
+				RuntimeOrigin::signed(1),
+				0,
+				utils::serialize_argument(Vec::<BlsFr>::new()),
+				proof.clone()
+			),
+			Error::<Test>::InvalidPublicInputs
+		);
+		assert_noop!(
+			TemplateModule::groth16_verify_prepared(RuntimeOrigin::signed(1), 1, inputs, proof),
+			Error::<Test>::UnknownVerifyingKey
+		);
+	});
+}
+
+#[test]
+fn groth16_verify_with_prepared_inputs() {
+	new_test_ext().execute_with(|| {
+		let (vk, inputs, proof) = groth16_fixture();
+		assert_ok!(TemplateModule::groth16_register_vk(RuntimeOrigin::signed(1), vk.clone()));
+		assert_noop!(
+			TemplateModule::groth16_optimized_prepare_inputs(
+				RuntimeOrigin::signed(1),
+				0,
+				utils::serialize_argument(Vec::<BlsFr>::new())
+			),
+			Error::<Test>::InvalidPublicInputs
+		);
+		assert_ok!(TemplateModule::groth16_optimized_prepare_inputs(
+			RuntimeOrigin::signed(1),
+			0,
+			inputs.clone()
+		));
+		System::assert_last_event(
+			Event::Groth16InputsPrepared { handle: 0, vk_id: 0, expires_at: 11, who: 1 }.into(),
+		);
+
+		assert_noop!(
+			TemplateModule::groth16_optimized_verify_with_prepared_inputs(
+				RuntimeOrigin::signed(1),
+				0,
+				proof[1..].to_vec()
+			),
+			Error::<Test>::InvalidGroth16Proof
+		);
+		// Inputs prepared in one block are verified in another and consumed.
+		System::set_block_number(2);
+		assert_ok!(TemplateModule::groth16_optimized_verify_with_prepared_inputs(
+			RuntimeOrigin::signed(1),
+			0,
+			proof.clone()
+		));
+		System::assert_last_event(groth16_event(1, true, &vk, &inputs, &proof));
+		assert_eq!(VerifiedProofs::get(), vec![(1, 0, inputs.clone())]);
+		assert_noop!(
+			TemplateModule::groth16_optimized_verify_with_prepared_inputs(
+				RuntimeOrigin::signed(1),
+				0,
+				proof.clone()
+			),
+			Error::<Test>::UnknownPreparedInputs
+		);
+
+		assert_ok!(TemplateModule::groth16_optimized_prepare_inputs(
+			RuntimeOrigin::signed(1),
+			0,
+			utils::serialize_argument(vec![BlsFr::from(1u64)])
+		));
+		assert_noop!(
+			TemplateModule::groth16_optimized_verify_with_prepared_inputs(
+				RuntimeOrigin::signed(1),
+				1,
+				proof.clone()
+			),
+			Error::<Test>::VerificationFailed
+		);
+		System::set_block_number(12);
+		assert_ok!(TemplateModule::groth16_optimized_prepare_inputs(
+			RuntimeOrigin::signed(1),
+			0,
+			inputs
+		));
+		assert_noop!(
+			TemplateModule::groth16_optimized_verify_with_prepared_inputs(
+				RuntimeOrigin::signed(1),
+				1,
+				proof
+			),
+			Error::<Test>::PreparedInputsExpired
+		);
+	});
+}
+
+#[test]
+fn verify_checks_registered_keys_without_dispatching() {
+	new_test_ext().execute_with(|| {
+		let (vk, inputs, proof) = groth16_fixture();
+		assert!(matches!(
+			TemplateModule::verify(0, &inputs, &proof),
+			Err(Error::<Test>::UnknownVerifyingKey)
+		));
+		assert_ok!(TemplateModule::groth16_register_vk(RuntimeOrigin::signed(1), vk));
+		let events = System::events().len();
+
+		assert!(matches!(
+			TemplateModule::verify(0, &inputs, &proof),
+			Ok(VerifyingKeyStatus::Pending)
+		));
+		assert_ok!(TemplateModule::groth16_approve_vk(RuntimeOrigin::root(), 0));
+		let events = System::events().len();
+		assert!(matches!(
+			TemplateModule::verify(0, &inputs, &proof),
+			Ok(VerifyingKeyStatus::Approved)
+		));
+		assert!(matches!(
+			TemplateModule::verify(0, &utils::serialize_argument(vec![BlsFr::from(1u64)]), &proof),
+			Err(Error::<Test>::VerificationFailed)
+		));
+		assert!(matches!(
+			TemplateModule::verify(0, &inputs, &proof[1..]),
+			Err(Error::<Test>::InvalidGroth16Proof)
+		));
+		assert_eq!(System::events().len(), events);
+		assert!(VerifiedProofs::get().is_empty());
+	});
+}
+
+#[cfg(feature = "host-call-stats")]
+#[test]
+fn counts_the_host_calls_of_groth16_verifications() {
+	use crate::host_stats::{self, HostCall};
+	new_test_ext().execute_with(|| {
+		let (vk, c, proof) = groth16_single_input_fixture();
+		host_stats::take();
+		assert_ok!(TemplateModule::groth16_verification(
+			RuntimeOrigin::signed(1),
+			vk.clone(),
+			c.clone(),
+			proof.clone()
+		));
+		assert_eq!(host_stats::take().total(), Default::default());
+
+		assert_ok!(TemplateModule::groth16_verification_optimized(
+			RuntimeOrigin::signed(1),
+			vk.clone(),
+			c,
+			proof.clone()
+		));
+		// One pairing prepares the key and another checks the proof.
+		let stats = host_stats::take();
+		assert_eq!(stats.get(HostCall::MultiMillerLoop).calls, 2);
+		assert_eq!(stats.get(HostCall::FinalExponentiation).calls, 2);
+		assert!(stats.total().bytes_in > 0 && stats.total().bytes_out > 0);
+
+		// Registered keys are prepared once.
+		let (vk, inputs, proof) = groth16_fixture();
+		assert_ok!(TemplateModule::groth16_register_vk(RuntimeOrigin::signed(1), vk));
+		assert_eq!(host_stats::take().get(HostCall::MultiMillerLoop).calls, 1);
+		assert_ok!(TemplateModule::groth16_verify_prepared(
+			RuntimeOrigin::signed(1),
+			0,
+			inputs,
+			proof
+		));
+		let stats = host_stats::take();
+		assert_eq!(stats.get(HostCall::MultiMillerLoop).calls, 1);
+		assert_eq!(stats.get(HostCall::FinalExponentiation).calls, 1);
+	});
+}
+
+#[test]
+fn on_idle_removes_expired_prepared_inputs() {
+	new_test_ext().execute_with(|| {
+		let (vk, inputs, _) = groth16_fixture();
+		assert_ok!(TemplateModule::groth16_register_vk(RuntimeOrigin::signed(1), vk));
+		for block in [1, 1, 5] {
+			System::set_block_number(block);
+			assert_ok!(TemplateModule::groth16_optimized_prepare_inputs(
+				RuntimeOrigin::signed(1),
+				0,
+				inputs.clone()
+			));
+		}
+
+		TemplateModule::on_idle(11, Weight::MAX);
+		assert!(!crate::PreparedInputs::<Test>::contains_key(0));
+		assert!(!crate::PreparedInputs::<Test>::contains_key(1));
+		assert!(crate::PreparedInputs::<Test>::contains_key(2));
+		assert_eq!(crate::OldestPreparedInputsHandle::<Test>::get(), 2);
+
+		TemplateModule::on_idle(15, Weight::MAX);
+		assert!(!crate::PreparedInputs::<Test>::contains_key(2));
+		assert_eq!(crate::OldestPreparedInputsHandle::<Test>::get(), 3);
+	});
+}
+
+/// The event of a Groth16 proof of `who` checked against a registered key.
+fn groth16_event(who: u64, valid: bool, vk: &[u8], inputs: &[u8], proof: &[u8]) -> RuntimeEvent {
+	let (vk_hash, inputs_hash, proof_hash) =
+		(BlakeTwo256::hash(vk), BlakeTwo256::hash(inputs), BlakeTwo256::hash(proof));
+	let backend = Groth16Backend::Bls12_381Optimized;
+	if valid {
+		Event::Groth16Verified { who, vk_hash, inputs_hash, proof_hash, backend }.into()
+	} else {
+		Event::VerificationFailed { who, vk_hash, inputs_hash, proof_hash, backend }.into()
+	}
+}
+
+#[test]
+fn groth16_verify_audited_records_failures() {
+	new_test_ext().execute_with(|| {
+		let (vk, inputs, proof) = groth16_fixture();
+		let wrong_inputs = utils::serialize_argument(vec![BlsFr::from(1u64)]);
+		assert_noop!(
+			TemplateModule::groth16_verify_audited(
+				RuntimeOrigin::signed(1),
+				0,
+				inputs.clone(),
+				proof.clone()
+			),
+			Error::<Test>::UnknownVerifyingKey
+		);
+		assert_ok!(TemplateModule::groth16_register_vk(RuntimeOrigin::signed(1), vk.clone()));
+
+		assert_ok!(TemplateModule::groth16_verify_audited(
+			RuntimeOrigin::signed(2),
+			0,
+			wrong_inputs.clone(),
+			proof.clone()
+		));
+		System::assert_last_event(groth16_event(2, false, &vk, &wrong_inputs, &proof));
+		assert_ok!(TemplateModule::groth16_verify_audited(
+			RuntimeOrigin::signed(2),
+			0,
+			inputs.clone(),
+			proof[1..].to_vec()
+		));
+		System::assert_last_event(groth16_event(2, false, &vk, &inputs, &proof[1..]));
+		assert!(VerifiedProofs::get().is_empty());
+
+		assert_ok!(TemplateModule::groth16_verify_audited(
+			RuntimeOrigin::signed(2),
+			0,
+			inputs.clone(),
+			proof.clone()
+		));
+		System::assert_last_event(groth16_event(2, true, &vk, &inputs, &proof));
+		assert_eq!(VerifiedProofs::get(), vec![(2, 0, inputs)]);
+	});
+}
+
+#[test]
+fn admin_governs_the_lifecycle_of_groth16_keys() {
+	new_test_ext().execute_with(|| {
+		let (vk, inputs, proof) = groth16_fixture();
+		assert_ok!(TemplateModule::groth16_register_vk(RuntimeOrigin::signed(1), vk.clone()));
+		assert_eq!(Groth16VerifyingKeyStatuses::<Test>::get(0), VerifyingKeyStatus::Pending);
+
+		assert_noop!(
+			TemplateModule::groth16_approve_vk(RuntimeOrigin::signed(1), 0),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TemplateModule::groth16_freeze_vk(RuntimeOrigin::signed(1), 0),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TemplateModule::groth16_deprecate_vk(RuntimeOrigin::signed(1), 0),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TemplateModule::groth16_approve_vk(RuntimeOrigin::root(), 1),
+			Error::<Test>::UnknownVerifyingKey
+		);
+
+		assert_ok!(TemplateModule::groth16_approve_vk(RuntimeOrigin::root(), 0));
+		assert_eq!(Groth16VerifyingKeyStatuses::<Test>::get(0), VerifyingKeyStatus::Approved);
+		System::assert_last_event(Event::Groth16VerifyingKeyApproved { id: 0 }.into());
+
+		assert_ok!(TemplateModule::groth16_freeze_vk(RuntimeOrigin::root(), 0));
+		assert_eq!(Groth16VerifyingKeyStatuses::<Test>::get(0), VerifyingKeyStatus::Frozen);
+		System::assert_last_event(Event::Groth16VerifyingKeyFrozen { id: 0 }.into());
+		assert!(matches!(
+			TemplateModule::verify(0, &inputs, &proof),
+			Err(Error::<Test>::VerifyingKeyFrozen)
+		));
+		let signed = || RuntimeOrigin::signed(1);
+		assert_noop!(
+			TemplateModule::groth16_verify_prepared(signed(), 0, inputs.clone(), proof.clone()),
+			Error::<Test>::VerifyingKeyFrozen
+		);
+		assert_noop!(
+			TemplateModule::groth16_verify_audited(signed(), 0, inputs.clone(), proof.clone()),
+			Error::<Test>::VerifyingKeyFrozen
+		);
+		assert_noop!(
+			TemplateModule::groth16_optimized_prepare_inputs(signed(), 0, inputs.clone()),
+			Error::<Test>::VerifyingKeyFrozen
+		);
+		assert_noop!(
+			TemplateModule::groth16_request_verification(
+				signed(),
+				0,
+				inputs.clone(),
+				proof.clone()
+			),
+			Error::<Test>::VerifyingKeyFrozen
+		);
+
+		// Approving a frozen key unfreezes it, deprecated keys still verify proofs.
+		assert_ok!(TemplateModule::groth16_approve_vk(RuntimeOrigin::root(), 0));
+		assert_ok!(TemplateModule::groth16_verify_prepared(
+			signed(),
+			0,
+			inputs.clone(),
+			proof.clone()
+		));
+		assert_ok!(TemplateModule::groth16_deprecate_vk(RuntimeOrigin::root(), 0));
+		assert_eq!(Groth16VerifyingKeyStatuses::<Test>::get(0), VerifyingKeyStatus::Deprecated);
+		System::assert_last_event(Event::Groth16VerifyingKeyDeprecated { id: 0 }.into());
+		assert_ok!(TemplateModule::groth16_verify_prepared(
+			signed(),
+			0,
+			inputs.clone(),
+			proof.clone()
+		));
+		System::assert_last_event(groth16_event(1, true, &vk, &inputs, &proof));
+	});
+}
+
+#[test]
+fn frozen_keys_fail_queued_verification_requests() {
+	new_test_ext().execute_with(|| {
+		let (vk, inputs, proof) = groth16_fixture();
+		queue_verification_requests();
+		assert_ok!(TemplateModule::groth16_freeze_vk(RuntimeOrigin::root(), 0));
+
+		assert_ok!(TemplateModule::submit_verification_result(RuntimeOrigin::signed(7), 0, true));
+		assert!(VerifiedProofs::get().is_empty());
+		System::assert_has_event(groth16_event(1, false, &vk, &inputs, &proof));
+		System::assert_last_event(
+			Event::VerificationRequestProcessed { request_id: 0, who: 1, valid: false }.into(),
+		);
+	});
+}
+
+#[test]
+fn verification_requests_reserve_a_deposit_until_settled() {
+	new_test_ext().execute_with(|| {
+		let proof = queue_verification_requests();
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(Balances::reserved_balance(2), 10);
+		assert_eq!(crate::VerificationRequestDeposits::<Test>::get(0), Some(10));
+		assert_noop!(
+			TemplateModule::groth16_request_verification(
+				RuntimeOrigin::signed(3),
+				0,
+				vec![],
+				proof
+			),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+
+		assert_ok!(TemplateModule::submit_verification_result(RuntimeOrigin::signed(7), 0, true));
+		assert_ok!(TemplateModule::submit_verification_result(RuntimeOrigin::signed(7), 1, false));
+		for who in [1, 2] {
+			assert_eq!(Balances::reserved_balance(who), 0);
+			assert_eq!(Balances::free_balance(who), 1_000);
+		}
+		assert_eq!(crate::VerificationRequestDeposits::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn circuit_versions_verify_until_the_grace_period_ends() {
+	new_test_ext().execute_with(|| {
+		let (vk, inputs, proof) = groth16_fixture();
+		let name = b"transfer".to_vec();
+		assert_ok!(TemplateModule::groth16_register_vk(RuntimeOrigin::signed(1), vk.clone()));
+		assert_ok!(TemplateModule::groth16_register_vk(RuntimeOrigin::signed(1), vk.clone()));
+
+		assert_noop!(
+			TemplateModule::groth16_publish_circuit_version(
+				RuntimeOrigin::signed(1),
+				name.clone(),
+				0
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TemplateModule::groth16_publish_circuit_version(RuntimeOrigin::root(), name.clone(), 2),
+			Error::<Test>::UnknownVerifyingKey
+		);
+		assert_noop!(
+			TemplateModule::groth16_publish_circuit_version(
+				RuntimeOrigin::root(),
+				vec![0; MAX_CIRCUIT_NAME_LEN as usize + 1],
+				0
+			),
+			Error::<Test>::InvalidCircuitName
+		);
+
+		assert_ok!(TemplateModule::groth16_publish_circuit_version(
+			RuntimeOrigin::root(),
+			name.clone(),
+			0
+		));
+		System::assert_last_event(
+			Event::CircuitVersionPublished {
+				name: name.clone().try_into().unwrap(),
+				version: 1,
+				vk_id: 0,
+			}
+			.into(),
+		);
+		let signed = || RuntimeOrigin::signed(1);
+		assert_ok!(TemplateModule::groth16_verify_circuit(
+			signed(),
+			name.clone(),
+			1,
+			inputs.clone(),
+			proof.clone()
+		));
+		System::assert_last_event(groth16_event(1, true, &vk, &inputs, &proof));
+		assert_noop!(
+			TemplateModule::groth16_verify_circuit(
+				signed(),
+				name.clone(),
+				2,
+				inputs.clone(),
+				proof.clone()
+			),
+			Error::<Test>::UnknownCircuitVersion
+		);
+
+		// Publishing version 2 gives the proofs of version 1 the grace period of 5 blocks.
+		System::set_block_number(3);
+		assert_ok!(TemplateModule::groth16_publish_circuit_version(
+			RuntimeOrigin::root(),
+			name.clone(),
+			1
+		));
+		let bounded_name: CircuitName = name.clone().try_into().unwrap();
+		assert_eq!(
+			CircuitVersions::<Test>::get(&bounded_name, 1),
+			Some(CircuitVersion { vk_id: 0, expires_at: Some(8) })
+		);
+		assert_eq!(
+			CircuitVersions::<Test>::get(&bounded_name, 2),
+			Some(CircuitVersion { vk_id: 1, expires_at: None })
+		);
+		System::set_block_number(7);
+		assert_ok!(TemplateModule::groth16_verify_circuit(
+			signed(),
+			name.clone(),
+			1,
+			inputs.clone(),
+			proof.clone()
+		));
+		System::set_block_number(8);
+		assert_noop!(
+			TemplateModule::groth16_verify_circuit(
+				signed(),
+				name.clone(),
+				1,
+				inputs.clone(),
+				proof.clone()
+			),
+			Error::<Test>::CircuitVersionExpired
+		);
+		assert_ok!(TemplateModule::groth16_verify_circuit(
+			signed(),
+			name.clone(),
+			2,
+			inputs.clone(),
+			proof.clone()
+		));
+
+		// The keys behind circuit versions are governed like any other.
+		assert_ok!(TemplateModule::groth16_freeze_vk(RuntimeOrigin::root(), 1));
+		assert_noop!(
+			TemplateModule::groth16_verify_circuit(signed(), name.clone(), 2, inputs, proof),
+			Error::<Test>::VerifyingKeyFrozen
+		);
+		assert_noop!(
+			TemplateModule::groth16_publish_circuit_version(RuntimeOrigin::root(), name, 1),
+			Error::<Test>::VerifyingKeyFrozen
+		);
+	});
+}
+
+#[test]
+fn migration_to_v1_records_the_status_of_registered_keys() {
+	new_test_ext().execute_with(|| {
+		let (vk, _, _) = groth16_fixture();
+		assert_ok!(TemplateModule::groth16_register_vk(RuntimeOrigin::signed(1), vk.clone()));
+		assert_ok!(TemplateModule::groth16_register_vk(RuntimeOrigin::signed(1), vk));
+		assert_ok!(TemplateModule::groth16_approve_vk(RuntimeOrigin::root(), 1));
+		// Keys registered at version 0 have no status.
+		Groth16VerifyingKeyStatuses::<Test>::remove(0);
+		StorageVersion::new(0).put::<TemplateModule>();
+
+		#[cfg(feature = "try-runtime")]
+		let state = migrations::v1::MigrateToV1::<Test>::pre_upgrade().unwrap();
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		#[cfg(feature = "try-runtime")]
+		migrations::v1::MigrateToV1::<Test>::post_upgrade(state).unwrap();
+
+		assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+		assert!(Groth16VerifyingKeyStatuses::<Test>::contains_key(0));
+		assert_eq!(Groth16VerifyingKeyStatuses::<Test>::get(0), VerifyingKeyStatus::Pending);
+		assert_eq!(Groth16VerifyingKeyStatuses::<Test>::get(1), VerifyingKeyStatus::Approved);
+
+		// Running it again is a no-op.
+		Groth16VerifyingKeyStatuses::<Test>::remove(0);
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert!(!Groth16VerifyingKeyStatuses::<Test>::contains_key(0));
+	});
+}
+
+#[test]
+fn genesis_stores_the_current_storage_version() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			TemplateModule::on_chain_storage_version(),
+			TemplateModule::current_storage_version()
+		);
+	});
+}
+
+/// The storage of `new_test_ext` with `circuits` registered and `result_submitters` trusted at
+/// genesis.
+fn genesis_test_ext(
+	circuits: Vec<(Vec<u8>, Groth16Curve, Vec<u8>)>,
+	result_submitters: Vec<u64>,
+) -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	crate::GenesisConfig::<Test> { circuits, result_submitters }
+		.assimilate_storage(&mut storage)
+		.unwrap();
+	let mut ext: sp_io::TestExternalities = storage.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+#[test]
+fn genesis_config_registers_circuits() {
+	let (vk, inputs, proof) = groth16_fixture();
+	let circuits = vec![
+		(b"transfer".to_vec(), Groth16Curve::Bls12_381, vk.clone()),
+		(b"vote".to_vec(), Groth16Curve::Bls12_381, vk.clone()),
+		(b"transfer".to_vec(), Groth16Curve::Bls12_381, vk.clone()),
+	];
+	genesis_test_ext(circuits, vec![]).execute_with(|| {
+		for id in 0..3 {
+			assert_eq!(Groth16VerifyingKeyStatuses::<Test>::get(id), VerifyingKeyStatus::Approved);
+		}
+		assert_eq!(TemplateModule::verify_circuit(b"vote", 1, &inputs, &proof).ok(), Some(1));
+		// Listing a circuit again publishes its next version, with the grace period running
+		// from genesis.
+		assert_eq!(TemplateModule::verify_circuit(b"transfer", 2, &inputs, &proof).ok(), Some(2));
+		assert_eq!(TemplateModule::verify_circuit(b"transfer", 1, &inputs, &proof).ok(), Some(0));
+		System::set_block_number(5);
+		assert!(matches!(
+			TemplateModule::verify_circuit(b"transfer", 1, &inputs, &proof),
+			Err(Error::<Test>::CircuitVersionExpired)
+		));
+
+		assert_ok!(TemplateModule::groth16_verify_circuit(
+			RuntimeOrigin::signed(1),
+			b"vote".to_vec(),
+			1,
+			inputs.clone(),
+			proof.clone()
+		));
+		System::assert_last_event(groth16_event(1, true, &vk, &inputs, &proof));
+	});
+}
+
+#[test]
+#[should_panic(expected = "invalid verifying key of circuit")]
+fn genesis_config_rejects_invalid_keys() {
+	let (mut vk, _, _) = groth16_fixture();
+	vk.truncate(vk.len() - 1);
+	genesis_test_ext(vec![(b"transfer".to_vec(), Groth16Curve::Bls12_381, vk)], vec![]);
+}
+
+#[test]
+fn genesis_config_rejects_other_curves() {
+	assert_eq!(
+		serde_json::from_str::<Groth16Curve>("\"Bls12_381\"").unwrap(),
+		Groth16Curve::Bls12_381
+	);
+	assert!(serde_json::from_str::<Groth16Curve>("\"Bn254\"").is_err());
+}
+
+#[test]
+fn genesis_config_trusts_result_submitters() {
+	let (vk, inputs, proof) = groth16_fixture();
+	let circuits = vec![(b"transfer".to_vec(), Groth16Curve::Bls12_381, vk)];
+	genesis_test_ext(circuits, vec![7]).execute_with(|| {
+		assert_eq!(crate::ResultSubmitters::<Test>::get().into_inner(), vec![7]);
+		assert_ok!(TemplateModule::groth16_request_verification(
+			RuntimeOrigin::signed(1),
+			0,
+			inputs,
+			proof
+		));
+		assert_noop!(
+			TemplateModule::submit_verification_result(RuntimeOrigin::signed(1), 0, true),
+			Error::<Test>::NotResultSubmitter
+		);
+		assert_ok!(TemplateModule::submit_verification_result(RuntimeOrigin::signed(7), 0, true));
+	});
+}
+
+/// Registers the Groth16 fixture key, trusts the offchain key `7` with results and queues a
+/// valid and an invalid request.
+fn queue_verification_requests() -> Vec<u8> {
+	let (vk, inputs, proof) = groth16_fixture();
+	UintAuthorityId::set_all_keys(vec![7]);
+	assert_ok!(TemplateModule::groth16_register_vk(RuntimeOrigin::signed(1), vk));
+	assert_ok!(TemplateModule::set_result_submitters(RuntimeOrigin::root(), vec![7]));
+	assert_ok!(TemplateModule::groth16_request_verification(
+		RuntimeOrigin::signed(1),
+		0,
+		inputs,
+		proof.clone()
+	));
+	System::assert_last_event(
+		Event::VerificationRequested { request_id: 0, vk_id: 0, who: 1 }.into(),
+	);
+	assert_ok!(TemplateModule::groth16_request_verification(
+		RuntimeOrigin::signed(2),
+		0,
+		utils::serialize_argument(vec![BlsFr::from(1u64)]),
+		proof.clone()
+	));
+	proof
+}
+
+fn offchain_test_ext() -> (sp_io::TestExternalities, Arc<RwLock<PoolState>>) {
+	let (offchain, _) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	ext.register_extension(OffchainDbExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	(ext, pool_state)
+}
+
+fn submitted_transactions(pool_state: &RwLock<PoolState>) -> Vec<Extrinsic> {
+	pool_state
+		.write()
+		.transactions
+		.drain(..)
+		.map(|tx| Extrinsic::decode(&mut &tx[..]).unwrap())
+		.collect()
+}
+
+#[test]
+fn offchain_worker_submits_unsigned_results() {
+	let (mut ext, pool_state) = offchain_test_ext();
+	ext.execute_with(|| {
+		queue_verification_requests();
+		TemplateModule::offchain_worker(1);
+
+		let transactions = submitted_transactions(&pool_state);
+		assert_eq!(transactions.len(), 2);
+		for tx in transactions {
+			assert_eq!(tx.signature, None);
+			let (payload, signature) = match tx.call {
+				RuntimeCall::TemplateModule(crate::Call::submit_verification_result_unsigned {
+					payload,
+					signature,
+				}) => (payload, signature),
+				call => panic!("unexpected call {:?}", call),
+			};
+			assert_eq!(payload.public, UintAuthorityId(7));
+			assert_eq!(payload.valid, payload.request_id == 0);
+			let call = crate::Call::submit_verification_result_unsigned {
+				payload: payload.clone(),
+				signature: signature.clone(),
+			};
+			assert_ok!(TemplateModule::validate_unsigned(TransactionSource::Local, &call));
+			assert_ok!(TemplateModule::submit_verification_result_unsigned(
+				RuntimeOrigin::none(),
+				payload.clone(),
+				signature
+			));
+			System::assert_last_event(
+				Event::VerificationRequestProcessed {
+					request_id: payload.request_id,
+					who: payload.request_id as u64 + 1,
+					valid: payload.valid,
+				}
+				.into(),
+			);
+		}
+		assert_eq!(crate::VerificationRequests::<Test>::iter().count(), 0);
+		let (vk, inputs, proof) = groth16_fixture();
+		let wrong_inputs = utils::serialize_argument(vec![BlsFr::from(1u64)]);
+		let events: Vec<_> = System::events().into_iter().map(|record| record.event).collect();
+		assert!(events.contains(&groth16_event(1, true, &vk, &inputs, &proof)));
+		assert!(events.contains(&groth16_event(2, false, &vk, &wrong_inputs, &proof)));
+		// Only the valid request reaches the hook.
+		assert_eq!(VerifiedProofs::get(), vec![(1, 0, inputs)]);
+
+		TemplateModule::offchain_worker(2);
+		assert!(submitted_transactions(&pool_state).is_empty());
+	});
+}
+
+#[test]
+fn offchain_worker_submits_signed_results() {
+	let (mut ext, pool_state) = offchain_test_ext();
+	ext.execute_with(|| {
+		SignedResults::set(true);
+		let proof = queue_verification_requests();
+		TemplateModule::offchain_worker(1);
+
+		let transactions = submitted_transactions(&pool_state);
+		assert_eq!(transactions.len(), 2);
+		for tx in transactions {
+			assert_eq!(tx.signature, Some((0, ())));
+			assert!(matches!(
+				tx.call,
+				RuntimeCall::TemplateModule(crate::Call::submit_verification_result {
+					request_id,
+					valid,
+				}) if valid == (request_id == 0)
+			));
+		}
+
+		// Results of recently processed requests are not submitted again.
+		TemplateModule::offchain_worker(2);
+		assert!(submitted_transactions(&pool_state).is_empty());
+		TemplateModule::offchain_worker(6);
+		assert_eq!(submitted_transactions(&pool_state).len(), 2);
+
+		assert_noop!(
+			TemplateModule::submit_verification_result(RuntimeOrigin::signed(1), 0, true),
+			Error::<Test>::NotResultSubmitter
+		);
+		assert_ok!(TemplateModule::submit_verification_result(RuntimeOrigin::signed(7), 0, true));
+		assert_noop!(
+			TemplateModule::submit_verification_result(RuntimeOrigin::signed(7), 0, true),
+			Error::<Test>::UnknownVerificationRequest
+		);
+		assert_noop!(
+			TemplateModule::groth16_request_verification(
+				RuntimeOrigin::signed(1),
+				1,
+				vec![],
+				proof
+			),
+			Error::<Test>::UnknownVerifyingKey
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_only_counts_processed_requests() {
+	let (mut ext, pool_state) = offchain_test_ext();
+	ext.execute_with(|| {
+		let proof = queue_verification_requests();
+		TemplateModule::offchain_worker(1);
+		assert_eq!(submitted_transactions(&pool_state).len(), 2);
+
+		// The requests sent in block 1 do not use up `MaxOffchainVerifications` of block 2.
+		for _ in 0..5 {
+			assert_ok!(TemplateModule::groth16_request_verification(
+				RuntimeOrigin::signed(1),
+				0,
+				vec![],
+				proof.clone()
+			));
+		}
+		TemplateModule::offchain_worker(2);
+		assert_eq!(submitted_transactions(&pool_state).len(), 4);
+		TemplateModule::offchain_worker(3);
+		assert_eq!(submitted_transactions(&pool_state).len(), 1);
+	});
+}
+
+#[test]
+fn validate_unsigned_rejects_untrusted_results() {
+	new_test_ext().execute_with(|| {
+		queue_verification_requests();
+		let signed_call = |request_id, public: u64| {
+			let payload = VerificationResultPayload {
+				request_id,
+				valid: true,
+				public: UintAuthorityId(public),
+			};
+			let signature = UintAuthorityId(public).sign(&payload.encode()).unwrap();
+			crate::Call::submit_verification_result_unsigned { payload, signature }
+		};
+		assert_ok!(TemplateModule::validate_unsigned(
+			TransactionSource::External,
+			&signed_call(0, 7)
+		));
+		assert_eq!(
+			TemplateModule::validate_unsigned(TransactionSource::External, &signed_call(0, 8)),
+			InvalidTransaction::BadSigner.into()
+		);
+		assert_eq!(
+			TemplateModule::validate_unsigned(TransactionSource::External, &signed_call(2, 7)),
+			InvalidTransaction::Stale.into()
+		);
+		let forged = crate::Call::submit_verification_result_unsigned {
+			payload: VerificationResultPayload {
+				request_id: 1,
+				valid: true,
+				public: UintAuthorityId(7),
+			},
+			signature: TestSignature(7, Vec::new()),
+		};
+		assert_eq!(
+			TemplateModule::validate_unsigned(TransactionSource::External, &forged),
+			InvalidTransaction::BadProof.into()
+		);
+	});
+}
+
+/// Re-encodes a value of one BLS12-381 implementation as the same value of the other.
+fn convert<A: CanonicalSerialize, B: CanonicalDeserialize>(a: &A) -> B {
+	let mut bytes = Vec::new();
+	a.serialize_uncompressed(&mut bytes).unwrap();
+	B::deserialize_uncompressed(&bytes[..]).unwrap()
+}
+
+/// Random scalars, with zero and plus and minus one among them.
+fn scalar() -> impl Strategy<Value = BlsFr> {
+	prop_oneof![
+		1 => Just(BlsFr::from(0u64)),
+		1 => Just(BlsFr::from(1u64)),
+		1 => Just(-BlsFr::from(1u64)),
+		6 => any::<[u8; 32]>().prop_map(|bytes| BlsFr::from_le_bytes_mod_order(&bytes)),
+	]
+}
+
+fn g1_point() -> impl Strategy<Value = G1Affine> {
+	scalar().prop_map(|s| (G1Affine::generator() * s).into_affine())
+}
+
+fn g2_point() -> impl Strategy<Value = G2Affine> {
+	scalar().prop_map(|s| (G2Affine::generator() * s).into_affine())
+}
+
+// `Bls12_381Optimized` must compute exactly what `ark_bls12_381` does, only in host calls.
+proptest! {
+	#![proptest_config(ProptestConfig::with_cases(32))]
+
+	#[test]
+	fn host_msm_g1_matches_arkworks(
+		terms in proptest::collection::vec((g1_point(), scalar()), 1..8)
+	) {
+		let (bases, scalars): (Vec<_>, Vec<_>) = terms.into_iter().unzip();
+		new_test_ext().execute_with(|| {
+			let expected = G1Projective::msm(&bases, &scalars).unwrap();
+			let bases: Vec<G1AffineOptimized> = bases.iter().map(convert).collect();
+			let scalars: Vec<BlsFrOptimized> = scalars.iter().map(convert).collect();
+			let actual = G1ProjectiveOptimized::msm(&bases, &scalars).unwrap();
+			assert_eq!(
+				utils::serialize_argument(expected.into_affine()),
+				utils::serialize_argument(actual.into_affine())
+			);
+		});
+	}
+
+	#[test]
+	fn host_msm_g2_matches_arkworks(
+		terms in proptest::collection::vec((g2_point(), scalar()), 1..8)
+	) {
+		let (bases, scalars): (Vec<_>, Vec<_>) = terms.into_iter().unzip();
+		new_test_ext().execute_with(|| {
+			let expected = G2Projective::msm(&bases, &scalars).unwrap();
+			let bases: Vec<G2AffineOptimized> = bases.iter().map(convert).collect();
+			let scalars: Vec<BlsFrOptimized> = scalars.iter().map(convert).collect();
+			let actual = G2ProjectiveOptimized::msm(&bases, &scalars).unwrap();
+			assert_eq!(
+				utils::serialize_argument(expected.into_affine()),
+				utils::serialize_argument(actual.into_affine())
+			);
+		});
+	}
+
+	#[test]
+	fn host_mul_matches_arkworks(g1 in g1_point(), g2 in g2_point(), s in scalar()) {
+		new_test_ext().execute_with(|| {
+			let (g1_host, g2_host): (G1AffineOptimized, G2AffineOptimized) =
+				(convert(&g1), convert(&g2));
+			let s_host: BlsFrOptimized = convert(&s);
+
+			let expected = utils::serialize_argument((g1 * s).into_affine());
+			assert_eq!(utils::serialize_argument((g1_host * s_host).into_affine()), expected);
+			assert_eq!(
+				utils::serialize_argument((g1_host.into_group() * s_host).into_affine()),
+				expected
+			);
+			let expected = utils::serialize_argument((g2 * s).into_affine());
+			assert_eq!(utils::serialize_argument((g2_host * s_host).into_affine()), expected);
+			assert_eq!(
+				utils::serialize_argument((g2_host.into_group() * s_host).into_affine()),
+				expected
+			);
+		});
+	}
+
+	#[test]
+	fn host_pairing_matches_arkworks(
+		pairs in proptest::collection::vec((g1_point(), g2_point()), 1..4)
+	) {
+		let (a, b): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
+		new_test_ext().execute_with(|| {
+			let expected = Bls12_381::multi_miller_loop(a.clone(), b.clone());
+			let a: Vec<G1AffineOptimized> = a.iter().map(convert).collect();
+			let b: Vec<G2AffineOptimized> = b.iter().map(convert).collect();
+			let actual = Bls12_381Optimized::multi_miller_loop(a, b);
+			assert_eq!(utils::serialize_argument(expected.0), utils::serialize_argument(actual.0));
+			assert_eq!(
+				Bls12_381::final_exponentiation(expected).map(|f| utils::serialize_argument(f.0)),
+				Bls12_381Optimized::final_exponentiation(actual)
+					.map(|f| utils::serialize_argument(f.0))
+			);
+		});
+	}
+
+	#[test]
+	fn host_final_exponentiation_matches_arkworks(
+		seed in any::<u64>(),
+		zero in proptest::bool::weighted(0.1)
+	) {
+		let f = if zero { Fq12::from(0u64) } else { Fq12::rand(&mut StdRng::seed_from_u64(seed)) };
+		new_test_ext().execute_with(|| {
+			let expected = Bls12_381::final_exponentiation(MillerLoopOutput(f));
+			let actual = Bls12_381Optimized::final_exponentiation(MillerLoopOutput(convert(&f)));
+			assert_eq!(
+				expected.map(|f| utils::serialize_argument(f.0)),
+				actual.map(|f| utils::serialize_argument(f.0))
+			);
+		});
+	}
+}
@@ -1,5 +1,7 @@
+use ark_ff::PrimeField;
 use ark_serialize::{CanonicalSerialize, Compress};
 use ark_std::{io::Cursor, vec, vec::Vec};
+use sha2::{Digest, Sha256};
 
 pub fn serialize_argument(argument: impl CanonicalSerialize) -> Vec<u8> {
 	let mut serialized_argument = vec![0u8; argument.serialized_size(Compress::No)];
@@ -7,3 +9,36 @@ pub fn serialize_argument(argument: impl CanonicalSerialize) -> Vec<u8> {
 	argument.serialize_uncompressed(&mut cursor).unwrap();
 	serialized_argument
 }
+
+/// Fiat-Shamir transcript hashing every message into a running SHA-256 state.
+pub(crate) struct Transcript {
+	state: [u8; 32],
+}
+
+impl Transcript {
+	/// Starts a transcript from the hash of the `protocol` label.
+	pub(crate) fn new(protocol: &[u8]) -> Self {
+		Self { state: Sha256::digest(protocol).into() }
+	}
+
+	/// Starts a transcript from the all zero state, as PLONK proofs always did.
+	pub(crate) fn unseeded() -> Self {
+		Self { state: [0u8; 32] }
+	}
+
+	pub(crate) fn append<S: CanonicalSerialize + ?Sized>(&mut self, label: &[u8], message: &S) {
+		let mut bytes = Vec::with_capacity(message.compressed_size());
+		message.serialize_compressed(&mut bytes).expect("writing to a vec; qed");
+		self.state = Sha256::new()
+			.chain_update(self.state)
+			.chain_update(label)
+			.chain_update(bytes)
+			.finalize()
+			.into();
+	}
+
+	pub(crate) fn challenge<F: PrimeField>(&mut self, label: &[u8]) -> F {
+		self.state = Sha256::new().chain_update(self.state).chain_update(label).finalize().into();
+		F::from_be_bytes_mod_order(&self.state)
+	}
+}
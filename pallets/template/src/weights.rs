@@ -0,0 +1,721 @@
+//! Weights for pallet_template
+//!
+//! Only the Groth16 extrinsics of the baseline are measured, as the means of the checked in
+//! `results.json`. Every other weight, marked `Estimated` below, is written by hand and has not
+//! been produced by a benchmark yet. `make benchmark-weights` replaces them all with the results
+//! of the pallet's benchmarks through `.maintain/frame-weight-template.hbs`:
+//!
+//! ./target/release/node-template benchmark pallet --chain dev --execution=wasm
+//! --wasm-execution=compiled --pallet pallet_template --extrinsic "*" --steps 50 --repeat 20
+//! --template .maintain/frame-weight-template.hbs --output pallets/template/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_template.
+pub trait WeightInfo {
+	fn groth16_verification() -> Weight;
+	fn groth16_verification_optimized() -> Weight;
+	fn groth16_verification_optimized_prepared() -> Weight;
+	fn groth16_verification_host(n: u32) -> Weight;
+	fn groth16_prepare_inputs() -> Weight;
+	fn groth16_optimized_prepare_inputs() -> Weight;
+	fn groth16_prepare_verifying_key() -> Weight;
+	fn groth16_optimized_prepare_verifying_key() -> Weight;
+	fn groth16_verify_with_prepared_inputs() -> Weight;
+	fn groth16_optimized_verify_with_prepared_inputs() -> Weight;
+	fn bls_verify() -> Weight;
+	fn bls_aggregate_verify(n: u32) -> Weight;
+	fn bls_fast_aggregate_verify(n: u32) -> Weight;
+	fn bls_verify_min_sig() -> Weight;
+	fn bls_aggregate_verify_min_sig(n: u32) -> Weight;
+	fn bls_fast_aggregate_verify_min_sig(n: u32) -> Weight;
+	fn kzg_set_trusted_setup() -> Weight;
+	fn verify_kzg_proof() -> Weight;
+	fn verify_kzg_proof_batch(n: u32) -> Weight;
+	fn plonk_register_vk() -> Weight;
+	fn plonk_verify() -> Weight;
+	fn plonk_verify_optimized() -> Weight;
+	fn plonk_verify_bn254() -> Weight;
+	fn marlin_register_vk() -> Weight;
+	fn marlin_verify() -> Weight;
+	fn marlin_verify_optimized() -> Weight;
+	fn groth16_register_vk() -> Weight;
+	fn groth16_verify_prepared() -> Weight;
+	fn groth16_set_aggregation_srs() -> Weight;
+	fn groth16_verify_aggregate(l: u32) -> Weight;
+	fn groth16_request_verification() -> Weight;
+	fn set_result_submitters() -> Weight;
+	fn submit_verification_result() -> Weight;
+	fn groth16_verify_audited() -> Weight;
+	fn groth16_approve_vk() -> Weight;
+	fn groth16_freeze_vk() -> Weight;
+	fn groth16_deprecate_vk() -> Weight;
+	fn groth16_publish_circuit_version() -> Weight;
+	fn groth16_verify_circuit() -> Weight;
+	fn plonk_set_srs() -> Weight;
+	fn plonk_remove_vk() -> Weight;
+	fn marlin_set_srs() -> Weight;
+	fn marlin_remove_vk() -> Weight;
+}
+
+/// Weights for pallet_template using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn groth16_verification() -> Weight {
+		Weight::from_parts(23_551_280_000, 0)
+	}
+	// Storage: TemplateModule Groth16VerifyingKeyIds (r:1 w:0)
+	fn groth16_verification_optimized() -> Weight {
+		Weight::from_parts(3_548_190_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule Groth16VerifyingKeyIds (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:1 w:0)
+	fn groth16_verification_optimized_prepared() -> Weight {
+		Weight::from_parts(2_210_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+	}
+	// Estimated, not benchmarked.
+	fn groth16_verification_host(n: u32) -> Weight {
+		Weight::from_parts(2_522_000_000, 0)
+			.saturating_add(Weight::from_parts(90_000_000, 0).saturating_mul(n.into()))
+	}
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule NextPreparedInputsHandle (r:1 w:1)
+	// Storage: TemplateModule PreparedInputs (r:0 w:1)
+	fn groth16_prepare_inputs() -> Weight {
+		Weight::from_parts(1_796_180_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule NextPreparedInputsHandle (r:1 w:1)
+	// Storage: TemplateModule PreparedInputs (r:0 w:1)
+	fn groth16_optimized_prepare_inputs() -> Weight {
+		Weight::from_parts(266_890_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: TemplateModule NextGroth16VerifyingKeyId (r:1 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:0 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyIds (r:0 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeys (r:0 w:1)
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:0 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:0 w:1)
+	fn groth16_prepare_verifying_key() -> Weight {
+		Weight::from_parts(10_917_280_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	// Storage: TemplateModule NextGroth16VerifyingKeyId (r:1 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:0 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyIds (r:0 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeys (r:0 w:1)
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:0 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:0 w:1)
+	fn groth16_optimized_prepare_verifying_key() -> Weight {
+		Weight::from_parts(1_353_790_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	// Storage: TemplateModule PreparedInputs (r:1 w:1)
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	fn groth16_verify_with_prepared_inputs() -> Weight {
+		Weight::from_parts(11_761_420_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: TemplateModule PreparedInputs (r:1 w:1)
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	fn groth16_optimized_verify_with_prepared_inputs() -> Weight {
+		Weight::from_parts(1_988_770_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Estimated, not benchmarked.
+	fn bls_verify() -> Weight {
+		Weight::from_parts(2_540_000_000, 0)
+	}
+	// Estimated, not benchmarked.
+	fn bls_aggregate_verify(n: u32) -> Weight {
+		Weight::from_parts(1_480_000_000, 0)
+			.saturating_add(Weight::from_parts(1_210_000_000, 0).saturating_mul(n.into()))
+	}
+	// Estimated, not benchmarked.
+	fn bls_fast_aggregate_verify(n: u32) -> Weight {
+		Weight::from_parts(2_510_000_000, 0)
+			.saturating_add(Weight::from_parts(205_000_000, 0).saturating_mul(n.into()))
+	}
+	// Estimated, not benchmarked.
+	fn bls_verify_min_sig() -> Weight {
+		Weight::from_parts(2_230_000_000, 0)
+	}
+	// Estimated, not benchmarked.
+	fn bls_aggregate_verify_min_sig(n: u32) -> Weight {
+		Weight::from_parts(1_470_000_000, 0)
+			.saturating_add(Weight::from_parts(1_020_000_000, 0).saturating_mul(n.into()))
+	}
+	// Estimated, not benchmarked.
+	fn bls_fast_aggregate_verify_min_sig(n: u32) -> Weight {
+		Weight::from_parts(2_220_000_000, 0)
+			.saturating_add(Weight::from_parts(452_000_000, 0).saturating_mul(n.into()))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule KzgTrustedSetup (r:0 w:1)
+	fn kzg_set_trusted_setup() -> Weight {
+		Weight::from_parts(498_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule KzgTrustedSetup (r:1 w:0)
+	fn verify_kzg_proof() -> Weight {
+		Weight::from_parts(2_030_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule KzgTrustedSetup (r:1 w:0)
+	fn verify_kzg_proof_batch(n: u32) -> Weight {
+		Weight::from_parts(1_010_000_000, 0)
+			.saturating_add(Weight::from_parts(352_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule PlonkSrs (r:1 w:0)
+	// Storage: TemplateModule NextPlonkVerifyingKeyId (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule PlonkVerifyingKeys (r:0 w:1)
+	// Storage: TemplateModule PlonkVerifyingKeyDeposits (r:0 w:1)
+	fn plonk_register_vk() -> Weight {
+		Weight::from_parts(5_120_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule PlonkVerifyingKeys (r:1 w:0)
+	fn plonk_verify() -> Weight {
+		Weight::from_parts(19_740_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule PlonkVerifyingKeys (r:1 w:0)
+	fn plonk_verify_optimized() -> Weight {
+		Weight::from_parts(4_510_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule PlonkVerifyingKeys (r:1 w:0)
+	fn plonk_verify_bn254() -> Weight {
+		Weight::from_parts(13_860_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule MarlinSrs (r:1 w:0)
+	// Storage: TemplateModule NextMarlinVerifyingKeyId (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule MarlinVerifyingKeys (r:0 w:1)
+	// Storage: TemplateModule MarlinVerifyingKeyDeposits (r:0 w:1)
+	fn marlin_register_vk() -> Weight {
+		Weight::from_parts(8_270_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule MarlinVerifyingKeys (r:1 w:0)
+	fn marlin_verify() -> Weight {
+		Weight::from_parts(36_580_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule MarlinVerifyingKeys (r:1 w:0)
+	fn marlin_verify_optimized() -> Weight {
+		Weight::from_parts(7_640_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule NextGroth16VerifyingKeyId (r:1 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:0 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyIds (r:0 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeys (r:0 w:1)
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:0 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:0 w:1)
+	fn groth16_register_vk() -> Weight {
+		Weight::from_parts(3_020_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	fn groth16_verify_prepared() -> Weight {
+		Weight::from_parts(2_310_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule AggregationSrs (r:0 w:1)
+	fn groth16_set_aggregation_srs() -> Weight {
+		Weight::from_parts(6_120_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule Groth16VerifyingKeys (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule AggregationSrs (r:1 w:0)
+	fn groth16_verify_aggregate(l: u32) -> Weight {
+		Weight::from_parts(9_120_000_000, 0)
+			.saturating_add(Weight::from_parts(7_940_000_000, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule NextVerificationRequestId (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule VerificationRequests (r:0 w:1)
+	// Storage: TemplateModule VerificationRequestDeposits (r:0 w:1)
+	fn groth16_request_verification() -> Weight {
+		Weight::from_parts(48_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule ResultSubmitters (r:0 w:1)
+	fn set_result_submitters() -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule ResultSubmitters (r:1 w:0)
+	// Storage: TemplateModule VerificationRequests (r:1 w:1)
+	// Storage: TemplateModule VerificationRequestDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	fn submit_verification_result() -> Weight {
+		Weight::from_parts(62_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:1 w:0)
+	fn groth16_verify_audited() -> Weight {
+		Weight::from_parts(2_330_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:0 w:1)
+	fn groth16_approve_vk() -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:0 w:1)
+	fn groth16_freeze_vk() -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:0 w:1)
+	fn groth16_deprecate_vk() -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule LatestCircuitVersions (r:1 w:1)
+	// Storage: TemplateModule CircuitVersions (r:0 w:2)
+	fn groth16_publish_circuit_version() -> Weight {
+		Weight::from_parts(31_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule CircuitVersions (r:1 w:0)
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	fn groth16_verify_circuit() -> Weight {
+		Weight::from_parts(2_350_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule PlonkSrs (r:0 w:1)
+	fn plonk_set_srs() -> Weight {
+		Weight::from_parts(412_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule PlonkVerifyingKeyDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule PlonkVerifyingKeys (r:0 w:1)
+	fn plonk_remove_vk() -> Weight {
+		Weight::from_parts(38_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule MarlinSrs (r:0 w:1)
+	fn marlin_set_srs() -> Weight {
+		Weight::from_parts(412_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule MarlinVerifyingKeyDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule MarlinVerifyingKeys (r:0 w:1)
+	fn marlin_remove_vk() -> Weight {
+		Weight::from_parts(38_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn groth16_verification() -> Weight {
+		Weight::from_parts(23_551_280_000, 0)
+	}
+	// Storage: TemplateModule Groth16VerifyingKeyIds (r:1 w:0)
+	fn groth16_verification_optimized() -> Weight {
+		Weight::from_parts(3_548_190_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule Groth16VerifyingKeyIds (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:1 w:0)
+	fn groth16_verification_optimized_prepared() -> Weight {
+		Weight::from_parts(2_210_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+	}
+	// Estimated, not benchmarked.
+	fn groth16_verification_host(n: u32) -> Weight {
+		Weight::from_parts(2_522_000_000, 0)
+			.saturating_add(Weight::from_parts(90_000_000, 0).saturating_mul(n.into()))
+	}
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule NextPreparedInputsHandle (r:1 w:1)
+	// Storage: TemplateModule PreparedInputs (r:0 w:1)
+	fn groth16_prepare_inputs() -> Weight {
+		Weight::from_parts(1_796_180_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule NextPreparedInputsHandle (r:1 w:1)
+	// Storage: TemplateModule PreparedInputs (r:0 w:1)
+	fn groth16_optimized_prepare_inputs() -> Weight {
+		Weight::from_parts(266_890_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	// Storage: TemplateModule NextGroth16VerifyingKeyId (r:1 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:0 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyIds (r:0 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeys (r:0 w:1)
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:0 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:0 w:1)
+	fn groth16_prepare_verifying_key() -> Weight {
+		Weight::from_parts(10_917_280_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	// Storage: TemplateModule NextGroth16VerifyingKeyId (r:1 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:0 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyIds (r:0 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeys (r:0 w:1)
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:0 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:0 w:1)
+	fn groth16_optimized_prepare_verifying_key() -> Weight {
+		Weight::from_parts(1_353_790_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	// Storage: TemplateModule PreparedInputs (r:1 w:1)
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	fn groth16_verify_with_prepared_inputs() -> Weight {
+		Weight::from_parts(11_761_420_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Storage: TemplateModule PreparedInputs (r:1 w:1)
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	fn groth16_optimized_verify_with_prepared_inputs() -> Weight {
+		Weight::from_parts(1_988_770_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Estimated, not benchmarked.
+	fn bls_verify() -> Weight {
+		Weight::from_parts(2_540_000_000, 0)
+	}
+	// Estimated, not benchmarked.
+	fn bls_aggregate_verify(n: u32) -> Weight {
+		Weight::from_parts(1_480_000_000, 0)
+			.saturating_add(Weight::from_parts(1_210_000_000, 0).saturating_mul(n.into()))
+	}
+	// Estimated, not benchmarked.
+	fn bls_fast_aggregate_verify(n: u32) -> Weight {
+		Weight::from_parts(2_510_000_000, 0)
+			.saturating_add(Weight::from_parts(205_000_000, 0).saturating_mul(n.into()))
+	}
+	// Estimated, not benchmarked.
+	fn bls_verify_min_sig() -> Weight {
+		Weight::from_parts(2_230_000_000, 0)
+	}
+	// Estimated, not benchmarked.
+	fn bls_aggregate_verify_min_sig(n: u32) -> Weight {
+		Weight::from_parts(1_470_000_000, 0)
+			.saturating_add(Weight::from_parts(1_020_000_000, 0).saturating_mul(n.into()))
+	}
+	// Estimated, not benchmarked.
+	fn bls_fast_aggregate_verify_min_sig(n: u32) -> Weight {
+		Weight::from_parts(2_220_000_000, 0)
+			.saturating_add(Weight::from_parts(452_000_000, 0).saturating_mul(n.into()))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule KzgTrustedSetup (r:0 w:1)
+	fn kzg_set_trusted_setup() -> Weight {
+		Weight::from_parts(498_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule KzgTrustedSetup (r:1 w:0)
+	fn verify_kzg_proof() -> Weight {
+		Weight::from_parts(2_030_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule KzgTrustedSetup (r:1 w:0)
+	fn verify_kzg_proof_batch(n: u32) -> Weight {
+		Weight::from_parts(1_010_000_000, 0)
+			.saturating_add(Weight::from_parts(352_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule PlonkSrs (r:1 w:0)
+	// Storage: TemplateModule NextPlonkVerifyingKeyId (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule PlonkVerifyingKeys (r:0 w:1)
+	// Storage: TemplateModule PlonkVerifyingKeyDeposits (r:0 w:1)
+	fn plonk_register_vk() -> Weight {
+		Weight::from_parts(5_120_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule PlonkVerifyingKeys (r:1 w:0)
+	fn plonk_verify() -> Weight {
+		Weight::from_parts(19_740_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule PlonkVerifyingKeys (r:1 w:0)
+	fn plonk_verify_optimized() -> Weight {
+		Weight::from_parts(4_510_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule PlonkVerifyingKeys (r:1 w:0)
+	fn plonk_verify_bn254() -> Weight {
+		Weight::from_parts(13_860_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule MarlinSrs (r:1 w:0)
+	// Storage: TemplateModule NextMarlinVerifyingKeyId (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule MarlinVerifyingKeys (r:0 w:1)
+	// Storage: TemplateModule MarlinVerifyingKeyDeposits (r:0 w:1)
+	fn marlin_register_vk() -> Weight {
+		Weight::from_parts(8_270_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule MarlinVerifyingKeys (r:1 w:0)
+	fn marlin_verify() -> Weight {
+		Weight::from_parts(36_580_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule MarlinVerifyingKeys (r:1 w:0)
+	fn marlin_verify_optimized() -> Weight {
+		Weight::from_parts(7_640_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule NextGroth16VerifyingKeyId (r:1 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:0 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyIds (r:0 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeys (r:0 w:1)
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:0 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:0 w:1)
+	fn groth16_register_vk() -> Weight {
+		Weight::from_parts(3_020_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	fn groth16_verify_prepared() -> Weight {
+		Weight::from_parts(2_310_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule AggregationSrs (r:0 w:1)
+	fn groth16_set_aggregation_srs() -> Weight {
+		Weight::from_parts(6_120_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule Groth16VerifyingKeys (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule AggregationSrs (r:1 w:0)
+	fn groth16_verify_aggregate(l: u32) -> Weight {
+		Weight::from_parts(9_120_000_000, 0)
+			.saturating_add(Weight::from_parts(7_940_000_000, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule NextVerificationRequestId (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule VerificationRequests (r:0 w:1)
+	// Storage: TemplateModule VerificationRequestDeposits (r:0 w:1)
+	fn groth16_request_verification() -> Weight {
+		Weight::from_parts(48_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule ResultSubmitters (r:0 w:1)
+	fn set_result_submitters() -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule ResultSubmitters (r:1 w:0)
+	// Storage: TemplateModule VerificationRequests (r:1 w:1)
+	// Storage: TemplateModule VerificationRequestDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	fn submit_verification_result() -> Weight {
+		Weight::from_parts(62_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:1 w:0)
+	fn groth16_verify_audited() -> Weight {
+		Weight::from_parts(2_330_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:0 w:1)
+	fn groth16_approve_vk() -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:0 w:1)
+	fn groth16_freeze_vk() -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:0 w:1)
+	fn groth16_deprecate_vk() -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule LatestCircuitVersions (r:1 w:1)
+	// Storage: TemplateModule CircuitVersions (r:0 w:2)
+	fn groth16_publish_circuit_version() -> Weight {
+		Weight::from_parts(31_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule CircuitVersions (r:1 w:0)
+	// Storage: TemplateModule Groth16PreparedVerifyingKeys (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyStatuses (r:1 w:0)
+	// Storage: TemplateModule Groth16VerifyingKeyHashes (r:1 w:0)
+	fn groth16_verify_circuit() -> Weight {
+		Weight::from_parts(2_350_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule PlonkSrs (r:0 w:1)
+	fn plonk_set_srs() -> Weight {
+		Weight::from_parts(412_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule PlonkVerifyingKeyDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule PlonkVerifyingKeys (r:0 w:1)
+	fn plonk_remove_vk() -> Weight {
+		Weight::from_parts(38_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule MarlinSrs (r:0 w:1)
+	fn marlin_set_srs() -> Weight {
+		Weight::from_parts(412_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Estimated, not benchmarked.
+	// Storage: TemplateModule MarlinVerifyingKeyDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule MarlinVerifyingKeys (r:0 w:1)
+	fn marlin_remove_vk() -> Weight {
+		Weight::from_parts(38_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+}
@@ -6,6 +6,7 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+use codec::Encode;
 use pallet_grandpa::AuthorityId as GrandpaId;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -13,9 +14,10 @@ use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{
-		AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify,
+		AccountIdLookup, BlakeTwo256, Block as BlockT, Extrinsic as ExtrinsicT, IdentifyAccount,
+		NumberFor, One, Verify,
 	},
-	transaction_validity::{TransactionSource, TransactionValidity},
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
 };
 use sp_std::prelude::*;
@@ -27,7 +29,8 @@ use sp_version::RuntimeVersion;
 pub use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{
-		ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness, StorageInfo,
+		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness,
+		StorageInfo,
 	},
 	weights::{
 		constants::{
@@ -39,6 +42,7 @@ pub use frame_support::{
 };
 pub use frame_system::Call as SystemCall;
 pub use pallet_balances::Call as BalancesCall;
+pub use pallet_template::Call as TemplateCall;
 pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::{ConstFeeMultiplier, CurrencyAdapter, Multiplier};
 #[cfg(any(feature = "std", test))]
@@ -48,6 +52,12 @@ pub use sp_runtime::{Perbill, Permill};
 /// Import the template pallet.
 pub use pallet_template;
 
+/// Import the sync committee light client pallet.
+pub use pallet_sync_committee;
+
+/// Import the example consumer of verified proofs.
+pub use pallet_proof_claims;
+
 /// An index to a block.
 pub type BlockNumber = u32;
 
@@ -270,6 +280,74 @@ impl pallet_sudo::Config for Runtime {
 /// Configure the pallet-template in pallets/template.
 impl pallet_template::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type PreparedInputsLifetime = ConstU32<{ 10 * MINUTES }>;
+	type AuthorityId = pallet_template::offchain::crypto::OffchainAuthId;
+	type SignedResults = ConstBool<false>;
+	type MaxOffchainVerifications = ConstU32<16>;
+	type UnsignedPriority = ConstU64<{ TransactionPriority::MAX / 2 }>;
+	type OnProofVerified = ProofClaims;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type CircuitGracePeriod = ConstU32<{ 7 * DAYS }>;
+	type Currency = Balances;
+	type VerifyingKeyDeposit = ConstU128<{ 1_000 * EXISTENTIAL_DEPOSIT }>;
+	type VerificationRequestDeposit = ConstU128<{ 10 * EXISTENTIAL_DEPOSIT }>;
+	type WeightInfo = pallet_template::weights::SubstrateWeight<Runtime>;
+}
+
+/// Configure the example consumer of verified proofs in pallets/proof-claims.
+impl pallet_proof_claims::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		public: <Signature as Verify>::Signer,
+		account: AccountId,
+		nonce: Index,
+	) -> Option<(RuntimeCall, <UncheckedExtrinsic as ExtrinsicT>::SignaturePayload)> {
+		let period = BlockHashCount::get().checked_next_power_of_two().map(|c| c / 2).unwrap_or(2);
+		let current_block = System::block_number().saturating_sub(1);
+		let extra: SignedExtra = (
+			frame_system::CheckNonZeroSender::<Runtime>::new(),
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(generic::Era::mortal(
+				period as u64,
+				current_block as u64,
+			)),
+			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(0),
+		);
+		let raw_payload = SignedPayload::new(call, extra).ok()?;
+		let signature = raw_payload.using_encoded(|payload| C::sign(payload, public))?;
+		let (call, extra, _) = raw_payload.deconstruct();
+		Some((call, (sp_runtime::MultiAddress::Id(account), signature, extra)))
+	}
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	RuntimeCall: From<C>,
+{
+	type Extrinsic = UncheckedExtrinsic;
+	type OverarchingCall = RuntimeCall;
+}
+
+/// Configure the Ethereum sync committee light client in pallets/sync-committee.
+impl pallet_sync_committee::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type InitializeOrigin = frame_system::EnsureRoot<AccountId>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -289,6 +367,8 @@ construct_runtime!(
 		Sudo: pallet_sudo,
 		// Include the custom logic from the pallet-template in the runtime.
 		TemplateModule: pallet_template,
+		SyncCommittee: pallet_sync_committee,
+		ProofClaims: pallet_proof_claims,
 	}
 );
 
@@ -322,7 +402,10 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
+/// The migrations of the pallets' storage to run on the next runtime upgrade.
+pub type Migrations = (pallet_template::migrations::v1::MigrateToV1<Runtime>,);
 
 #[cfg(feature = "runtime-benchmarks")]
 #[macro_use]
@@ -0,0 +1,287 @@
+//! Executes a block of every Groth16 call of the template pallet once with the native runtime
+//! and once with the wasm runtime, as the `NativeElseWasmExecutor` of the node may, and checks
+//! that both give the same results, events, header and storage root. A host function of the
+//! optimized curves that computes differently natively and in wasm would fork the nodes of the
+//! two strategies.
+//!
+//! The SnarkPack aggregate is the checked in `snarkpack::aggregate_fixture`, as the prover is
+//! only built for the tests and benchmarks of the pallet.
+
+use codec::{Decode, Encode};
+use frame_system::EventRecord;
+use node_template_runtime::{
+	pallet_template::{self, bls12_381, snarkpack, utils::serialize_argument},
+	AccountId, BalancesConfig, BuildStorage, GenesisConfig, Hash, Header, Runtime, RuntimeCall,
+	RuntimeEvent, Signature, SignedExtra, SignedPayload, SudoConfig, SystemConfig, TemplateCall,
+	TimestampCall, UncheckedExtrinsic, VERSION, WASM_BINARY,
+};
+use sc_executor::{NativeElseWasmExecutor, NativeExecutionDispatch, NativeVersion};
+use sp_core::{
+	blake2_256,
+	traits::{CodeExecutor, RuntimeCode, WrappedRuntimeCode},
+	Pair,
+};
+use sp_io::TestExternalities;
+use sp_keyring::AccountKeyring;
+use sp_runtime::{generic::Era, traits::Header as _, ApplyExtrinsicResult, Storage};
+
+/// The executor of the node, with the host functions it adds to those of Substrate.
+struct ExecutorDispatch;
+
+impl NativeExecutionDispatch for ExecutorDispatch {
+	#[cfg(feature = "experimental")]
+	type ExtendHostFunctions = pallet_template::host::groth16::HostFunctions;
+	#[cfg(not(feature = "experimental"))]
+	type ExtendHostFunctions = ();
+
+	fn dispatch(method: &str, data: &[u8]) -> Option<Vec<u8>> {
+		node_template_runtime::api::dispatch(method, data)
+	}
+
+	fn native_version() -> NativeVersion {
+		node_template_runtime::native_version()
+	}
+}
+
+fn wasm_binary() -> &'static [u8] {
+	WASM_BINARY.expect("the wasm runtime is not built with `SKIP_WASM_BUILD`")
+}
+
+fn genesis_storage() -> Storage {
+	let alice = AccountKeyring::Alice.to_account_id();
+	GenesisConfig {
+		system: SystemConfig { code: wasm_binary().to_vec() },
+		balances: BalancesConfig { balances: vec![(alice.clone(), 1 << 60)] },
+		sudo: SudoConfig { key: Some(alice) },
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap()
+}
+
+/// The number of `groth16_calls` that fail, of the `Groth16Verified` events of the others and of
+/// those dispatched by sudo.
+const FAILED_CALLS: usize = 1;
+const VERIFIED_EVENTS: usize = if cfg!(feature = "experimental") { 8 } else { 7 };
+const SUDO_CALLS: usize = 5;
+
+/// `call` dispatched by the sudo key, for the calls of the admin origin.
+fn sudo(call: TemplateCall<Runtime>) -> RuntimeCall {
+	pallet_sudo::Call::sudo { call: Box::new(call.into()) }.into()
+}
+
+/// Every Groth16 call of the template pallet with valid arguments, the key registered by
+/// `groth16_prepare_verifying_key` under id 0 and the inputs stored for it under handles 0 and
+/// 1, and calls with a proof that does not verify. The keys registered next get the ids 1 to 3,
+/// the last one that of the aggregate. `groth16_verification_host` only with the `experimental`
+/// feature.
+fn groth16_calls() -> Vec<RuntimeCall> {
+	let (vk, c, proof) = bls12_381::groth16_fixture();
+	let (aggregate_vk, srs, inputs_list, aggregate_proof) = snarkpack::aggregate_fixture();
+	let mut tampered = proof.clone();
+	tampered[100] ^= 1;
+	let public_inputs = serialize_argument(vec![c]);
+	let c = serialize_argument(c);
+
+	#[cfg(feature = "experimental")]
+	let host_call = TemplateCall::groth16_verification_host {
+		vk: vk.clone(),
+		inputs: c.clone(),
+		proof: proof.clone(),
+	};
+	let name = b"cube".to_vec();
+	#[allow(unused_mut)]
+	let mut calls = vec![
+		TemplateCall::groth16_verification { vk: vk.clone(), c: c.clone(), proof: proof.clone() }
+			.into(),
+		TemplateCall::groth16_verification_optimized {
+			vk: vk.clone(),
+			c: c.clone(),
+			proof: proof.clone(),
+		}
+		.into(),
+		TemplateCall::groth16_verification_optimized {
+			vk: vk.clone(),
+			c: c.clone(),
+			proof: tampered.clone(),
+		}
+		.into(),
+		TemplateCall::groth16_prepare_verifying_key { vk: vk.clone() }.into(),
+		TemplateCall::groth16_optimized_prepare_verifying_key { vk: vk.clone() }.into(),
+		sudo(TemplateCall::groth16_approve_vk { vk_id: 0 }),
+		TemplateCall::groth16_prepare_inputs { vk_id: 0, public_inputs: public_inputs.clone() }
+			.into(),
+		TemplateCall::groth16_optimized_prepare_inputs {
+			vk_id: 0,
+			public_inputs: public_inputs.clone(),
+		}
+		.into(),
+		TemplateCall::groth16_verify_with_prepared_inputs { handle: 0, proof: proof.clone() }
+			.into(),
+		TemplateCall::groth16_optimized_verify_with_prepared_inputs {
+			handle: 1,
+			proof: proof.clone(),
+		}
+		.into(),
+		TemplateCall::groth16_register_vk { vk }.into(),
+		TemplateCall::groth16_verify_prepared {
+			vk_id: 0,
+			public_inputs: public_inputs.clone(),
+			proof: proof.clone(),
+		}
+		.into(),
+		TemplateCall::groth16_request_verification {
+			vk_id: 0,
+			public_inputs: public_inputs.clone(),
+			proof: proof.clone(),
+		}
+		.into(),
+		TemplateCall::groth16_verify_audited {
+			vk_id: 0,
+			public_inputs: public_inputs.clone(),
+			proof: tampered,
+		}
+		.into(),
+		TemplateCall::groth16_register_vk { vk: aggregate_vk }.into(),
+		sudo(TemplateCall::groth16_set_aggregation_srs { srs }),
+		TemplateCall::groth16_verify_aggregate { vk_id: 3, inputs_list, aggregate_proof }.into(),
+		sudo(TemplateCall::groth16_publish_circuit_version { name: name.clone(), vk_id: 0 }),
+		TemplateCall::groth16_verify_circuit { name, version: 1, public_inputs, proof }.into(),
+		sudo(TemplateCall::groth16_freeze_vk { vk_id: 1 }),
+		sudo(TemplateCall::groth16_deprecate_vk { vk_id: 2 }),
+	];
+	#[cfg(feature = "experimental")]
+	calls.insert(3, host_call.into());
+	calls
+}
+
+/// An immortal extrinsic of `call` signed by Alice.
+fn signed(call: RuntimeCall, nonce: u32, genesis_hash: Hash) -> UncheckedExtrinsic {
+	let sender = AccountKeyring::Alice.pair();
+	let extra: SignedExtra = (
+		frame_system::CheckNonZeroSender::<Runtime>::new(),
+		frame_system::CheckSpecVersion::<Runtime>::new(),
+		frame_system::CheckTxVersion::<Runtime>::new(),
+		frame_system::CheckGenesis::<Runtime>::new(),
+		frame_system::CheckEra::<Runtime>::from(Era::Immortal),
+		frame_system::CheckNonce::<Runtime>::from(nonce),
+		frame_system::CheckWeight::<Runtime>::new(),
+		pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(0),
+	);
+	let payload = SignedPayload::from_raw(
+		call.clone(),
+		extra.clone(),
+		(
+			(),
+			VERSION.spec_version,
+			VERSION.transaction_version,
+			genesis_hash,
+			genesis_hash,
+			(),
+			(),
+			(),
+		),
+	);
+	let signature = payload.using_encoded(|payload| sender.sign(payload));
+	UncheckedExtrinsic::new_signed(
+		call,
+		AccountId::from(sender.public()).into(),
+		Signature::Sr25519(signature),
+		extra,
+	)
+}
+
+/// What executing the block of `groth16_calls` changed.
+struct Outcome {
+	results: Vec<ApplyExtrinsicResult>,
+	events: Vec<EventRecord<RuntimeEvent, Hash>>,
+	header: Header,
+	storage_root: Hash,
+}
+
+/// Builds the block of the timestamp inherent and `groth16_calls` on top of genesis, natively
+/// or in wasm.
+fn execute(use_native: bool) -> Outcome {
+	let executor = NativeElseWasmExecutor::<ExecutorDispatch>::new(Default::default(), None, 8, 2);
+	let code = WrappedRuntimeCode(wasm_binary().into());
+	let runtime_code = RuntimeCode {
+		code_fetcher: &code,
+		heap_pages: None,
+		hash: blake2_256(wasm_binary()).to_vec(),
+	};
+	let call = |ext: &mut TestExternalities, method: &str, data: &[u8]| {
+		let (result, native) =
+			executor.call(&mut ext.ext(), &runtime_code, method, data, use_native);
+		assert_eq!(native, use_native, "`{method}` did not run with the requested strategy");
+		result.unwrap_or_else(|error| panic!("`{method}` failed: {error}"))
+	};
+
+	let mut ext = TestExternalities::new(genesis_storage());
+	let genesis_hash = ext.execute_with(|| frame_system::Pallet::<Runtime>::block_hash(0));
+	let header =
+		Header::new(1, Default::default(), Default::default(), genesis_hash, Default::default());
+	call(&mut ext, "Core_initialize_block", &header.encode());
+
+	// Aura needs the timestamp to fall into the slot of the block, slot 0 without a digest.
+	let timestamp = UncheckedExtrinsic::new_unsigned(TimestampCall::set { now: 1 }.into());
+	let extrinsics = Some(timestamp).into_iter().chain(
+		groth16_calls()
+			.into_iter()
+			.enumerate()
+			.map(|(nonce, call)| signed(call, nonce as u32, genesis_hash)),
+	);
+	let results = extrinsics
+		.map(|extrinsic| {
+			let result = call(&mut ext, "BlockBuilder_apply_extrinsic", &extrinsic.encode());
+			ApplyExtrinsicResult::decode(&mut &result[..]).unwrap()
+		})
+		.collect();
+	let header =
+		Header::decode(&mut &call(&mut ext, "BlockBuilder_finalize_block", &[])[..]).unwrap();
+
+	let events = ext.execute_with(frame_system::Pallet::<Runtime>::events);
+	ext.commit_all().unwrap();
+	Outcome { results, events, header, storage_root: *ext.backend.root() }
+}
+
+#[test]
+fn groth16_calls_execute_the_same_natively_and_in_wasm() {
+	let native = execute(true);
+	let wasm = execute(false);
+
+	// The block must actually verify proofs for the comparison to mean anything.
+	assert!(
+		native.results.iter().all(Result::is_ok),
+		"an extrinsic is invalid: {:?}",
+		native.results
+	);
+	let failed = native.results.iter().filter(|result| matches!(result, Ok(Err(_)))).count();
+	assert_eq!(failed, FAILED_CALLS);
+	let verified = native
+		.events
+		.iter()
+		.filter(|record| {
+			matches!(
+				record.event,
+				RuntimeEvent::TemplateModule(pallet_template::Event::Groth16Verified { .. })
+			)
+		})
+		.count();
+	assert_eq!(verified, VERIFIED_EVENTS);
+	let sudid = native
+		.events
+		.iter()
+		.filter(|record| {
+			matches!(
+				record.event,
+				RuntimeEvent::Sudo(pallet_sudo::Event::Sudid { sudo_result: Ok(_) })
+			)
+		})
+		.count();
+	assert_eq!(sudid, SUDO_CALLS);
+
+	assert_eq!(native.results, wasm.results);
+	assert_eq!(native.events, wasm.events);
+	assert_eq!(native.header, wasm.header);
+	assert_eq!(native.storage_root, wasm.storage_root);
+}